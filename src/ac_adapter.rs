@@ -1,6 +1,10 @@
-use std::fs::read_dir;
+use std::convert::TryFrom;
 use std::path;
+use std::time;
 
+use crate::attrs;
+use crate::power_supply::PowerSupplyType;
+use crate::system::DeviceId;
 use crate::utils::*;
 
 /// An enumeration of the states that the AC adapter system can be in.
@@ -16,21 +20,53 @@ pub enum Status {
 pub struct ACAdapterInfo {
     /// The name used by ACPI to refer to the adapter.
     pub name: String,
+    /// The path to the adapter's directory.
+    pub path: path::PathBuf,
     /// Whether the adapter is plugged in and charging or not.
     pub status: Status,
+    /// The power the adapter can supply in W, e.g. 65 for a 65 W USB-C charger, if it reports its
+    /// power or its current and voltage limits. Barrel adapters generally don't.
+    pub power_watts: Option<f32>,
+    /// The time at which the adapter was read.
+    pub sampled_at: time::SystemTime,
 }
 
-/// Check the ACPI system for all AC adapters the OS knows about.
+/// Check the ACPI system for all AC adapters the OS knows about, which are the power supplies of
+/// type `Mains`.
 ///
 /// # Arguments
 ///
 /// * `path` - The path to AC adapter entries produced by the ACPI subsystem.
 pub fn get_ac_adapter_info(path: &path::Path) -> Result<Vec<ACAdapterInfo>, AcpiClientError> {
+    get_adapter_info(path, false)
+}
+
+/// Check the ACPI system for all AC adapters the OS knows about, optionally treating USB power
+/// supplies as adapters too. Other kinds of power supply, such as batteries and UPSes, are
+/// skipped.
+///
+/// # Arguments
+///
+/// * `path` - The path to AC adapter entries produced by the ACPI subsystem.
+/// * `include_usb` - Whether to include power supplies of type `USB`.
+pub fn get_adapter_info(
+    path: &path::Path,
+    include_usb: bool,
+) -> Result<Vec<ACAdapterInfo>, AcpiClientError> {
     let mut results: Vec<ACAdapterInfo> = vec![];
 
-    for entry in read_dir(&path)? {
-        let path = entry?.path();
-        if !determine_is_battery(parse_entry_file(&path.join("type"))?) {
+    for path in read_device_dirs(path)? {
+        // A device without a readable type can't be identified, so skip it
+        let device_type = match parse_entry_file(&path.join(attrs::TYPE)) {
+            Ok(device_type) => device_type,
+            Err(_) => continue,
+        };
+        let is_adapter = match PowerSupplyType::from(device_type.as_str()) {
+            PowerSupplyType::Mains => true,
+            PowerSupplyType::Usb => include_usb,
+            _ => false,
+        };
+        if is_adapter {
             let adapter = ACAdapterInfo::new(&path);
             if adapter.is_ok() {
                 results.push(adapter?);
@@ -41,6 +77,22 @@ pub fn get_ac_adapter_info(path: &path::Path) -> Result<Vec<ACAdapterInfo>, Acpi
     Ok(results)
 }
 
+impl TryFrom<&path::Path> for ACAdapterInfo {
+    type Error = AcpiClientError;
+
+    fn try_from(path: &path::Path) -> Result<ACAdapterInfo, AcpiClientError> {
+        ACAdapterInfo::new(path)
+    }
+}
+
+impl TryFrom<path::PathBuf> for ACAdapterInfo {
+    type Error = AcpiClientError;
+
+    fn try_from(path: path::PathBuf) -> Result<ACAdapterInfo, AcpiClientError> {
+        ACAdapterInfo::try_from(path.as_path())
+    }
+}
+
 impl ACAdapterInfo {
     /// Create a new AC adapter object from data from the ACPI subsystem.
     ///
@@ -48,22 +100,73 @@ impl ACAdapterInfo {
     ///
     /// * `path` - The path to the ACPI device.
     pub fn new(path: &path::Path) -> Result<ACAdapterInfo, AcpiClientError> {
+        let sampled_at = time::SystemTime::now();
         let name = get_device_name(path)?;
-        let status = parse_entry_file(&path.join("online"))?
+        let status = parse_entry_file(&path.join(attrs::ONLINE))?
             .trim()
             .to_lowercase();
-        let status = if status == "1" {
+        // Most drivers report a number, but a few spell the state out
+        let status = if status == "1" || status == "yes" || status == "true" {
             Status::Online
-        } else if status == "0" {
+        } else if status == "0" || status == "no" || status == "false" {
             Status::Offline
         } else {
-            return Err(AcpiClientError::InvalidInput(std::io::Error::new(
-                std::io::ErrorKind::Other,
+            return Err(AcpiClientError::InvalidInput(std::io::Error::other(
                 // Safe to unwrap path's string representation at this point as it's done earlier
                 format!("Unexpected value in {}", path.to_str().unwrap()),
             )));
         };
 
-        Ok(ACAdapterInfo { name, status })
+        let power_watts = read_power_watts(path);
+
+        Ok(ACAdapterInfo {
+            name,
+            path: path.to_path_buf(),
+            status,
+            power_watts,
+            sampled_at,
+        })
+    }
+
+    /// Create a new AC adapter object from the directory of a device named `name` within a class
+    /// directory, returning a `NotFound` error if there is no such device.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The path to the power supply entries produced by the ACPI subsystem.
+    /// * `name` - The name used by ACPI to refer to the device, e.g. `AC`.
+    pub fn from_root_and_name(
+        root: &path::Path,
+        name: &str,
+    ) -> Result<ACAdapterInfo, AcpiClientError> {
+        ACAdapterInfo::new(&device_path(root, name)?)
+    }
+
+    /// Returns whether the adapter is plugged in and charging.
+    pub fn is_online(&self) -> bool {
+        self.status == Status::Online
+    }
+
+    /// Returns the identity of the adapter, for use as a key across polls.
+    pub fn id(&self) -> DeviceId {
+        DeviceId(self.name.clone())
+    }
+}
+
+/// Reads the power an adapter can supply in W from its `power_now` attribute, or from the product
+/// of its input current and voltage limits if it doesn't report a positive `power_now`, returning
+/// `None` if it reports neither.
+///
+/// # Arguments
+///
+/// * `path` - The path to the ACPI device.
+fn read_power_watts(path: &path::Path) -> Option<f32> {
+    if let Ok(power) = parse_file_to_i32(&path.join(attrs::POWER_NOW), 1000) {
+        if power > 0 {
+            return Some(power as f32 / 1000.);
+        }
     }
+    let current = parse_file_to_i32(&path.join(attrs::INPUT_CURRENT_LIMIT), 1000).ok()?;
+    let voltage = parse_file_to_i32(&path.join(attrs::INPUT_VOLTAGE_LIMIT), 1000).ok()?;
+    Some(current as f32 * voltage as f32 / 1e6).filter(|power| *power > 0.)
 }
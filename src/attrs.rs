@@ -0,0 +1,190 @@
+/// The kind of power supply or thermal zone, e.g. `Battery` or `acpitz`.
+pub const TYPE: &str = "type";
+/// A file of `KEY=value` lines summarizing a power supply's attributes.
+pub const UEVENT: &str = "uevent";
+
+/// The charging status of a battery, e.g. `Discharging`.
+pub const STATUS: &str = "status";
+/// The present voltage of a battery in µV.
+pub const VOLTAGE_NOW: &str = "voltage_now";
+/// The averaged voltage of a battery in µV.
+pub const VOLTAGE_AVG: &str = "voltage_avg";
+/// The open-circuit voltage of a battery, i.e. its voltage without a load, in µV.
+pub const VOLTAGE_OCV: &str = "voltage_ocv";
+/// The nominal voltage of a battery as designed in µV.
+pub const VOLTAGE_MIN_DESIGN: &str = "voltage_min_design";
+/// The highest voltage of a battery as designed in µV.
+pub const VOLTAGE_MAX_DESIGN: &str = "voltage_max_design";
+/// The charge remaining in a battery in µAh.
+pub const CHARGE_NOW: &str = "charge_now";
+/// The charge remaining in a battery as counted by a coulomb counter in µAh, which is more precise
+/// than `CHARGE_NOW` on the fuel gauges which report it.
+pub const CHARGE_COUNTER: &str = "charge_counter";
+/// The charge of a battery when last fully charged in µAh.
+pub const CHARGE_FULL: &str = "charge_full";
+/// The charge of a battery at the time of manufacture in µAh.
+pub const CHARGE_FULL_DESIGN: &str = "charge_full_design";
+/// The energy remaining in a battery in µWh.
+pub const ENERGY_NOW: &str = "energy_now";
+/// The energy of a battery when last fully charged in µWh.
+pub const ENERGY_FULL: &str = "energy_full";
+/// The energy of a battery at the time of manufacture in µWh.
+pub const ENERGY_FULL_DESIGN: &str = "energy_full_design";
+/// The instantaneous current of a battery in µA.
+pub const CURRENT_NOW: &str = "current_now";
+/// The averaged current of a battery in µA.
+pub const CURRENT_AVG: &str = "current_avg";
+/// The instantaneous power of a battery in µW.
+pub const POWER_NOW: &str = "power_now";
+/// The averaged power of a battery in µW.
+pub const POWER_AVG: &str = "power_avg";
+/// The charge of a battery as a percentage computed by the driver.
+pub const CAPACITY: &str = "capacity";
+/// The time until a power supply is empty in seconds, as estimated by the device.
+pub const TIME_TO_EMPTY_NOW: &str = "time_to_empty_now";
+/// The coarse charge level of a battery, e.g. `Normal`.
+pub const CAPACITY_LEVEL: &str = "capacity_level";
+/// Whether a power supply powers the whole system, `System`, or only a peripheral, `Device`.
+pub const SCOPE: &str = "scope";
+/// The mode a battery is being charged in, e.g. `Fast`.
+pub const CHARGE_TYPE: &str = "charge_type";
+/// The health of a battery, e.g. `Good`.
+pub const HEALTH: &str = "health";
+/// The model name of a battery.
+pub const MODEL_NAME: &str = "model_name";
+/// The manufacturer of a battery.
+pub const MANUFACTURER: &str = "manufacturer";
+/// The serial number of a battery.
+pub const SERIAL_NUMBER: &str = "serial_number";
+/// The year a battery was manufactured in, e.g. `2019`.
+pub const MANUFACTURE_YEAR: &str = "manufacture_year";
+/// The month of the year a battery was manufactured in, from 1 to 12.
+pub const MANUFACTURE_MONTH: &str = "manufacture_month";
+/// The day of the month a battery was manufactured on, from 1 to 31.
+pub const MANUFACTURE_DAY: &str = "manufacture_day";
+/// The remaining charge or energy at which a battery raises a low battery alarm, in µAh or µWh
+/// matching the units the battery reports in.
+pub const ALARM: &str = "alarm";
+/// The percentage below which a battery starts charging.
+pub const CHARGE_CONTROL_START_THRESHOLD: &str = "charge_control_start_threshold";
+/// The percentage at which a battery stops charging.
+pub const CHARGE_CONTROL_END_THRESHOLD: &str = "charge_control_end_threshold";
+/// The older name of `CHARGE_CONTROL_START_THRESHOLD` used by some drivers.
+pub const CHARGE_START_THRESHOLD: &str = "charge_start_threshold";
+/// The older name of `CHARGE_CONTROL_END_THRESHOLD` used by some drivers.
+pub const CHARGE_STOP_THRESHOLD: &str = "charge_stop_threshold";
+/// The voltage a charger holds a battery at during constant voltage charging in µV.
+pub const CONSTANT_CHARGE_VOLTAGE: &str = "constant_charge_voltage";
+/// The highest constant charge voltage a battery supports in µV.
+pub const CONSTANT_CHARGE_VOLTAGE_MAX: &str = "constant_charge_voltage_max";
+/// The highest current a battery is rated for in µA.
+pub const CURRENT_MAX: &str = "current_max";
+/// The highest voltage a battery is rated for in µV.
+pub const VOLTAGE_MAX: &str = "voltage_max";
+/// The current a charger supplies to a deeply discharged battery before charging it normally in
+/// µA.
+pub const PRECHARGE_CURRENT: &str = "precharge_current";
+/// The current below which a charger considers a battery charged and stops charging it in µA.
+pub const CHARGE_TERM_CURRENT: &str = "charge_term_current";
+/// The number of charge cycles a battery has been through.
+pub const CYCLE_COUNT: &str = "cycle_count";
+/// The uncertainty of a battery's fuel gauge as a percentage.
+pub const CAPACITY_ERROR_MARGIN: &str = "capacity_error_margin";
+
+/// The key of a battery's charging status in its `uevent` file.
+pub const UEVENT_STATUS: &str = "POWER_SUPPLY_STATUS";
+/// The key of a power supply's internal name in its `uevent` file.
+pub const UEVENT_NAME: &str = "POWER_SUPPLY_NAME";
+/// The key of a battery's model name in its `uevent` file.
+pub const UEVENT_MODEL_NAME: &str = "POWER_SUPPLY_MODEL_NAME";
+
+/// The link from a power supply to the hardware device backing it.
+pub const DEVICE: &str = "device";
+/// The model of the hardware device backing a power supply, found beneath `DEVICE`.
+pub const DEVICE_MODEL: &str = "model";
+
+/// Whether an AC adapter is plugged in, either `1` or `0`.
+pub const ONLINE: &str = "online";
+/// The most current an adapter can supply in µA.
+pub const INPUT_CURRENT_LIMIT: &str = "input_current_limit";
+/// The voltage an adapter supplies at its current limit in µV.
+pub const INPUT_VOLTAGE_LIMIT: &str = "input_voltage_limit";
+
+/// The present temperature of a thermal zone in millidegrees Celsius, or of a battery in tenths of
+/// a degree Celsius.
+pub const TEMP: &str = "temp";
+/// The prefix of the hardware monitoring directories beneath a power supply, e.g. `hwmon2`.
+pub const HWMON_PREFIX: &str = "hwmon";
+/// The first temperature measured by a hardware monitor in millidegrees Celsius.
+pub const HWMON_TEMP1_INPUT: &str = "temp1_input";
+/// The temperature a thermal zone is emulating in millidegrees Celsius, or zero if it isn't.
+pub const EMUL_TEMP: &str = "emul_temp";
+/// Whether a thermal zone is monitored by the kernel, either `enabled` or `disabled`.
+pub const MODE: &str = "mode";
+/// The prefix of the links from a thermal zone to its bound cooling devices, e.g. `cdev0`.
+pub const CDEV_PREFIX: &str = "cdev";
+
+/// The present activity level of a cooling device.
+pub const CUR_STATE: &str = "cur_state";
+/// The highest activity level of a cooling device.
+pub const MAX_STATE: &str = "max_state";
+/// The number of times a cooling device has changed state, found beneath its `stats` directory.
+pub const STATS_TOTAL_TRANS: &str = "stats/total_trans";
+
+/// The prefix of the powercap entries of RAPL domains, e.g. `intel-rapl:0`.
+pub const RAPL_PREFIX: &str = "intel-rapl:";
+/// The part of the system a RAPL domain measures, e.g. `package-0`.
+pub const RAPL_NAME: &str = "name";
+/// The energy consumed by a RAPL domain in µJ, which wraps around at `MAX_ENERGY_RANGE_UJ`.
+pub const ENERGY_UJ: &str = "energy_uj";
+/// The value at which a RAPL domain's energy counter wraps around to zero in µJ.
+pub const MAX_ENERGY_RANGE_UJ: &str = "max_energy_range_uj";
+
+/// Returns the name of the file holding the temperature of a thermal zone's trip point in
+/// millidegrees Celsius.
+///
+/// # Arguments
+///
+/// * `number` - The numerical id of the trip point.
+pub fn trip_point_temp(number: u8) -> String {
+    format!("trip_point_{}_temp", number)
+}
+
+/// Returns the name of the file holding the number of the trip point a thermal zone's bound
+/// cooling device responds to, or -1 if it doesn't respond to a particular trip point.
+///
+/// # Arguments
+///
+/// * `index` - The N of the zone's `cdevN` link to the cooling device.
+pub fn cdev_trip_point(index: u32) -> String {
+    format!("{}{}_trip_point", CDEV_PREFIX, index)
+}
+
+/// Returns the name of the file holding a temperature measured by a hardware monitor in
+/// millidegrees Celsius.
+///
+/// # Arguments
+///
+/// * `index` - The M of the monitor's `tempM_input` file.
+pub fn hwmon_temp_input(index: u32) -> String {
+    format!("temp{}_input", index)
+}
+
+/// Returns the name of the file holding the label of a temperature measured by a hardware
+/// monitor, e.g. `Core 0`.
+///
+/// # Arguments
+///
+/// * `index` - The M of the monitor's `tempM_input` file.
+pub fn hwmon_temp_label(index: u32) -> String {
+    format!("temp{}_label", index)
+}
+
+/// Returns the name of the file holding the action type of a thermal zone's trip point.
+///
+/// # Arguments
+///
+/// * `number` - The numerical id of the trip point.
+pub fn trip_point_type(number: u8) -> String {
+    format!("trip_point_{}_type", number)
+}
@@ -1,63 +1,1533 @@
-use std::fs;
+use std::collections::{BTreeMap, VecDeque};
+use std::convert::TryFrom;
+use std::fmt;
+use std::io;
 use std::path;
+use std::sync::Arc;
 use std::time;
 
+use crate::attrs;
+use crate::capacity::*;
+use crate::source::*;
+use crate::system::DeviceId;
 use crate::utils::*;
 
 /// Different possible battery charging states.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum ChargingState {
     Charging,
     Discharging,
     Full,
+    /// The battery is plugged in but neither charging nor discharging, e.g. because it has
+    /// reached a charge stop threshold.
+    NotCharging,
+    /// The battery reports its state as unknown and the direction of its rate couldn't be used to
+    /// infer one.
+    Unknown,
+}
+
+impl fmt::Display for ChargingState {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match *self {
+            ChargingState::Charging => write!(f, "Charging"),
+            ChargingState::Discharging => write!(f, "Discharging"),
+            ChargingState::Full => write!(f, "Full"),
+            ChargingState::NotCharging => write!(f, "Not charging"),
+            ChargingState::Unknown => write!(f, "Unknown"),
+        }
+    }
+}
+
+/// The units a battery's present rate is reported in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RateUnit {
+    /// The rate is a current in mA.
+    MilliAmps,
+    /// The rate is a power in mW.
+    MilliWatts,
+}
+
+/// The units a battery's capacities are reported in.
+///
+/// Batteries report either a charge or an energy. A charge in mAh is converted to an energy in mWh
+/// by multiplying by the present voltage in V, and an energy is converted to a charge by dividing
+/// by it, so capacities reported in the battery's own units are exact while converted capacities
+/// depend on the present voltage.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum CapacityUnit {
+    /// The capacities are charges in mAh.
+    MilliAmpHours,
+    /// The capacities are energies in mWh.
+    MilliWattHours,
+}
+
+/// The ways a battery's percentage can be computed, tried in the order given to
+/// `BatteryReader::percentage_sources` until one is available.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PercentageSource {
+    /// The remaining and last full capacities the rest of the battery's measurements are read
+    /// from, which are charges or energies depending on what the battery reports.
+    Measured,
+    /// The `charge_now` and `charge_full` attributes.
+    Charge,
+    /// The `energy_now` and `energy_full` attributes.
+    Energy,
+    /// The percentage computed by the driver in the `capacity` attribute.
+    CapacityAttribute,
+}
+
+/// The order percentage sources are tried in by default: the battery's measured capacities, so
+/// the percentage agrees with the capacities reported alongside it, then the driver's `capacity`
+/// attribute for batteries which don't report a last full capacity.
+pub const DEFAULT_PERCENTAGE_SOURCES: [PercentageSource; 2] = [
+    PercentageSource::Measured,
+    PercentageSource::CapacityAttribute,
+];
+
+/// A normalized, high-level state of a battery, derived from its charging state, percentage, and
+/// charge thresholds, see `BatteryInfo::high_level_state`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum BatteryState {
+    Charging,
+    Discharging,
+    /// The battery is charged to 100%.
+    FullyCharged,
+    /// The battery has stopped charging short of 100% because of its charge thresholds.
+    HeldByThreshold,
+    /// The battery's state couldn't be determined.
+    Unknown,
+}
+
+/// The coarse charge level reported by a battery's `capacity_level` attribute, ordered from the
+/// least to the most charged.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum CapacityLevel {
+    Critical,
+    Low,
+    Normal,
+    High,
+    Full,
+}
+
+/// The health of a battery as reported by its `health` attribute.
+#[derive(Clone, Debug, PartialEq)]
+pub enum BatteryHealthStatus {
+    Good,
+    Overheat,
+    Dead,
+    OverVoltage,
+    UnspecifiedFailure,
+    Cold,
+    Warm,
+    Cool,
+    Hot,
+    /// Any other health, holding the string reported by the system.
+    Other(String),
+}
+
+impl From<&str> for BatteryHealthStatus {
+    fn from(health: &str) -> BatteryHealthStatus {
+        match health.trim().to_lowercase().as_str() {
+            "good" => BatteryHealthStatus::Good,
+            "overheat" => BatteryHealthStatus::Overheat,
+            "dead" => BatteryHealthStatus::Dead,
+            "over voltage" => BatteryHealthStatus::OverVoltage,
+            "unspecified failure" => BatteryHealthStatus::UnspecifiedFailure,
+            "cold" => BatteryHealthStatus::Cold,
+            "warm" => BatteryHealthStatus::Warm,
+            "cool" => BatteryHealthStatus::Cool,
+            "hot" => BatteryHealthStatus::Hot,
+            _ => BatteryHealthStatus::Other(String::from(health.trim())),
+        }
+    }
+}
+
+/// The mode a battery is being charged in as reported by its `charge_type` attribute, which
+/// explains why the charge rate varies.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ChargeType {
+    Fast,
+    Standard,
+    Trickle,
+    /// Any other mode, holding the string reported by the system.
+    Other(String),
+}
+
+impl From<&str> for ChargeType {
+    fn from(charge_type: &str) -> ChargeType {
+        match charge_type.trim().to_lowercase().as_str() {
+            "fast" => ChargeType::Fast,
+            "standard" => ChargeType::Standard,
+            "trickle" => ChargeType::Trickle,
+            _ => ChargeType::Other(String::from(charge_type.trim())),
+        }
+    }
 }
 
 /// Metadata pertaining to a battery.
+#[derive(Clone, Debug, PartialEq)]
 pub struct BatteryInfo {
     /// The name used by ACPI to refer to the device.
     pub name: String,
-    /// The charge remaining in the battery in units of mAh.
-    pub remaining_capacity: u32,
-    /// The rate at which the charge of the battery is changing in mA.
+    /// The path to the device's directory.
+    pub path: path::PathBuf,
+    /// The model name reported by the battery, if it reports one.
+    pub model_name: Option<String>,
+    /// The manufacturer reported by the battery, if it reports one.
+    pub manufacturer: Option<String>,
+    /// The serial number reported by the battery, if it reports one.
+    pub serial_number: Option<String>,
+    /// The date the battery was manufactured as `YYYY-MM-DD`, if it reports one. Batteries which
+    /// only report the year or month have the remaining parts omitted, e.g. `2019-04`.
+    pub manufacture_date: Option<String>,
+    /// The name the kernel gives the battery in its `uevent` file, if it has one. This normally
+    /// matches `name`, see `has_name_mismatch`.
+    pub uevent_name: Option<String>,
+    /// The charge remaining in the battery in the units given by `capacity_unit`. For a battery
+    /// reporting energy read in mAh, this is computed from its `capacity` attribute and
+    /// `last_capacity` if it reports one.
+    pub remaining_capacity: Capacity,
+    /// The rate at which the charge of the battery is changing in the units given by
+    /// `present_rate_unit`, or zero if the battery doesn't report a rate.
     pub present_rate: u32,
-    /// The current voltage of the battery in mV.
+    /// The units of the present rate, which is a power for batteries reporting `power_now`.
+    pub present_rate_unit: RateUnit,
+    /// The current voltage of the battery in mV, averaged if the battery reports `voltage_avg`.
     pub voltage: u32,
-    /// The charge available in the battery at the time of manufacture in units of mAh.
-    pub design_capacity: u32,
-    /// The charge available in the battery at the last time the device was charged to full in
-    /// units of mAh.
-    pub last_capacity: u32,
-    /// The time remaining until the battery reaches full charge or empty.
-    pub time_remaining: time::Duration,
+    /// The nominal voltage of the battery as designed in mV, if the battery reports it.
+    pub design_voltage: Option<u32>,
+    /// The highest voltage of the battery as designed in mV, if the battery reports it.
+    pub design_voltage_max: Option<u32>,
+    /// The open-circuit voltage of the battery in mV, if the battery reports it. Unlike `voltage`
+    /// this doesn't sag under load, so it tracks the state of charge more closely.
+    pub voltage_ocv: Option<u32>,
+    /// The charge available in the battery at the time of manufacture in the units given by
+    /// `capacity_unit`.
+    pub design_capacity: Capacity,
+    /// The charge available in the battery at the last time the device was charged to full in the
+    /// units given by `capacity_unit`.
+    pub last_capacity: Capacity,
+    /// The units of the capacities, which are mAh unless the reader was configured otherwise.
+    pub capacity_unit: CapacityUnit,
+    /// The time remaining until the battery reaches full charge or empty, or `None` if the battery
+    /// doesn't report the rate at which its charge is changing or reports a rate of zero while
+    /// charging.
+    pub time_remaining: Option<time::Duration>,
     /// The ratio of the remaining charge to the full charge.
     pub percentage: f32,
     /// The state of the battery's charging.
     pub state: ChargingState,
+    /// The status the battery reported, trimmed but otherwise as read, or empty if it reports
+    /// none. A status which isn't recognized leaves `state` as `Unknown` but is kept here for
+    /// debugging new firmware.
+    pub raw_status: String,
+    /// The coarse charge level reported by the battery, if it reports a known one.
+    pub capacity_level: Option<CapacityLevel>,
+    /// The health reported by the battery, or `None` if it doesn't report one or reports it as
+    /// unknown.
+    pub reported_health: Option<BatteryHealthStatus>,
+    /// The mode the battery is being charged in, or `None` if it doesn't report one or reports it
+    /// as unknown or not applicable.
+    pub charge_type: Option<ChargeType>,
+    /// The percentage below which the battery starts charging, if the device limits charging.
+    pub charge_start_threshold: Option<u8>,
+    /// The percentage at which the battery stops charging, if the device limits charging.
+    pub charge_stop_threshold: Option<u8>,
+    /// The uncertainty of the battery's fuel gauge in percentage points, if the battery reports
+    /// it, for showing error bars on the percentage.
+    pub capacity_error_margin: Option<u8>,
+    /// The temperature of the battery in degrees Celsius, if it reports one either directly or
+    /// through a linked hardware monitor.
+    pub temperature: Option<f32>,
+    /// The voltage the charger holds the battery at during constant voltage charging in mV, if
+    /// the battery reports it.
+    pub constant_charge_voltage: Option<u32>,
+    /// The highest constant charge voltage the battery supports in mV, if the battery reports it.
+    pub constant_charge_voltage_max: Option<u32>,
+    /// The highest current the battery is rated for in mA, if the battery reports it.
+    pub current_max: Option<u32>,
+    /// The highest voltage the battery is rated for in mV, if the battery reports it.
+    pub voltage_max: Option<u32>,
+    /// The current the charger supplies to the battery while it is deeply discharged in mA, if
+    /// the battery reports it.
+    pub precharge_current: Option<u32>,
+    /// The current below which the charger stops charging the battery in mA, if the battery
+    /// reports it.
+    pub charge_term_current: Option<u32>,
+    /// The number of charge cycles the battery has been through, if the battery reports it.
+    pub cycle_count: Option<u32>,
+    /// The charge remaining in the battery as counted by its coulomb counter in µAh, if the
+    /// battery reports a count which isn't negative. For a battery reporting charge this is
+    /// preferred over `charge_now` for `remaining_capacity`.
+    pub charge_counter: Option<i32>,
+    /// The time at which the battery was read.
+    pub sampled_at: time::SystemTime,
+    /// The energy available in the battery at the time of manufacture in units of mWh, if the
+    /// battery reports energy rather than charge.
+    pub design_energy: Option<u32>,
+    /// The energy available in the battery at the last time the device was charged to full in
+    /// units of mWh, if the battery reports energy rather than charge.
+    pub last_energy: Option<u32>,
+    /// The energy remaining in the battery in units of mWh, if the battery reports energy rather
+    /// than charge.
+    pub remaining_energy: Option<u32>,
+}
+
+impl TryFrom<&path::Path> for BatteryInfo {
+    type Error = AcpiClientError;
+
+    fn try_from(path: &path::Path) -> Result<BatteryInfo, AcpiClientError> {
+        BatteryInfo::new(path)
+    }
+}
+
+impl TryFrom<path::PathBuf> for BatteryInfo {
+    type Error = AcpiClientError;
+
+    fn try_from(path: path::PathBuf) -> Result<BatteryInfo, AcpiClientError> {
+        BatteryInfo::try_from(path.as_path())
+    }
+}
+
+impl fmt::Display for BatteryInfo {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}: {}, {:.1}%", self.name, self.state, self.percentage)?;
+        match (self.state, self.time_remaining) {
+            (ChargingState::Charging, Some(time_remaining)) => {
+                let minutes = time_remaining.as_secs() / 60;
+                write!(f, ", {}:{:02} until charged", minutes / 60, minutes % 60)
+            }
+            (ChargingState::Discharging, Some(time_remaining)) => {
+                let minutes = time_remaining.as_secs() / 60;
+                write!(f, ", {}:{:02} remaining", minutes / 60, minutes % 60)
+            }
+            _ => Ok(()),
+        }
+    }
+}
+
+/// The changes between two readings of the same battery.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BatteryDiff {
+    /// The previous and current charging states, if the state changed between the readings.
+    pub state_transition: Option<(ChargingState, ChargingState)>,
+    /// The change in the percentage of full charge since the previous reading.
+    pub percentage_delta: f32,
+    /// The change in the present rate since the previous reading, in the units given by the
+    /// battery's `present_rate_unit`: mA, or mW for batteries reporting `power_now`.
+    pub rate_delta: i64,
+}
+
+/// Tracks successive readings of a battery to detect a fuel gauge which has stopped updating,
+/// i.e. one reporting the same remaining capacity and present rate for many polls while the
+/// battery is neither full nor idle.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StuckDetector {
+    polls: usize,
+    previous: Option<(u32, u32)>,
+    unchanged: usize,
+}
+
+impl StuckDetector {
+    /// Create a new detector.
+    ///
+    /// # Arguments
+    ///
+    /// * `polls` - The number of successive unchanged readings after which the gauge is stuck.
+    pub fn new(polls: usize) -> StuckDetector {
+        StuckDetector {
+            polls: polls.max(1),
+            previous: None,
+            unchanged: 0,
+        }
+    }
+
+    /// Records a new reading of the battery and returns whether the gauge appears stuck.
+    ///
+    /// # Arguments
+    ///
+    /// * `battery` - The latest reading of the battery.
+    pub fn update(&mut self, battery: &BatteryInfo) -> bool {
+        let values = (battery.remaining_capacity.value(), battery.present_rate);
+        let active = battery.state != ChargingState::Full && battery.present_rate != 0;
+        if active && self.previous == Some(values) {
+            self.unchanged += 1;
+        } else {
+            self.unchanged = 0;
+        }
+        self.previous = if active { Some(values) } else { None };
+        self.is_stuck()
+    }
+
+    /// Returns whether the readings recorded so far haven't changed for the configured number of
+    /// polls.
+    pub fn is_stuck(&self) -> bool {
+        self.unchanged >= self.polls
+    }
+}
+
+/// The characters `PercentageHistory::sparkline` draws percentages with, from emptiest to fullest.
+pub const SPARKLINE_BLOCKS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// Records a battery's percentage over successive readings, keeping the most recent ones, for
+/// drawing a small graph of its charge in a status bar.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PercentageHistory {
+    capacity: usize,
+    percentages: VecDeque<f32>,
+}
+
+impl PercentageHistory {
+    /// Create a new, empty history.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The number of readings to keep, after which the oldest are dropped.
+    pub fn new(capacity: usize) -> PercentageHistory {
+        PercentageHistory {
+            capacity: capacity.max(1),
+            percentages: VecDeque::new(),
+        }
+    }
+
+    /// Records a new reading of the battery.
+    ///
+    /// # Arguments
+    ///
+    /// * `battery` - The latest reading of the battery.
+    pub fn update(&mut self, battery: &BatteryInfo) {
+        if self.percentages.len() == self.capacity {
+            self.percentages.pop_front();
+        }
+        self.percentages.push_back(battery.percentage);
+    }
+
+    /// Returns the recorded percentages from oldest to newest.
+    pub fn percentages(&self) -> Vec<f32> {
+        self.percentages.iter().copied().collect()
+    }
+
+    /// Returns the recorded percentages from oldest to newest drawn as a sparkline, one of
+    /// `SPARKLINE_BLOCKS` per reading, with each block covering an eighth of the range from 0 to
+    /// 100%.
+    pub fn sparkline(&self) -> String {
+        self.percentages
+            .iter()
+            .map(|percentage| {
+                let index = (percentage.clamp(0.0, 100.0) / 12.5) as usize;
+                SPARKLINE_BLOCKS[index.min(SPARKLINE_BLOCKS.len() - 1)]
+            })
+            .collect()
+    }
+}
+
+/// Records the changes in a battery's state over successive readings, keeping the most recent ones,
+/// for debugging erratic charging.
+#[derive(Clone, Debug, PartialEq)]
+pub struct StateHistory {
+    capacity: usize,
+    transitions: VecDeque<(time::SystemTime, ChargingState)>,
+}
+
+impl StateHistory {
+    /// Create a new, empty history.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The number of transitions to keep, after which the oldest are dropped.
+    pub fn new(capacity: usize) -> StateHistory {
+        StateHistory {
+            capacity: capacity.max(1),
+            transitions: VecDeque::new(),
+        }
+    }
+
+    /// Records a new reading of the battery, adding a transition if its state differs from the
+    /// last recorded one. The first reading is always recorded. Returns whether a transition was
+    /// added.
+    ///
+    /// # Arguments
+    ///
+    /// * `battery` - The latest reading of the battery.
+    pub fn update(&mut self, battery: &BatteryInfo) -> bool {
+        if self
+            .transitions
+            .back()
+            .is_some_and(|(_, state)| *state == battery.state)
+        {
+            return false;
+        }
+        if self.transitions.len() == self.capacity {
+            self.transitions.pop_front();
+        }
+        self.transitions
+            .push_back((battery.sampled_at, battery.state));
+        true
+    }
+
+    /// Returns the recorded transitions from oldest to newest, each being the time of the reading
+    /// the battery was first seen in a state and the state.
+    pub fn transitions(&self) -> impl Iterator<Item = &(time::SystemTime, ChargingState)> {
+        self.transitions.iter()
+    }
+}
+
+/// The direction a battery's present rate is moving in.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Trend {
+    Increasing,
+    Steady,
+    Decreasing,
+}
+
+/// Tracks the present rate over successive readings of a battery to tell whether the power draw
+/// is trending up, e.g. as a workload starts, or down. The average rate of the newer half of a
+/// window of readings is compared against the average of the older half.
+#[derive(Clone, Debug, PartialEq)]
+pub struct PowerTrend {
+    window: usize,
+    threshold: u32,
+    rates: VecDeque<u32>,
+}
+
+impl PowerTrend {
+    /// Create a new tracker.
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - The number of most recent readings to consider, at least two.
+    /// * `threshold` - The change in average rate, in the units of the battery's present rate,
+    ///   beyond which the rate is no longer considered steady.
+    pub fn new(window: usize, threshold: u32) -> PowerTrend {
+        PowerTrend {
+            window: window.max(2),
+            threshold,
+            rates: VecDeque::new(),
+        }
+    }
+
+    /// Records a new reading of the battery and returns the trend of its present rate.
+    ///
+    /// # Arguments
+    ///
+    /// * `battery` - The latest reading of the battery.
+    pub fn update(&mut self, battery: &BatteryInfo) -> Trend {
+        if self.rates.len() == self.window {
+            self.rates.pop_front();
+        }
+        self.rates.push_back(battery.present_rate);
+        self.trend()
+    }
+
+    /// Returns the trend of the present rate over the readings recorded so far, which is steady
+    /// until at least two readings have been recorded.
+    pub fn trend(&self) -> Trend {
+        let half = self.rates.len() / 2;
+        if half == 0 {
+            return Trend::Steady;
+        }
+        let average = |rates: &mut dyn Iterator<Item = &u32>| {
+            rates.map(|rate| f64::from(*rate)).sum::<f64>() / half as f64
+        };
+        let older = average(&mut self.rates.iter().take(half));
+        let newer = average(&mut self.rates.iter().rev().take(half));
+        let threshold = f64::from(self.threshold);
+        if newer - older > threshold {
+            Trend::Increasing
+        } else if older - newer > threshold {
+            Trend::Decreasing
+        } else {
+            Trend::Steady
+        }
+    }
+}
+
+/// Tracks successive readings of a battery to estimate how long ago it was last fully charged.
+#[derive(Clone, Debug, PartialEq)]
+pub struct LastFullTracker {
+    last_full: Option<time::SystemTime>,
+    latest: Option<time::SystemTime>,
+}
+
+impl Default for LastFullTracker {
+    fn default() -> LastFullTracker {
+        LastFullTracker::new()
+    }
+}
+
+impl LastFullTracker {
+    /// Create a new tracker which hasn't seen the battery full.
+    pub fn new() -> LastFullTracker {
+        LastFullTracker {
+            last_full: None,
+            latest: None,
+        }
+    }
+
+    /// Records a new reading of the battery, noting its time if the battery is full, i.e. its
+    /// state is `Full` or its percentage rounds to 100.
+    ///
+    /// # Arguments
+    ///
+    /// * `battery` - The latest reading of the battery.
+    pub fn update(&mut self, battery: &BatteryInfo) {
+        // Fuel gauges often settle just below 100% once charging ends
+        if battery.state == ChargingState::Full || battery.percentage >= 99.5 {
+            self.last_full = Some(battery.sampled_at);
+        }
+        self.latest = Some(battery.sampled_at);
+    }
+
+    /// Returns the time between the last reading in which the battery was full and the latest
+    /// reading, or `None` if no reading recorded so far was full.
+    pub fn time_since_full(&self) -> Option<time::Duration> {
+        let last_full = self.last_full?;
+        let latest = self.latest?;
+        Some(
+            latest
+                .duration_since(last_full)
+                .unwrap_or_else(|_| time::Duration::new(0, 0)),
+        )
+    }
+}
+
+/// The errors encountered parsing each device during a scan, keyed by the device's name.
+pub type DeviceErrors = Vec<(String, AcpiClientError)>;
+
+/// Returns a vector of data on power supplies in the system or any errors encountered.
+///
+/// Batteries which fail to parse are skipped, so the result is empty both for a system without
+/// batteries and for one whose batteries all fail to parse. Use `get_battery_info_with_errors` to
+/// tell the two apart. A battery which is removed between listing the directory and parsing it
+/// is treated as never having been listed.
+///
+/// # Arguments
+///
+/// * `path` - The path to battery entries produced by the ACPI subsystem.
+pub fn get_battery_info(path: &path::Path) -> Result<Vec<BatteryInfo>, AcpiClientError> {
+    BatteryReader::new().read_all(path)
+}
+
+/// Check the ACPI system for all batteries the OS knows about, returning the error of each battery
+/// which fails to parse alongside the batteries which parse. A system without batteries gives no
+/// batteries and no errors.
+///
+/// # Arguments
+///
+/// * `path` - The path to battery entries produced by the ACPI subsystem.
+pub fn get_battery_info_with_errors(
+    path: &path::Path,
+) -> Result<(Vec<BatteryInfo>, DeviceErrors), AcpiClientError> {
+    BatteryReader::new().read_all_with_errors(path)
+}
+
+/// Check the ACPI system for the batteries whose names match a glob pattern, e.g. `BAT*`. See
+/// `name_matches` for the supported wildcards. Returns an empty list if no battery matches.
+///
+/// # Arguments
+///
+/// * `path` - The path to battery entries produced by the ACPI subsystem.
+/// * `pattern` - The pattern to match the names of the batteries against.
+pub fn get_batteries_matching(
+    path: &path::Path,
+    pattern: &str,
+) -> Result<Vec<BatteryInfo>, AcpiClientError> {
+    Ok(get_battery_info(path)?
+        .into_iter()
+        .filter(|battery| name_matches(pattern, &battery.name))
+        .collect())
+}
+
+/// Returns the battery powering the system, which is the first battery by name that isn't the
+/// battery of a peripheral such as a wireless mouse, or `None` if the system has no batteries.
+///
+/// # Arguments
+///
+/// * `path` - The path to battery entries produced by the ACPI subsystem.
+pub fn primary_battery(path: &path::Path) -> Result<Option<BatteryInfo>, AcpiClientError> {
+    let mut batteries = get_battery_info(path)?;
+    batteries.sort_by(|a, b| a.name.cmp(&b.name));
+    let primary = batteries.into_iter().find(|battery| {
+        parse_entry_file(&path.join(&battery.name).join(attrs::SCOPE))
+            .map_or(true, |scope| !scope.eq_ignore_ascii_case("device"))
+    });
+    Ok(primary)
+}
+
+/// Blocks until a battery's charge crosses a target percentage, polling it at a fixed interval.
+/// A charging or full battery is waited on until it reaches at least the target, and a
+/// discharging battery until it falls to at most the target.
+///
+/// # Arguments
+///
+/// * `path` - The path to the ACPI device.
+/// * `target` - The percentage of full charge to wait for.
+/// * `poll` - The time to wait between readings of the battery.
+/// * `timeout` - The longest time to wait before returning a `TimedOut` error. A timeout too long
+///   to represent, such as `Duration::MAX`, waits without a deadline.
+pub fn wait_for_percentage(
+    path: &path::Path,
+    target: f32,
+    poll: time::Duration,
+    timeout: time::Duration,
+) -> Result<(), AcpiClientError> {
+    BatteryReader::new().wait_for_percentage(path, target, poll, timeout)
 }
 
-/// Returns a vector of data on power supplies in the system or any errors encountered.
-///
-/// # Arguments
-///
-/// * `path` - The path to battery entries produced by the ACPI subsystem.
-pub fn get_battery_info(path: &path::Path) -> Result<Vec<BatteryInfo>, AcpiClientError> {
-    let mut results: Vec<BatteryInfo> = vec![];
+impl BatteryInfo {
+    /// Returns a battery corresponding to a given ACPI device path.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the ACPI device.
+    ///
+    /// # Example
+    /// ```
+    /// let directory = std::path::Path::new("/sys/class/power_supply/BAT1");
+    /// let ps_info = acpi_client::BatteryInfo::new(&directory);
+    /// ```
+    pub fn new(path: &path::Path) -> Result<BatteryInfo, AcpiClientError> {
+        BatteryReader::new().read(path)
+    }
+
+    /// Create a new battery object from the directory of a device named `name` within a class
+    /// directory, returning a `NotFound` error if there is no such device.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The path to the power supply entries produced by the ACPI subsystem.
+    /// * `name` - The name used by ACPI to refer to the device, e.g. `BAT0`.
+    pub fn from_root_and_name(
+        root: &path::Path,
+        name: &str,
+    ) -> Result<BatteryInfo, AcpiClientError> {
+        BatteryInfo::new(&device_path(root, name)?)
+    }
+
+    /// Returns the identity of the battery, for use as a key across polls.
+    pub fn id(&self) -> DeviceId {
+        DeviceId(self.name.clone())
+    }
+
+    /// Returns whether two readings are of the same physical battery, for detecting when a battery
+    /// has been swapped between polls even though it keeps the same name, e.g. `BAT0`. The
+    /// readings are compared by their serial numbers and manufacture dates, and are assumed to be
+    /// of the same battery if neither reading reports either.
+    ///
+    /// # Arguments
+    ///
+    /// * `other` - Another reading of a battery.
+    pub fn is_same_physical_battery(&self, other: &BatteryInfo) -> bool {
+        fn matches(a: &Option<String>, b: &Option<String>) -> bool {
+            match (a, b) {
+                (Some(a), Some(b)) => a == b,
+                _ => true,
+            }
+        }
+        self.name == other.name
+            && matches(&self.serial_number, &other.serial_number)
+            && matches(&self.manufacture_date, &other.manufacture_date)
+    }
+
+    /// Returns a label for displaying the battery, combining its manufacturer, model name, and
+    /// name, e.g. `SANYO 45N1773 (BAT0)`. The manufacturer and model name are omitted if the
+    /// battery doesn't report them, leaving just the name when it reports neither.
+    pub fn label(&self) -> String {
+        let description: Vec<&str> = [&self.manufacturer, &self.model_name]
+            .iter()
+            .filter_map(|field| field.as_deref())
+            .map(str::trim)
+            .filter(|field| !field.is_empty())
+            .collect();
+        if description.is_empty() {
+            self.name.clone()
+        } else {
+            format!("{} ({})", description.join(" "), self.name)
+        }
+    }
+
+    /// Returns the ratio of the remaining charge to the charge the battery held at the time of
+    /// manufacture as a percentage, clamped to 100. Unlike `percentage` this accounts for wear.
+    pub fn percentage_of_design(&self) -> f32 {
+        determine_charge_percentage(
+            self.remaining_capacity.value(),
+            self.design_capacity.value(),
+        )
+        .min(100.0)
+    }
+
+    /// Returns the ratio of the remaining charge to the full charge as a fraction from 0.0 to 1.0,
+    /// for rendering code such as progress bars.
+    pub fn fraction(&self) -> f32 {
+        (self.percentage / 100.).clamp(0.0, 1.0)
+    }
+
+    /// Returns the energy remaining in the battery in Wh. This is read directly for batteries which
+    /// report energy, otherwise it is computed from the remaining capacity and present voltage.
+    pub fn remaining_energy_wh(&self) -> f32 {
+        match self.remaining_energy {
+            Some(remaining_energy) => remaining_energy as f32 / 1000.,
+            None => determine_energy_wh(
+                self.remaining_capacity.value(),
+                self.capacity_unit,
+                self.voltage,
+            ),
+        }
+    }
+
+    /// Returns the energy the battery was designed to hold in Wh, for comparison with the
+    /// manufacturer's specification. This is read directly for batteries which report energy,
+    /// otherwise it is computed from the design capacity and the nominal design voltage, falling
+    /// back to the present voltage if the battery doesn't report a design voltage.
+    pub fn design_energy_wh(&self) -> f32 {
+        match self.design_energy {
+            Some(design_energy) => design_energy as f32 / 1000.,
+            None => determine_energy_wh(
+                self.design_capacity.value(),
+                self.capacity_unit,
+                self.design_voltage.unwrap_or(self.voltage),
+            ),
+        }
+    }
+
+    /// Returns the remaining capacity as a plain number in the units given by `capacity_unit`.
+    pub fn remaining_capacity_raw(&self) -> u32 {
+        self.remaining_capacity.value()
+    }
+
+    /// Returns the last full capacity as a plain number in the units given by `capacity_unit`.
+    pub fn last_capacity_raw(&self) -> u32 {
+        self.last_capacity.value()
+    }
+
+    /// Returns the design capacity as a plain number in the units given by `capacity_unit`.
+    pub fn design_capacity_raw(&self) -> u32 {
+        self.design_capacity.value()
+    }
+
+    /// Returns the charge remaining in the battery, converted at the present voltage if the
+    /// battery reports energy.
+    pub fn remaining_charge(&self) -> MilliAmpHours {
+        self.remaining_capacity.to_milli_amp_hours(self.voltage)
+    }
+
+    /// Returns the energy remaining in the battery, converted at the present voltage if the
+    /// battery reports charge.
+    pub fn remaining_energy_mwh(&self) -> MilliWattHours {
+        self.remaining_capacity.to_milli_watt_hours(self.voltage)
+    }
+
+    /// Returns the charge the battery held the last time it was charged to full, converted at the
+    /// present voltage if the battery reports energy.
+    pub fn last_charge(&self) -> MilliAmpHours {
+        self.last_capacity.to_milli_amp_hours(self.voltage)
+    }
+
+    /// Returns the energy the battery held the last time it was charged to full, converted at the
+    /// present voltage if the battery reports charge.
+    pub fn last_energy_mwh(&self) -> MilliWattHours {
+        self.last_capacity.to_milli_watt_hours(self.voltage)
+    }
+
+    /// Returns the charge the battery held at the time of manufacture, converted at the present
+    /// voltage if the battery reports energy.
+    pub fn design_charge(&self) -> MilliAmpHours {
+        self.design_capacity.to_milli_amp_hours(self.voltage)
+    }
+
+    /// Returns the energy the battery held at the time of manufacture, converted at the present
+    /// voltage if the battery reports charge.
+    pub fn design_energy_mwh(&self) -> MilliWattHours {
+        self.design_capacity.to_milli_watt_hours(self.voltage)
+    }
+
+    /// Returns whether the battery's voltage lies within its design voltages, widened by
+    /// `VOLTAGE_TOLERANCE` on either side, or `None` if the battery doesn't report both design
+    /// voltages. A voltage far outside them is likely a bogus reading, such as those some drivers
+    /// report just after resuming from suspend, and the sample can be discarded.
+    pub fn voltage_is_plausible(&self) -> Option<bool> {
+        let minimum = self.design_voltage? as f32 * (1. - VOLTAGE_TOLERANCE);
+        let maximum = self.design_voltage_max? as f32 * (1. + VOLTAGE_TOLERANCE);
+        let voltage = self.voltage as f32;
+        Some(voltage >= minimum && voltage <= maximum)
+    }
+
+    /// Returns the battery's present rate relative to its full capacity as a C-rate, e.g. 0.5 for
+    /// a battery charging at half of its capacity per hour, for comparing charge speeds across
+    /// batteries of different sizes. The capacity is converted to match the units of the rate if
+    /// they differ. Returns `None` if the battery's full capacity is zero.
+    pub fn c_rate(&self) -> Option<f32> {
+        let last_capacity = match capacity_unit_for_rate(self.present_rate_unit) {
+            CapacityUnit::MilliAmpHours => self.last_charge().value(),
+            CapacityUnit::MilliWattHours => self.last_energy_mwh().value(),
+        };
+        if last_capacity == 0 {
+            return None;
+        }
+        Some(self.present_rate as f32 / last_capacity as f32)
+    }
+
+    /// Returns the energy corresponding to one percentage point of the battery's charge in Wh, for
+    /// converting between a drop in percentage and the energy consumed.
+    pub fn wh_per_percent(&self) -> f32 {
+        self.last_energy_wh() / 100.
+    }
+
+    /// Returns the energy the battery held the last time it was charged to full in Wh. This is
+    /// read directly for batteries which report energy, otherwise it is computed from the last
+    /// full capacity and present voltage.
+    pub fn last_energy_wh(&self) -> f32 {
+        match self.last_energy {
+            Some(last_energy) => last_energy as f32 / 1000.,
+            None => {
+                determine_energy_wh(self.last_capacity.value(), self.capacity_unit, self.voltage)
+            }
+        }
+    }
+
+    /// Returns the battery's fields formatted as strings and keyed by name, e.g. `percentage` to
+    /// `83` and `time_remaining` to `0:42:00`, for referencing the fields by name in templates.
+    /// Fields the battery doesn't report are given as empty strings.
+    pub fn as_map(&self) -> BTreeMap<&'static str, String> {
+        let time_remaining = self
+            .time_remaining
+            .map(|time_remaining| {
+                let seconds = time_remaining.as_secs();
+                format!(
+                    "{}:{:02}:{:02}",
+                    seconds / 3600,
+                    (seconds / 60) % 60,
+                    seconds % 60
+                )
+            })
+            .unwrap_or_default();
+
+        let mut map = BTreeMap::new();
+        map.insert("name", self.name.clone());
+        map.insert("model_name", self.model_name.clone().unwrap_or_default());
+        map.insert(
+            "manufacturer",
+            self.manufacturer.clone().unwrap_or_default(),
+        );
+        map.insert("state", self.state.to_string());
+        map.insert("percentage", format!("{:.0}", self.percentage));
+        map.insert("time_remaining", time_remaining);
+        map.insert(
+            "remaining_capacity",
+            self.remaining_capacity.value().to_string(),
+        );
+        map.insert("last_capacity", self.last_capacity.value().to_string());
+        map.insert("design_capacity", self.design_capacity.value().to_string());
+        map.insert("present_rate", self.present_rate.to_string());
+        map.insert("voltage", self.voltage.to_string());
+        map
+    }
+
+    /// Returns the line the `acpi` tool prints for this battery, e.g.
+    /// `Battery 0: Discharging, 83%, 01:23:45 remaining`.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The number `acpi` would assign the battery, counting from zero.
+    pub fn to_acpi_line(&self, index: usize) -> String {
+        let (remaining_capacity, last_capacity) =
+            (self.remaining_capacity.value(), self.last_capacity.value());
+        let percentage = if last_capacity > 0 {
+            (u64::from(remaining_capacity) * 100 / u64::from(last_capacity)).min(100)
+        } else {
+            0
+        };
+        let mut line = format!("Battery {}: {}, {}%", index, self.state, percentage);
+
+        match (self.state, self.time_remaining) {
+            (ChargingState::Full, _) | (ChargingState::NotCharging, _) => {}
+            (_, Some(time_remaining)) if time_remaining.as_secs() > 0 => {
+                let seconds = time_remaining.as_secs();
+                line.push_str(&format!(
+                    ", {:02}:{:02}:{:02}",
+                    seconds / 3600,
+                    (seconds / 60) % 60,
+                    seconds % 60
+                ));
+                match self.state {
+                    ChargingState::Charging => line.push_str(" until charged"),
+                    _ => line.push_str(" remaining"),
+                }
+            }
+            (_, None) => line.push_str(", rate information unavailable"),
+            (ChargingState::Charging, _) if self.present_rate == 0 => {
+                line.push_str(", charging at zero rate - will never fully charge.")
+            }
+            (ChargingState::Discharging, _) if self.present_rate == 0 => {
+                line.push_str(", discharging at zero rate - will never fully discharge.")
+            }
+            _ => {}
+        }
+
+        line
+    }
+
+    /// Returns what changed between a previous reading of the battery and this one.
+    ///
+    /// # Arguments
+    ///
+    /// * `previous` - An earlier reading of the same battery.
+    pub fn diff(&self, previous: &BatteryInfo) -> BatteryDiff {
+        let state_transition = if self.state != previous.state {
+            Some((previous.state, self.state))
+        } else {
+            None
+        };
+
+        BatteryDiff {
+            state_transition,
+            percentage_delta: self.percentage - previous.percentage,
+            rate_delta: i64::from(self.present_rate) - i64::from(previous.present_rate),
+        }
+    }
+
+    /// Returns the time remaining formatted for people to read, e.g. `2 hr 15 min`, `1 hr`,
+    /// `45 min`, or `less than a minute`. Units with a count of zero are left out. Returns `None`
+    /// if the battery is neither charging nor discharging or doesn't report a rate.
+    pub fn time_remaining_human(&self) -> Option<String> {
+        let time_remaining = match self.state {
+            ChargingState::Charging | ChargingState::Discharging => self.time_remaining?,
+            _ => return None,
+        };
+        let minutes = time_remaining.as_secs() / 60;
+        let (hours, minutes) = (minutes / 60, minutes % 60);
+        let formatted = match (hours, minutes) {
+            (0, 0) => String::from("less than a minute"),
+            (0, minutes) => format!("{} min", minutes),
+            (hours, 0) => format!("{} hr", hours),
+            (hours, minutes) => format!("{} hr {} min", hours, minutes),
+        };
+        Some(formatted)
+    }
+
+    /// Sets the energy remaining at which the battery raises a low battery alarm by writing its
+    /// `alarm` attribute, which requires root privileges. For batteries which report charge
+    /// rather than energy, the energy is converted to a charge at the present voltage.
+    ///
+    /// # Arguments
+    ///
+    /// * `value_mwh` - The remaining energy to raise the alarm at in mWh.
+    pub fn set_alarm(&self, value_mwh: u32) -> Result<(), AcpiClientError> {
+        let value = match self.remaining_energy {
+            Some(_) => value_mwh,
+            None => MilliWattHours(value_mwh)
+                .to_milli_amp_hours(self.voltage)
+                .value(),
+        };
+        write_attribute(
+            &self.path.join(attrs::ALARM),
+            &(u64::from(value) * 1000).to_string(),
+        )
+    }
+
+    /// Sets the percentages the battery starts and stops charging at by writing its charge
+    /// threshold attributes under whichever names the driver uses, which requires root
+    /// privileges.
+    ///
+    /// # Arguments
+    ///
+    /// * `start` - The percentage below which the battery starts charging, or `None` to leave it
+    ///   unchanged. Must be below `end`.
+    /// * `end` - The percentage at which the battery stops charging, at most 100.
+    pub fn set_charge_thresholds(&self, start: Option<u8>, end: u8) -> Result<(), AcpiClientError> {
+        if end > 100 || start.is_some_and(|start| start >= end) {
+            return Err(AcpiClientError::InvalidInput(io::Error::other(format!(
+                "Invalid charge thresholds: start {:?}, end {}.",
+                start, end
+            ))));
+        }
+        let start_path = find_attribute(
+            &self.path,
+            &[
+                attrs::CHARGE_CONTROL_START_THRESHOLD,
+                attrs::CHARGE_START_THRESHOLD,
+            ],
+        );
+        let end_path = find_attribute(
+            &self.path,
+            &[
+                attrs::CHARGE_CONTROL_END_THRESHOLD,
+                attrs::CHARGE_STOP_THRESHOLD,
+            ],
+        );
+
+        // Drivers reject a start threshold above the end threshold, so when both thresholds are
+        // moving up the end threshold has to be written first
+        let write_start = || match start {
+            Some(start) => write_attribute(&start_path, &start.to_string()),
+            None => Ok(()),
+        };
+        let raising = start.is_some_and(|start| {
+            self.charge_stop_threshold
+                .is_some_and(|current_end| start >= current_end)
+        });
+        if raising {
+            write_attribute(&end_path, &end.to_string())?;
+            write_start()
+        } else {
+            write_start()?;
+            write_attribute(&end_path, &end.to_string())
+        }
+    }
+
+    /// Returns whether the name in the battery's `uevent` file differs from the name of its
+    /// directory, which indicates the directory was renamed or linked to from elsewhere and
+    /// readings may not be correlated with kernel events by name.
+    pub fn has_name_mismatch(&self) -> bool {
+        self.uevent_name
+            .as_ref()
+            .is_some_and(|uevent_name| *uevent_name != self.name)
+    }
+
+    /// Returns whether the battery is intentionally being held from charging, i.e. it reports that
+    /// it isn't charging and its thresholds are holding it, as for `BatteryState::HeldByThreshold`
+    /// in `high_level_state`. For batteries without a stop threshold this is just whether it
+    /// reports that it isn't charging.
+    pub fn is_charge_inhibited(&self) -> bool {
+        if self.state != ChargingState::NotCharging {
+            return false;
+        }
+        match self.charge_stop_threshold {
+            Some(_) => self.high_level_state() == BatteryState::HeldByThreshold,
+            None => true,
+        }
+    }
+
+    /// Returns whether the battery is depleted, i.e. it has no remaining capacity, or its
+    /// percentage is at most `EMPTY_PERCENTAGE` while it is discharging or reports a critical
+    /// capacity level. A battery which is charging or full is never considered empty.
+    pub fn is_empty(&self) -> bool {
+        if matches!(self.state, ChargingState::Charging | ChargingState::Full) {
+            return false;
+        }
+        if self.remaining_capacity.value() == 0 {
+            return true;
+        }
+        self.percentage <= EMPTY_PERCENTAGE
+            && (self.state == ChargingState::Discharging
+                || self.capacity_level == Some(CapacityLevel::Critical))
+    }
+
+    /// Returns the time elapsed since the battery was read, or zero if the system clock has moved
+    /// back since.
+    pub fn age(&self) -> time::Duration {
+        time::SystemTime::now()
+            .duration_since(self.sampled_at)
+            .unwrap_or_else(|_| time::Duration::new(0, 0))
+    }
+
+    /// Returns whether the battery was read longer ago than a given age, so that tools holding on
+    /// to readings can ignore stale ones.
+    ///
+    /// # Arguments
+    ///
+    /// * `max_age` - The longest time since the battery was read for the reading to be fresh.
+    pub fn is_stale(&self, max_age: time::Duration) -> bool {
+        self.age() > max_age
+    }
+
+    /// Returns the battery's state normalized across firmwares, which differ in whether a battery
+    /// which has stopped charging reports `Full` or `NotCharging`. The state is derived as follows:
+    ///
+    /// * `Charging` and `Discharging` are reported as they are.
+    /// * A battery which is `Full` or `NotCharging` at 100%, after rounding, is `FullyCharged`.
+    /// * Otherwise, a battery which is `Full` or `NotCharging` with a charge stop threshold below
+    ///   100 is `HeldByThreshold` once its charge is at or above its start threshold, or its stop
+    ///   threshold if it has no start threshold.
+    /// * Otherwise, a battery which is `Full` is `FullyCharged`, trusting the firmware.
+    /// * Anything else, including `NotCharging` with no threshold to explain it, is `Unknown`.
+    pub fn high_level_state(&self) -> BatteryState {
+        let percentage = self.percentage.round();
+        match self.state {
+            ChargingState::Charging => return BatteryState::Charging,
+            ChargingState::Discharging => return BatteryState::Discharging,
+            ChargingState::Unknown => return BatteryState::Unknown,
+            ChargingState::Full | ChargingState::NotCharging => {}
+        }
+        if percentage >= 100. {
+            return BatteryState::FullyCharged;
+        }
+        let held = match self.charge_stop_threshold {
+            Some(stop) if stop < 100 => {
+                f32::from(self.charge_start_threshold.unwrap_or(stop)) <= percentage
+            }
+            _ => false,
+        };
+        if held {
+            BatteryState::HeldByThreshold
+        } else if self.state == ChargingState::Full {
+            BatteryState::FullyCharged
+        } else {
+            BatteryState::Unknown
+        }
+    }
+
+    /// Returns the time until a charging battery stops charging. For batteries with a charge stop
+    /// threshold this is the time to reach the threshold rather than full charge, otherwise it is
+    /// the time to full charge. Returns `None` if the battery is not charging.
+    pub fn time_to_threshold(&self) -> Option<time::Duration> {
+        if self.state != ChargingState::Charging {
+            return None;
+        }
+        match self.charge_stop_threshold {
+            Some(threshold) if self.time_remaining.is_some() => {
+                let target_capacity = (u64::from(self.last_capacity.value())
+                    * u64::from(threshold.min(100))
+                    / 100) as u32;
+                if self.remaining_capacity.value() >= target_capacity {
+                    return Some(time::Duration::new(0, 0));
+                }
+                let rate_unit = capacity_unit_for_rate(self.present_rate_unit);
+                determine_time_to_state_change(
+                    convert_capacity(
+                        self.remaining_capacity.value(),
+                        self.capacity_unit,
+                        rate_unit,
+                        self.voltage,
+                    ),
+                    convert_capacity(target_capacity, self.capacity_unit, rate_unit, self.voltage),
+                    self.present_rate,
+                    self.state,
+                )
+            }
+            _ => self.time_remaining,
+        }
+    }
+
+    /// Returns the share of the battery's cycle life it has used as a percentage, clamped to 100,
+    /// or `None` if the battery doesn't report its cycle count. This is a rough estimate of its
+    /// age, as batteries wear with time and temperature as well as cycles.
+    ///
+    /// # Arguments
+    ///
+    /// * `rated_cycles` - The number of cycles the battery is rated for, e.g. from its datasheet,
+    ///   since batteries don't report it themselves.
+    pub fn cycle_life_used(&self, rated_cycles: u32) -> Option<f32> {
+        let cycle_count = self.cycle_count?;
+        if rated_cycles == 0 {
+            return Some(100.);
+        }
+        Some((cycle_count as f32 * 100. / rated_cycles as f32).min(100.))
+    }
+
+    /// Returns how long the battery's remaining energy would last if the system drew a given
+    /// power from it, e.g. to answer how long it would last under a heavier workload. Returns
+    /// `None` if the power isn't positive, if the runtime is too long to represent, or if the
+    /// remaining energy can't be determined because the battery reports neither energy nor a
+    /// voltage to convert its charge with.
+    ///
+    /// # Arguments
+    ///
+    /// * `watts` - The hypothetical power drawn from the battery in W.
+    pub fn runtime_at_power(&self, watts: f32) -> Option<time::Duration> {
+        if !watts.is_finite() || watts <= 0. {
+            return None;
+        }
+        if self.remaining_energy.is_none() && self.voltage == 0 {
+            return None;
+        }
+        // A tiny power gives a runtime too long to represent
+        time::Duration::try_from_secs_f32(self.remaining_energy_wh() / watts * 3600.).ok()
+    }
+
+    /// Returns the time until the battery's charge reaches a target percentage of its full charge
+    /// at its present rate, e.g. the time to charge to 90%. Returns `None` if the battery is
+    /// neither charging nor discharging, doesn't report a rate, or has already passed the target
+    /// in the direction its charge is moving.
+    ///
+    /// # Arguments
+    ///
+    /// * `target` - The percentage of full charge to reach.
+    pub fn time_to_percentage(&self, target: f32) -> Option<time::Duration> {
+        self.time_remaining?;
+        let target_capacity =
+            (self.last_capacity.value() as f32 * target.clamp(0.0, 100.0) / 100.).round() as u32;
+        let rate_unit = capacity_unit_for_rate(self.present_rate_unit);
+        let remaining_capacity = convert_capacity(
+            self.remaining_capacity.value(),
+            self.capacity_unit,
+            rate_unit,
+            self.voltage,
+        );
+        let target_capacity =
+            convert_capacity(target_capacity, self.capacity_unit, rate_unit, self.voltage);
+        match self.state {
+            ChargingState::Charging if remaining_capacity < target_capacity => {
+                determine_time_to_state_change(
+                    remaining_capacity,
+                    target_capacity,
+                    self.present_rate,
+                    self.state,
+                )
+            }
+            ChargingState::Discharging if remaining_capacity > target_capacity => {
+                determine_time_to_state_change(
+                    remaining_capacity - target_capacity,
+                    0,
+                    self.present_rate,
+                    self.state,
+                )
+            }
+            _ => None,
+        }
+    }
+}
+
+/// The fraction of a battery's design voltages by which its voltage may lie outside them and still
+/// be considered plausible by `voltage_is_plausible`. Batteries routinely sag below their nominal
+/// voltage as they empty, so the band is generous.
+pub const VOLTAGE_TOLERANCE: f32 = 0.2;
+
+/// The percentage at or below which a discharging battery, or one reporting a critical capacity
+/// level, is considered empty by `BatteryInfo::is_empty`. Fuel gauges commonly report a point or
+/// two of charge which the system can't use before shutting down.
+pub const EMPTY_PERCENTAGE: f32 = 2.0;
+
+/// The default weight given to a newly read present rate when blending it with a previous
+/// reading.
+pub const DEFAULT_SMOOTHING_FACTOR: f32 = 0.3;
+
+/// Builder for configuring how a battery is read from the ACPI subsystem.
+#[derive(Clone)]
+pub struct BatteryReader {
+    /// The present rate and percentage of earlier readings, keyed by the batteries' names.
+    previous: BTreeMap<String, (u32, f32)>,
+    smoothing_factor: f32,
+    percentage_hysteresis: Option<f32>,
+    source: Arc<dyn AttributeSource>,
+    attempts: u32,
+    retry_delay: time::Duration,
+    optional_attributes: Vec<String>,
+    capacity_unit: CapacityUnit,
+    averaged_rate: bool,
+    full_at_capacity: bool,
+    min_design_capacity_mah: u32,
+    percentage_sources: Vec<PercentageSource>,
+    zero_capacity_as_missing: bool,
+}
+
+impl Default for BatteryReader {
+    fn default() -> BatteryReader {
+        BatteryReader {
+            previous: BTreeMap::new(),
+            smoothing_factor: DEFAULT_SMOOTHING_FACTOR,
+            percentage_hysteresis: None,
+            source: Arc::new(FileSystemSource),
+            attempts: 1,
+            retry_delay: time::Duration::new(0, 0),
+            optional_attributes: vec![],
+            capacity_unit: CapacityUnit::MilliAmpHours,
+            averaged_rate: true,
+            full_at_capacity: false,
+            min_design_capacity_mah: 0,
+            percentage_sources: DEFAULT_PERCENTAGE_SOURCES.to_vec(),
+            zero_capacity_as_missing: false,
+        }
+    }
+}
+
+impl BatteryReader {
+    /// Returns a reader with the default configuration.
+    pub fn new() -> BatteryReader {
+        BatteryReader::default()
+    }
+
+    /// Blends the present rate of the next reading with the rate of a previous reading of the same
+    /// battery before the time remaining is computed. This damps the spikes in `present_rate`
+    /// which otherwise make `time_remaining` jump between polls. The previous reading's percentage
+    /// is also used by `percentage_hysteresis`.
+    ///
+    /// A previous reading only applies to the battery with the same name, so when reading several
+    /// batteries with `read_all` this can be called once with each battery's previous reading.
+    ///
+    /// # Arguments
+    ///
+    /// * `previous` - An earlier reading of a battery, or `None` to forget every previous reading
+    ///   and disable smoothing.
+    pub fn previous(mut self, previous: Option<&BatteryInfo>) -> BatteryReader {
+        match previous {
+            Some(info) => {
+                self.previous
+                    .insert(info.name.clone(), (info.present_rate, info.percentage));
+            }
+            None => self.previous.clear(),
+        }
+        self
+    }
+
+    /// Keeps reporting the percentage of the previous reading until the battery's charge moves
+    /// more than a given number of percentage points away from it. This stops the percentage
+    /// flickering by a point between polls as the fuel gauge jitters. Has no effect unless a
+    /// previous reading is given with `previous`. Disabled by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `delta` - The change in percentage points up to which the previous percentage is kept.
+    pub fn percentage_hysteresis(mut self, delta: f32) -> BatteryReader {
+        self.percentage_hysteresis = Some(delta.max(0.0));
+        self
+    }
+
+    /// Sets the weight of the exponential moving average used to smooth the present rate.
+    ///
+    /// The smoothed rate is `factor * new_rate + (1 - factor) * previous_rate`, so a factor of
+    /// 1.0 disables smoothing and values closer to 0.0 favour the previous reading. Values are
+    /// clamped to the range 0.0 to 1.0. Defaults to `DEFAULT_SMOOTHING_FACTOR`.
+    ///
+    /// # Arguments
+    ///
+    /// * `factor` - The weight given to the newly read present rate.
+    pub fn smoothing_factor(mut self, factor: f32) -> BatteryReader {
+        self.smoothing_factor = factor.clamp(0.0, 1.0);
+        self
+    }
+
+    /// Sets the source the battery's attribute files are read from. Defaults to the filesystem.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source to read attributes from.
+    pub fn source<S: AttributeSource + 'static>(mut self, source: S) -> BatteryReader {
+        self.source = Arc::new(source);
+        self
+    }
+
+    /// Retries reading a battery which fails to parse, waiting between each attempt.
+    ///
+    /// This is intended for the window immediately after the system resumes from suspend, when
+    /// reads of the battery's attributes can fail transiently until the driver catches up.
+    ///
+    /// # Arguments
+    ///
+    /// * `attempts` - The total number of times to try reading the battery, at least one.
+    /// * `delay` - The time to wait after a failed attempt before trying again.
+    pub fn with_retries(mut self, attempts: u32, delay: time::Duration) -> BatteryReader {
+        self.attempts = attempts.max(1);
+        self.retry_delay = delay;
+        self
+    }
+
+    /// Treats a measurement attribute, e.g. `voltage_now`, as optional so that a battery missing
+    /// it is still read, with the measurement reported as zero.
+    ///
+    /// By default every charge, energy, and voltage attribute is required. The rate attributes,
+    /// e.g. `current_now` and `power_now`, are always optional, since a missing rate is reported
+    /// by `time_remaining` being `None`.
+    ///
+    /// # Arguments
+    ///
+    /// * `attribute` - The name of the attribute file to treat as optional.
+    pub fn optional_attribute(mut self, attribute: &str) -> BatteryReader {
+        if !self
+            .optional_attributes
+            .iter()
+            .any(|name| name == attribute)
+        {
+            self.optional_attributes.push(String::from(attribute));
+        }
+        self
+    }
+
+    /// Treats a measurement attribute which was previously made optional as required again, so
+    /// that a battery missing it fails to parse.
+    ///
+    /// # Arguments
+    ///
+    /// * `attribute` - The name of the attribute file to treat as required.
+    pub fn required_attribute(mut self, attribute: &str) -> BatteryReader {
+        self.optional_attributes.retain(|name| name != attribute);
+        self
+    }
+
+    /// Sets the units the battery's capacities are reported in, converting them from the units the
+    /// battery reports if they differ. Defaults to mAh.
+    ///
+    /// # Arguments
+    ///
+    /// * `unit` - The units to report capacities in.
+    pub fn capacity_unit(mut self, unit: CapacityUnit) -> BatteryReader {
+        self.capacity_unit = unit;
+        self
+    }
 
-    for entry in fs::read_dir(&path)? {
-        let path = entry?.path();
-        if determine_is_battery(parse_entry_file(&path.join("type"))?) {
-            let ps = BatteryInfo::new(&path);
-            if ps.is_ok() {
-                results.push(ps?);
-            }
-        }
+    /// Sets whether the present rate is read from the averaged rate attributes, `current_avg` and
+    /// `power_avg`, which are far less noisy than the instantaneous `current_now` and `power_now`.
+    ///
+    /// When enabled, which is the default, a battery's rate is read from the first of `power_avg`,
+    /// `power_now`, `current_avg`, and `current_now` it reports, with the power attributes only
+    /// considered for batteries reporting energy. When disabled the averaged attributes are
+    /// skipped.
+    ///
+    /// # Arguments
+    ///
+    /// * `averaged` - Whether to prefer the averaged rate attributes.
+    pub fn averaged_rate(mut self, averaged: bool) -> BatteryReader {
+        self.averaged_rate = averaged;
+        self
     }
 
-    Ok(results)
-}
+    /// Sets whether a battery whose `capacity` attribute reads 100 or more is reported as `Full`,
+    /// regardless of its `status` attribute, which some drivers leave stale once charging ends.
+    ///
+    /// Disabled by default, since some batteries are held at 100% while still reporting that they
+    /// are charging or not charging, and the status is then accurate.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether a full `capacity` overrides the reported state.
+    pub fn full_at_capacity(mut self, enabled: bool) -> BatteryReader {
+        self.full_at_capacity = enabled;
+        self
+    }
 
-impl BatteryInfo {
-    /// Returns a battery corresponding to a given ACPI device path.
+    /// Skips batteries whose design capacity is below a given charge when reading every battery
+    /// with `read_all`. Bluetooth peripherals and other device scope supplies often report tiny
+    /// design capacities which clutter a list of the system's batteries. Batteries reporting energy
+    /// are compared at their present voltage, and kept if it reads as zero. Defaults to 0, which
+    /// skips none.
+    ///
+    /// # Arguments
+    ///
+    /// * `capacity` - The smallest design capacity of a battery to keep in mAh.
+    pub fn min_design_capacity_mah(mut self, capacity: u32) -> BatteryReader {
+        self.min_design_capacity_mah = capacity;
+        self
+    }
+
+    /// Sets the order in which the ways of computing a battery's percentage are tried, the first
+    /// one the battery supports being used. Defaults to `DEFAULT_PERCENTAGE_SOURCES`. A battery
+    /// supporting none of the sources reports its percentage from its measured capacities.
+    ///
+    /// # Arguments
+    ///
+    /// * `sources` - The sources to try, in order of preference.
+    pub fn percentage_sources(mut self, sources: &[PercentageSource]) -> BatteryReader {
+        self.percentage_sources = sources.to_vec();
+        self
+    }
+
+    /// Sets whether a full or design capacity which is present but reads zero is treated as
+    /// missing. Some batteries report a last full capacity of zero until they are first charged to
+    /// full, which otherwise gives a percentage of zero.
+    ///
+    /// When enabled, a battery reporting both charge and energy is read in whichever units have
+    /// non-zero full capacities, and a zero full capacity falls back to the design capacity, or a
+    /// zero design capacity to the full capacity. Disabled by default.
+    ///
+    /// # Arguments
+    ///
+    /// * `enabled` - Whether zero full and design capacities are treated as missing.
+    pub fn zero_capacity_as_missing(mut self, enabled: bool) -> BatteryReader {
+        self.zero_capacity_as_missing = enabled;
+        self
+    }
+
+    /// Returns a battery corresponding to a given ACPI device path using this configuration.
     ///
     /// # Arguments
     ///
@@ -66,90 +1536,690 @@ impl BatteryInfo {
     /// # Example
     /// ```
     /// let directory = std::path::Path::new("/sys/class/power_supply/BAT1");
-    /// let ps_info = acpi_client::BatteryInfo::new(&directory);
+    /// let ps_info = acpi_client::BatteryReader::new()
+    ///     .smoothing_factor(0.5)
+    ///     .read(&directory);
     /// ```
-    pub fn new(path: &path::Path) -> Result<BatteryInfo, AcpiClientError> {
+    pub fn read(&self, path: &path::Path) -> Result<BatteryInfo, AcpiClientError> {
+        let mut attempt = 1;
+        loop {
+            match self.read_once(path) {
+                Err(_) if attempt < self.attempts => {
+                    attempt += 1;
+                    std::thread::sleep(self.retry_delay);
+                }
+                result => return result,
+            }
+        }
+    }
+
+    /// Returns every battery among the power supplies in a directory using this configuration.
+    /// See `get_battery_info` for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to battery entries produced by the ACPI subsystem.
+    pub fn read_all(&self, path: &path::Path) -> Result<Vec<BatteryInfo>, AcpiClientError> {
+        Ok(self.read_all_with_errors(path)?.0)
+    }
+
+    /// Returns every battery among the power supplies in a directory using this configuration,
+    /// along with the error of each battery which fails to parse. See
+    /// `get_battery_info_with_errors` for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to battery entries produced by the ACPI subsystem.
+    pub fn read_all_with_errors(
+        &self,
+        path: &path::Path,
+    ) -> Result<(Vec<BatteryInfo>, DeviceErrors), AcpiClientError> {
+        let mut results: Vec<BatteryInfo> = vec![];
+        let mut errors: DeviceErrors = vec![];
+
+        for name in self.source.list(path)? {
+            let path = path.join(name);
+            // Entries without a readable type, including files, can't be identified, so skip them
+            let device_type = match read_attribute(self.source.as_ref(), &path.join(attrs::TYPE)) {
+                Ok(device_type) => device_type,
+                Err(_) => continue,
+            };
+            if is_battery_type(&device_type) {
+                match self.read(&path) {
+                    Ok(battery) => {
+                        if !self.is_below_min_design_capacity(&battery) {
+                            results.push(battery);
+                        }
+                    }
+                    // The device was removed, e.g. unplugged, after the directory was listed
+                    Err(_) if !self.source.exists(&path) => continue,
+                    Err(error) => errors.push((get_device_name(&path)?, error)),
+                }
+            }
+        }
+
+        Ok((results, errors))
+    }
+
+    /// Blocks until a battery's charge crosses a target percentage, polling it with this
+    /// configuration. See `wait_for_percentage` for details.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the ACPI device.
+    /// * `target` - The percentage of full charge to wait for.
+    /// * `poll` - The time to wait between readings of the battery.
+    /// * `timeout` - The longest time to wait before returning a `TimedOut` error. A timeout too
+    ///   long to represent, such as `Duration::MAX`, waits without a deadline.
+    pub fn wait_for_percentage(
+        &self,
+        path: &path::Path,
+        target: f32,
+        poll: time::Duration,
+        timeout: time::Duration,
+    ) -> Result<(), AcpiClientError> {
+        // A timeout too long to represent is treated as waiting without a deadline
+        let deadline = time::Instant::now().checked_add(timeout);
+        loop {
+            let battery = self.read(path)?;
+            let reached = match battery.state {
+                ChargingState::Discharging => battery.percentage <= target,
+                _ => battery.percentage >= target,
+            };
+            if reached {
+                return Ok(());
+            }
+            let next_poll = time::Instant::now().checked_add(poll);
+            if deadline.is_some_and(|deadline| next_poll.is_none_or(|next| next > deadline)) {
+                return Err(AcpiClientError::Io(io::Error::new(
+                    io::ErrorKind::TimedOut,
+                    format!("Battery did not reach {}% in time.", target),
+                )));
+            }
+            std::thread::sleep(poll);
+        }
+    }
+
+    /// Returns whether a battery's design capacity is below the configured minimum. A battery
+    /// reporting energy without a voltage to convert it to charge at is never below the minimum,
+    /// since its design charge is unknown.
+    ///
+    /// # Arguments
+    ///
+    /// * `battery` - The battery to check.
+    fn is_below_min_design_capacity(&self, battery: &BatteryInfo) -> bool {
+        let reports_energy = battery.design_energy.is_some()
+            || matches!(battery.design_capacity, Capacity::Energy(_));
+        if reports_energy && battery.voltage == 0 {
+            return false;
+        }
+        battery.design_charge().value() < self.min_design_capacity_mah
+    }
+
+    /// Makes a single attempt at reading a battery.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the ACPI device.
+    fn read_once(&self, path: &path::Path) -> Result<BatteryInfo, AcpiClientError> {
+        let source = self.source.as_ref();
+        let sampled_at = time::SystemTime::now();
+        let name = get_device_name(path)?;
         // Check whether the system reports energy or capacity
-        match determine_reporting_type(&path)? {
-            ReportType::Capacity => return parse_capacity_supply(&path),
-            ReportType::Energy => return parse_energy_supply(&path),
+        let optional = &self.optional_attributes;
+        let measurements =
+            match determine_reporting_type(source, path, self.zero_capacity_as_missing)? {
+                ReportType::Capacity => {
+                    parse_capacity_supply(source, path, optional, self.averaged_rate)?
+                }
+                ReportType::Energy => {
+                    parse_energy_supply(source, path, optional, self.averaged_rate)?
+                }
+            };
+        let Measurements {
+            voltage,
+            remaining_capacity,
+            present_rate,
+            present_rate_unit,
+            design_capacity,
+            last_capacity,
+            capacity_unit,
+            charge_counter,
+        } = measurements;
+        let (design_capacity, last_capacity) = match (design_capacity, last_capacity) {
+            (design_capacity, 0) if self.zero_capacity_as_missing => {
+                (design_capacity, design_capacity)
+            }
+            (0, last_capacity) if self.zero_capacity_as_missing => (last_capacity, last_capacity),
+            capacities => capacities,
+        };
+        let capacity_level = read_capacity_level(source, path);
+        let raw_status = read_status(source, path)?
+            .map(|status| String::from(status.trim()))
+            .unwrap_or_default();
+        let reported_state = parse_charging_state(&raw_status).unwrap_or(ChargingState::Unknown);
+        let state = match reported_state {
+            ChargingState::Unknown => match infer_state_from_rate(present_rate) {
+                ChargingState::Unknown => infer_state_from_capacity_level(capacity_level),
+                state => state,
+            },
+            state => state,
+        };
+        let state = if self.full_at_capacity
+            && read_optional_attribute_to_i32(source, &path.join(attrs::CAPACITY), 1)
+                .is_some_and(|capacity| capacity >= 100)
+        {
+            ChargingState::Full
+        } else {
+            state
+        };
+        let present_rate = present_rate.map(|present_rate| present_rate.unsigned_abs());
+        // Some batteries stop reporting a rate once they are full, and some never report one
+        let present_rate = match present_rate {
+            Some(present_rate) => Some(self.smooth_rate(&name, present_rate)),
+            None if state == ChargingState::Full => Some(0),
+            None => None,
+        };
+        let percentage = self.hold_percentage(
+            &name,
+            self.compute_percentage(source, path, remaining_capacity, last_capacity),
+        );
+        // The time remaining is computed with capacities in the units matching the rate
+        let rate_unit = capacity_unit_for_rate(present_rate_unit);
+        let time_remaining = present_rate.and_then(|present_rate| {
+            determine_time_to_state_change(
+                convert_capacity(remaining_capacity, capacity_unit, rate_unit, voltage),
+                convert_capacity(last_capacity, capacity_unit, rate_unit, voltage),
+                present_rate,
+                state,
+            )
+        });
+        let (remaining_energy, design_energy, last_energy) = match capacity_unit {
+            CapacityUnit::MilliWattHours => (
+                Some(remaining_capacity),
+                Some(design_capacity),
+                Some(last_capacity),
+            ),
+            CapacityUnit::MilliAmpHours => (None, None, None),
+        };
+        let remaining_capacity = convert_capacity(
+            remaining_capacity,
+            capacity_unit,
+            self.capacity_unit,
+            voltage,
+        );
+        let design_capacity =
+            convert_capacity(design_capacity, capacity_unit, self.capacity_unit, voltage);
+        let last_capacity =
+            convert_capacity(last_capacity, capacity_unit, self.capacity_unit, voltage);
+        // Dividing an energy by the voltage truncates, so a charge converted from an energy is
+        // reconstructed from the driver's percentage to match the percentage displayed
+        let remaining_capacity = match (capacity_unit, self.capacity_unit) {
+            (CapacityUnit::MilliWattHours, CapacityUnit::MilliAmpHours) => {
+                read_optional_attribute_to_i32(source, &path.join(attrs::CAPACITY), 1)
+                    .map(|capacity| {
+                        (capacity.clamp(0, 100) as f32 / 100. * last_capacity as f32).round() as u32
+                    })
+                    .unwrap_or(remaining_capacity)
+            }
+            _ => remaining_capacity,
+        };
+        let present_rate = present_rate.unwrap_or(0);
+        let reported_health = read_attribute(source, &path.join(attrs::HEALTH))
+            .ok()
+            .filter(|health| !health.is_empty() && !health.eq_ignore_ascii_case("unknown"))
+            .map(|health| BatteryHealthStatus::from(health.as_str()));
+        let charge_type = read_attribute(source, &path.join(attrs::CHARGE_TYPE))
+            .ok()
+            .filter(|charge_type| {
+                !charge_type.is_empty()
+                    && !charge_type.eq_ignore_ascii_case("unknown")
+                    && !charge_type.eq_ignore_ascii_case("n/a")
+            })
+            .map(|charge_type| ChargeType::from(charge_type.as_str()));
+        let charge_start_threshold = read_threshold(
+            source,
+            path,
+            &[
+                attrs::CHARGE_CONTROL_START_THRESHOLD,
+                attrs::CHARGE_START_THRESHOLD,
+            ],
+        );
+        let charge_stop_threshold = read_threshold(
+            source,
+            path,
+            &[
+                attrs::CHARGE_CONTROL_END_THRESHOLD,
+                attrs::CHARGE_STOP_THRESHOLD,
+            ],
+        );
+        let capacity_error_margin = read_threshold(source, path, &[attrs::CAPACITY_ERROR_MARGIN]);
+        let temperature = read_temperature(source, path);
+        let design_voltage =
+            read_optional_attribute_to_i32(source, &path.join(attrs::VOLTAGE_MIN_DESIGN), 1000)
+                .filter(|voltage| *voltage > 0)
+                .map(|voltage| voltage as u32);
+        let design_voltage_max =
+            read_optional_attribute_to_i32(source, &path.join(attrs::VOLTAGE_MAX_DESIGN), 1000)
+                .filter(|voltage| *voltage > 0)
+                .map(|voltage| voltage as u32);
+        let voltage_ocv =
+            read_optional_attribute_to_i32(source, &path.join(attrs::VOLTAGE_OCV), 1000)
+                .filter(|voltage| *voltage > 0)
+                .map(|voltage| voltage as u32);
+        let constant_charge_voltage = read_optional_attribute_to_i32(
+            source,
+            &path.join(attrs::CONSTANT_CHARGE_VOLTAGE),
+            1000,
+        )
+        .map(|voltage| voltage.max(0) as u32);
+        let constant_charge_voltage_max = read_optional_attribute_to_i32(
+            source,
+            &path.join(attrs::CONSTANT_CHARGE_VOLTAGE_MAX),
+            1000,
+        )
+        .map(|voltage| voltage.max(0) as u32);
+        let current_max =
+            read_optional_attribute_to_i32(source, &path.join(attrs::CURRENT_MAX), 1000)
+                .map(|current| current.max(0) as u32);
+        let voltage_max =
+            read_optional_attribute_to_i32(source, &path.join(attrs::VOLTAGE_MAX), 1000)
+                .map(|voltage| voltage.max(0) as u32);
+        let precharge_current =
+            read_optional_attribute_to_i32(source, &path.join(attrs::PRECHARGE_CURRENT), 1000)
+                .map(|current| current.max(0) as u32);
+        let charge_term_current =
+            read_optional_attribute_to_i32(source, &path.join(attrs::CHARGE_TERM_CURRENT), 1000)
+                .map(|current| current.max(0) as u32);
+        let cycle_count = read_optional_attribute_to_i32(source, &path.join(attrs::CYCLE_COUNT), 1)
+            .filter(|cycles| *cycles >= 0)
+            .map(|cycles| cycles as u32);
+        let model_name = read_model_name(source, path);
+        let manufacturer = read_manufacturer(source, path);
+        let serial_number_path = path.join(attrs::SERIAL_NUMBER);
+        let serial_number = if source.exists(&serial_number_path) {
+            read_attribute(source, &serial_number_path)
+                .ok()
+                .filter(|serial_number| !serial_number.is_empty())
+        } else {
+            None
+        };
+        let manufacture_date = read_manufacture_date(source, path);
+        let uevent_name = read_uevent_entry(source, path, attrs::UEVENT_NAME)
+            .ok()
+            .filter(|uevent_name| !uevent_name.is_empty());
+
+        Ok(BatteryInfo {
+            name,
+            path: path.to_path_buf(),
+            model_name,
+            manufacturer,
+            serial_number,
+            manufacture_date,
+            uevent_name,
+            remaining_capacity: Capacity::new(remaining_capacity, self.capacity_unit),
+            present_rate,
+            present_rate_unit,
+            voltage,
+            design_voltage,
+            design_voltage_max,
+            voltage_ocv,
+            design_capacity: Capacity::new(design_capacity, self.capacity_unit),
+            last_capacity: Capacity::new(last_capacity, self.capacity_unit),
+            capacity_unit: self.capacity_unit,
+            percentage,
+            time_remaining,
+            state,
+            raw_status,
+            capacity_level,
+            reported_health,
+            charge_type,
+            charge_start_threshold,
+            charge_stop_threshold,
+            capacity_error_margin,
+            temperature,
+            constant_charge_voltage,
+            constant_charge_voltage_max,
+            current_max,
+            voltage_max,
+            precharge_current,
+            charge_term_current,
+            cycle_count,
+            charge_counter,
+            sampled_at,
+            design_energy,
+            last_energy,
+            remaining_energy,
+        })
+    }
+
+    /// Applies the configured smoothing to a newly read present rate.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the battery the rate was read from.
+    /// * `present_rate` - The rate read from the device in mA.
+    fn smooth_rate(&self, name: &str, present_rate: u32) -> u32 {
+        match self.previous.get(name) {
+            Some(&(previous_rate, _)) => {
+                let blended = self.smoothing_factor * present_rate as f32
+                    + (1.0 - self.smoothing_factor) * previous_rate as f32;
+                blended.round() as u32
+            }
+            None => present_rate,
+        }
+    }
+
+    /// Computes a battery's percentage from the first of the configured sources it supports.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source to read the device's attributes from.
+    /// * `path` - The path to the ACPI device.
+    /// * `remaining_capacity` - The measured remaining capacity.
+    /// * `last_capacity` - The measured last full capacity, in the same units.
+    fn compute_percentage(
+        &self,
+        source: &dyn AttributeSource,
+        path: &path::Path,
+        remaining_capacity: u32,
+        last_capacity: u32,
+    ) -> f32 {
+        let from_attributes = |now: &str, full: &str| {
+            let now = read_optional_attribute_to_i32(source, &path.join(now), 1)?;
+            let full = read_optional_attribute_to_i32(source, &path.join(full), 1)?;
+            if full <= 0 {
+                return None;
+            }
+            Some(determine_charge_percentage(now.max(0) as u32, full as u32))
+        };
+        self.percentage_sources
+            .iter()
+            .find_map(|percentage_source| match percentage_source {
+                PercentageSource::Measured if last_capacity > 0 => Some(
+                    determine_charge_percentage(remaining_capacity, last_capacity),
+                ),
+                PercentageSource::Measured => None,
+                PercentageSource::Charge => from_attributes(attrs::CHARGE_NOW, attrs::CHARGE_FULL),
+                PercentageSource::Energy => from_attributes(attrs::ENERGY_NOW, attrs::ENERGY_FULL),
+                PercentageSource::CapacityAttribute => {
+                    read_optional_attribute_to_i32(source, &path.join(attrs::CAPACITY), 1)
+                        .map(|capacity| capacity.clamp(0, 100) as f32)
+                }
+            })
+            .unwrap_or_else(|| determine_charge_percentage(remaining_capacity, last_capacity))
+    }
+
+    /// Applies the configured hysteresis to a newly computed percentage.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the battery the percentage was computed for.
+    /// * `percentage` - The percentage computed from the device's capacities.
+    fn hold_percentage(&self, name: &str, percentage: f32) -> f32 {
+        let previous = self.previous.get(name).map(|(_, percentage)| *percentage);
+        match (self.percentage_hysteresis, previous) {
+            (Some(delta), Some(previous)) if (percentage - previous).abs() <= delta => previous,
+            _ => percentage,
+        }
+    }
+}
+
+/// The raw measurements read from a battery in the units the battery reports them in.
+struct Measurements {
+    voltage: u32,
+    remaining_capacity: u32,
+    /// The rate as reported, which is negative for drivers which sign it by its direction.
+    present_rate: Option<i32>,
+    present_rate_unit: RateUnit,
+    design_capacity: u32,
+    last_capacity: u32,
+    capacity_unit: CapacityUnit,
+    /// The coulomb counter in µAh, if the battery reports a count which isn't negative.
+    charge_counter: Option<i32>,
+}
+
+/// Reads a measurement attribute in thousandths and scales it, reporting zero for a missing
+/// attribute which has been configured as optional.
+///
+/// # Arguments
+///
+/// * `source` - The source to read the device's attributes from.
+/// * `path` - The path to the ACPI device.
+/// * `attribute` - The name of the attribute file to read.
+/// * `optional` - The names of the attributes which may be missing.
+fn read_measurement(
+    source: &dyn AttributeSource,
+    path: &path::Path,
+    attribute: &str,
+    optional: &[String],
+) -> Result<u32, AcpiClientError> {
+    let attribute_path = path.join(attribute);
+    if optional.iter().any(|name| name == attribute) && !source.exists(&attribute_path) {
+        return Ok(0);
+    }
+    Ok(read_attribute_to_i32(source, &attribute_path, 1000)? as u32)
+}
+
+/// Reads the voltage of a battery in mV, preferring `voltage_avg`, which is less noisy, over
+/// `voltage_now` when the battery reports it and it can be read.
+///
+/// # Arguments
+///
+/// * `source` - The source to read the device's attributes from.
+/// * `path` - The path to the ACPI device.
+/// * `optional` - The names of the attributes which may be missing.
+fn read_voltage(
+    source: &dyn AttributeSource,
+    path: &path::Path,
+    optional: &[String],
+) -> Result<u32, AcpiClientError> {
+    let voltage_avg_path = path.join(attrs::VOLTAGE_AVG);
+    if source.exists(&voltage_avg_path) {
+        if let Ok(voltage) = read_attribute_to_i32(source, &voltage_avg_path, 1000) {
+            return Ok(voltage as u32);
         }
     }
+    read_measurement(source, path, attrs::VOLTAGE_NOW, optional)
+}
+
+/// Reads a rate attribute in thousandths, preferring the averaged form of the attribute when
+/// requested and reported, e.g. `current_avg` over `current_now`.
+///
+/// # Arguments
+///
+/// * `source` - The source to read the device's attributes from.
+/// * `path` - The path to the ACPI device.
+/// * `averaged_name` - The name of the averaged form of the attribute.
+/// * `instantaneous_name` - The name of the instantaneous form of the attribute.
+/// * `averaged` - Whether to prefer the averaged form of the attribute.
+fn read_rate(
+    source: &dyn AttributeSource,
+    path: &path::Path,
+    averaged_name: &str,
+    instantaneous_name: &str,
+    averaged: bool,
+) -> Option<i32> {
+    let averaged_rate = if averaged {
+        read_optional_attribute_to_i32(source, &path.join(averaged_name), 1000)
+    } else {
+        None
+    };
+    averaged_rate
+        .or_else(|| read_optional_attribute_to_i32(source, &path.join(instantaneous_name), 1000))
 }
 
 /// Parses a battery ACPI device entry which reports capacity in units of mAh.
 ///
 /// # Arguments
 ///
+/// * `source` - The source to read the device's attributes from.
 /// * `path` - The path to the ACPI device.
-fn parse_capacity_supply(path: &path::Path) -> Result<BatteryInfo, AcpiClientError> {
-    let voltage = parse_file_to_i32(&path.join("voltage_now"), 1000)? as u32;
-    let remaining_capacity = parse_file_to_i32(&path.join("charge_now"), 1000)? as u32;
-    let present_rate = parse_file_to_i32(&path.join("current_now"), 1000)? as u32;
-    let design_capacity = parse_file_to_i32(&path.join("charge_full_design"), 1000)? as u32;
-    let last_capacity = parse_file_to_i32(&path.join("charge_full"), 1000)? as u32;
-    let state = parse_state_from_str(
-        parse_entry_file(&path.join("status"))?
-            .trim()
-            .to_lowercase(),
-    )?;
-    let percentage = determine_charge_percentage(remaining_capacity, last_capacity);
-    let time_remaining =
-        determine_time_to_state_change(remaining_capacity, last_capacity, present_rate, state);
-    let name = get_device_name(path)?;
-
-    Ok(BatteryInfo {
-        name,
-        remaining_capacity: remaining_capacity,
-        present_rate: present_rate,
-        voltage: voltage,
-        design_capacity: design_capacity,
-        last_capacity: last_capacity,
-        percentage,
-        time_remaining,
-        state: state,
+/// * `optional` - The names of the attributes which may be missing.
+/// * `averaged` - Whether to prefer the averaged rate attributes.
+fn parse_capacity_supply(
+    source: &dyn AttributeSource,
+    path: &path::Path,
+    optional: &[String],
+    averaged: bool,
+) -> Result<Measurements, AcpiClientError> {
+    let voltage = read_voltage(source, path, optional)?;
+    let charge_counter = read_charge_counter(source, path);
+    Ok(Measurements {
+        voltage,
+        // Truncated to whole mAh, as `read_measurement` truncates the other attributes
+        remaining_capacity: match charge_counter {
+            Some(charge_counter) => (charge_counter / 1000) as u32,
+            None => read_measurement(source, path, attrs::CHARGE_NOW, optional)?,
+        },
+        present_rate: read_rate(
+            source,
+            path,
+            attrs::CURRENT_AVG,
+            attrs::CURRENT_NOW,
+            averaged,
+        ),
+        present_rate_unit: RateUnit::MilliAmps,
+        design_capacity: read_measurement(source, path, attrs::CHARGE_FULL_DESIGN, optional)?,
+        last_capacity: read_measurement(source, path, attrs::CHARGE_FULL, optional)?,
+        capacity_unit: CapacityUnit::MilliAmpHours,
+        charge_counter,
     })
 }
 
-/// Parses a battery ACPI device entry which reports capacity in units of mWh.
+/// Reads a battery's coulomb counter in µAh, ignoring a negative count, which some gauges report
+/// relative to an arbitrary reference rather than as the remaining charge.
 ///
 /// # Arguments
 ///
+/// * `source` - The source to read the device's attributes from.
 /// * `path` - The path to the ACPI device.
-fn parse_energy_supply(path: &path::Path) -> Result<BatteryInfo, AcpiClientError> {
-    let voltage = parse_file_to_i32(&path.join("voltage_now"), 1000)? as u32;
-    let remaining_capacity = parse_file_to_i32(&path.join("energy_now"), 1000)? as u32 / voltage;
-    let present_rate = if let Ok(power_now) = parse_file_to_i32(&path.join("power_now"), 1000) {
-        power_now as u32
-    } else {
-        parse_file_to_i32(&path.join("current_now"), 1000)? as u32
+fn read_charge_counter(source: &dyn AttributeSource, path: &path::Path) -> Option<i32> {
+    read_optional_attribute_to_i32(source, &path.join(attrs::CHARGE_COUNTER), 1)
+        .filter(|charge_counter| *charge_counter >= 0)
+}
+
+/// Chooses between the charge and energy attributes of a battery which reports both. The
+/// percentage each gives is cross-checked against the kernel's `capacity` attribute and the closer
+/// one is chosen. Energy is chosen if the battery doesn't report `capacity`, if the percentages are
+/// equally close, or if either set can't be read, since energy doesn't depend on the voltage and
+/// is usually the more accurate of the two.
+///
+/// # Arguments
+///
+/// * `source` - The source to read the device's attributes from.
+/// * `path` - The path to the ACPI device.
+fn reconcile_reporting_type(source: &dyn AttributeSource, path: &path::Path) -> ReportType {
+    let percentage = |now: &str, full: &str| {
+        let now = read_optional_attribute_to_i32(source, &path.join(now), 1)?;
+        let full = read_optional_attribute_to_i32(source, &path.join(full), 1)?;
+        Some(determine_charge_percentage(
+            now.max(0) as u32,
+            full.max(0) as u32,
+        ))
     };
-    let design_capacity =
-        parse_file_to_i32(&path.join("energy_full_design"), 1000)? as u32 / voltage;
-    let last_capacity = parse_file_to_i32(&path.join("energy_full"), 1000)? as u32 / voltage;
-    let state = parse_state_from_str(
-        parse_entry_file(&path.join("status"))?
-            .trim()
-            .to_lowercase(),
-    )?;
-    let percentage = determine_charge_percentage(remaining_capacity, last_capacity);
-    let time_remaining =
-        determine_time_to_state_change(remaining_capacity, last_capacity, present_rate, state);
-    let name = get_device_name(path)?;
-
-    Ok(BatteryInfo {
-        name,
-        remaining_capacity,
+    let reported = read_optional_attribute_to_i32(source, &path.join(attrs::CAPACITY), 1);
+    let charge = percentage(attrs::CHARGE_NOW, attrs::CHARGE_FULL);
+    let energy = percentage(attrs::ENERGY_NOW, attrs::ENERGY_FULL);
+    match (reported, charge, energy) {
+        (Some(reported), Some(charge), Some(energy))
+            if (charge - reported as f32).abs() < (energy - reported as f32).abs() =>
+        {
+            ReportType::Capacity
+        }
+        _ => ReportType::Energy,
+    }
+}
+
+/// Parses a battery ACPI device entry which reports capacity in units of mWh.
+///
+/// # Arguments
+///
+/// * `source` - The source to read the device's attributes from.
+/// * `path` - The path to the ACPI device.
+/// * `optional` - The names of the attributes which may be missing.
+/// * `averaged` - Whether to prefer the averaged rate attributes.
+fn parse_energy_supply(
+    source: &dyn AttributeSource,
+    path: &path::Path,
+    optional: &[String],
+    averaged: bool,
+) -> Result<Measurements, AcpiClientError> {
+    let (present_rate, present_rate_unit) =
+        match read_rate(source, path, attrs::POWER_AVG, attrs::POWER_NOW, averaged) {
+            Some(power) => (Some(power), RateUnit::MilliWatts),
+            None => (
+                read_rate(
+                    source,
+                    path,
+                    attrs::CURRENT_AVG,
+                    attrs::CURRENT_NOW,
+                    averaged,
+                ),
+                RateUnit::MilliAmps,
+            ),
+        };
+
+    Ok(Measurements {
+        voltage: read_voltage(source, path, optional)?,
+        remaining_capacity: read_measurement(source, path, attrs::ENERGY_NOW, optional)?,
         present_rate,
-        voltage,
-        design_capacity,
-        last_capacity,
-        percentage,
-        time_remaining,
-        state,
+        present_rate_unit,
+        design_capacity: read_measurement(source, path, attrs::ENERGY_FULL_DESIGN, optional)?,
+        last_capacity: read_measurement(source, path, attrs::ENERGY_FULL, optional)?,
+        capacity_unit: CapacityUnit::MilliWattHours,
+        charge_counter: read_charge_counter(source, path),
     })
 }
 
+/// Converts a capacity between a charge in mAh and an energy in mWh using the present voltage.
+/// Converting to a charge at zero voltage gives zero.
+///
+/// # Arguments
+///
+/// * `capacity` - The capacity to convert.
+/// * `from` - The units the capacity is in.
+/// * `to` - The units to convert the capacity to.
+/// * `voltage` - The present voltage in mV.
+fn convert_capacity(capacity: u32, from: CapacityUnit, to: CapacityUnit, voltage: u32) -> u32 {
+    match (from, to) {
+        (CapacityUnit::MilliAmpHours, CapacityUnit::MilliWattHours) => {
+            MilliAmpHours(capacity).to_milli_watt_hours(voltage).value()
+        }
+        (CapacityUnit::MilliWattHours, CapacityUnit::MilliAmpHours) => {
+            MilliWattHours(capacity).to_milli_amp_hours(voltage).value()
+        }
+        _ => capacity,
+    }
+}
+
+/// Returns the units of capacity which, divided by a rate in the given units, give a time in
+/// hours.
+///
+/// # Arguments
+///
+/// * `unit` - The units of the rate.
+fn capacity_unit_for_rate(unit: RateUnit) -> CapacityUnit {
+    match unit {
+        RateUnit::MilliAmps => CapacityUnit::MilliAmpHours,
+        RateUnit::MilliWatts => CapacityUnit::MilliWattHours,
+    }
+}
+
+/// Determines the energy in Wh held by a given capacity at a given voltage.
+///
+/// # Arguments
+///
+/// * `capacity` - The capacity.
+/// * `unit` - The units the capacity is in.
+/// * `voltage` - The voltage in mV.
+fn determine_energy_wh(capacity: u32, unit: CapacityUnit, voltage: u32) -> f32 {
+    match unit {
+        CapacityUnit::MilliAmpHours => (capacity as f32) * (voltage as f32) / 1_000_000.,
+        CapacityUnit::MilliWattHours => capacity as f32 / 1000.,
+    }
+}
+
 /// Determines the percentage of full charge from the current charge and the full charge
 /// measurements.
 ///
@@ -158,57 +2228,296 @@ fn parse_energy_supply(path: &path::Path) -> Result<BatteryInfo, AcpiClientError
 /// * `remaining_capacity` - The current charge of the battery in mAh.
 /// * `full_capacity` - The full charge of the battery in mAh.
 fn determine_charge_percentage(remaining_capacity: u32, full_capacity: u32) -> f32 {
-    (remaining_capacity as f32) * 100.0 / (full_capacity as f32)
+    finite_or_zero((remaining_capacity as f32) * 100.0 / (full_capacity as f32))
+}
+
+/// Returns a computed value if it is finite, otherwise zero, so that divisions by a zero
+/// measurement don't propagate `NaN` or infinity to callers.
+///
+/// # Arguments
+///
+/// * `value` - The computed value.
+fn finite_or_zero(value: f32) -> f32 {
+    if value.is_finite() {
+        value
+    } else {
+        0.0
+    }
 }
 
 /// Determines the amount of time until the battery finishes charging or until the battery is
-/// depleted.
+/// depleted. Returns `None` if the battery is charging at a rate of zero, which some batteries
+/// report momentarily, since the time to full would be unbounded.
 ///
 /// # Arguments
 ///
-/// * `remaining_capacity` - The current charge of the battery in mAh.
-/// * `full_capacity` - The full charge of the battery in mAh.
-/// * `present_rate` - The rate at which the current charge is changing in mA.
+/// * `remaining_capacity` - The current charge of the battery in mAh, or energy in mWh.
+/// * `full_capacity` - The full charge of the battery in mAh, or energy in mWh.
+/// * `present_rate` - The rate at which the current charge is changing in mA, or mW for energy.
 /// * `state` - Whether the battery is charging or discharging energy.
 fn determine_time_to_state_change(
     remaining_capacity: u32,
     full_capacity: u32,
     present_rate: u32,
     state: ChargingState,
-) -> time::Duration {
+) -> Option<time::Duration> {
     match state {
+        ChargingState::Charging if present_rate == 0 => None,
         ChargingState::Charging => {
-            let seconds = (3600 * (full_capacity - remaining_capacity) / (present_rate + 1)) as u64;
-            time::Duration::new(seconds, 0)
+            // Batteries can report more than their full capacity after calibration
+            let seconds = u64::from(full_capacity.saturating_sub(remaining_capacity)) * 3600
+                / (u64::from(present_rate) + 1);
+            Some(time::Duration::new(seconds, 0))
         }
         ChargingState::Discharging => {
-            let seconds = (3600 * remaining_capacity / (present_rate + 1)) as u64;
-            time::Duration::new(seconds, 0)
+            let seconds = u64::from(remaining_capacity) * 3600 / (u64::from(present_rate) + 1);
+            Some(time::Duration::new(seconds, 0))
+        }
+        _ => Some(time::Duration::new(0, 0)),
+    }
+}
+
+/// Reads the charging status string of a battery, falling back to the device's `uevent` file when
+/// the standalone `status` attribute is missing. Returns `None` if the battery reports its status
+/// in neither.
+///
+/// # Arguments
+///
+/// * `source` - The source to read the device's attributes from.
+/// * `path` - The path to the ACPI device.
+fn read_status(
+    source: &dyn AttributeSource,
+    path: &path::Path,
+) -> Result<Option<String>, AcpiClientError> {
+    let status_path = path.join(attrs::STATUS);
+    if source.exists(&status_path) {
+        read_attribute(source, &status_path).map(Some)
+    } else {
+        Ok(read_uevent_entry(source, path, attrs::UEVENT_STATUS).ok())
+    }
+}
+
+/// Reads the model name of a battery, falling back to the `uevent` file for devices which don't
+/// expose `model_name` as a standalone attribute.
+///
+/// # Arguments
+///
+/// * `source` - The source to read the device's attributes from.
+/// * `path` - The path to the ACPI device.
+fn read_model_name(source: &dyn AttributeSource, path: &path::Path) -> Option<String> {
+    let model_name_path = path.join(attrs::MODEL_NAME);
+    let model_name = if source.exists(&model_name_path) {
+        read_attribute(source, &model_name_path)
+    } else {
+        read_uevent_entry(source, path, attrs::UEVENT_MODEL_NAME)
+    };
+    model_name
+        .ok()
+        .filter(|model_name| !model_name.is_empty())
+        .or_else(|| read_device_attribute(source, path, attrs::DEVICE_MODEL))
+}
+
+/// Reads the manufacturer of a battery, falling back to the hardware device backing the power
+/// supply when the battery doesn't report one itself.
+///
+/// # Arguments
+///
+/// * `source` - The source to read the attributes from
+/// * `path` - The path to the battery device
+fn read_manufacturer(source: &dyn AttributeSource, path: &path::Path) -> Option<String> {
+    let manufacturer_path = path.join(attrs::MANUFACTURER);
+    let manufacturer = if source.exists(&manufacturer_path) {
+        read_attribute(source, &manufacturer_path).ok()
+    } else {
+        None
+    };
+    manufacturer
+        .filter(|manufacturer| !manufacturer.is_empty())
+        .or_else(|| read_device_attribute(source, path, attrs::MANUFACTURER))
+}
+
+/// Reads the date a battery was manufactured from its `manufacture_year`, `manufacture_month`, and
+/// `manufacture_day` attributes, returning `None` if it doesn't report the year.
+///
+/// # Arguments
+///
+/// * `source` - The source to read the attributes from
+/// * `path` - The path to the battery device
+fn read_manufacture_date(source: &dyn AttributeSource, path: &path::Path) -> Option<String> {
+    let year = read_optional_attribute_to_i32(source, &path.join(attrs::MANUFACTURE_YEAR), 1)
+        .filter(|year| *year > 0)?;
+    let mut date = format!("{:04}", year);
+    let month = read_optional_attribute_to_i32(source, &path.join(attrs::MANUFACTURE_MONTH), 1)
+        .filter(|month| (1..=12).contains(month));
+    if let Some(month) = month {
+        date.push_str(&format!("-{:02}", month));
+        let day = read_optional_attribute_to_i32(source, &path.join(attrs::MANUFACTURE_DAY), 1)
+            .filter(|day| (1..=31).contains(day));
+        if let Some(day) = day {
+            date.push_str(&format!("-{:02}", day));
         }
-        _ => time::Duration::new(0, 0),
     }
+    Some(date)
+}
+
+/// Reads an attribute of the hardware device beneath a power supply's `device` directory, which
+/// holds details like the model of UPS and USB supplies. Returns `None` if the power supply has no
+/// `device` directory or the attribute is missing or empty.
+///
+/// # Arguments
+///
+/// * `source` - The source to read the attribute from
+/// * `path` - The path to the power supply device
+/// * `name` - The name of the attribute beneath the `device` directory
+fn read_device_attribute(
+    source: &dyn AttributeSource,
+    path: &path::Path,
+    name: &str,
+) -> Option<String> {
+    let attribute_path = path.join(attrs::DEVICE).join(name);
+    if !source.exists(&attribute_path) {
+        return None;
+    }
+    read_attribute(source, &attribute_path)
+        .ok()
+        .filter(|value| !value.is_empty())
 }
 
-/// Parses a ChargingState value from a string representation.
+/// Parses a ChargingState value from a string representation, such as the contents of a battery's
+/// `status` attribute. The string is normalized first, so variations in case, whitespace, and
+/// separators between words are accepted.
 ///
 /// # Arguments
 ///
-/// * `state_str` - A trimmed string containing the state read from the battery device's file.
-fn parse_state_from_str(state_str: String) -> Result<ChargingState, AcpiClientError> {
-    if state_str == "charging" {
+/// * `state_str` - A string containing the state, e.g. `Not charging`.
+pub fn parse_charging_state(state_str: &str) -> Result<ChargingState, AcpiClientError> {
+    let normalized = state_str
+        .to_lowercase()
+        .split(|c: char| c.is_whitespace() || c == '_' || c == '-')
+        .filter(|word| !word.is_empty())
+        .collect::<Vec<&str>>()
+        .join(" ");
+    if normalized == "charging" {
         Ok(ChargingState::Charging)
-    } else if state_str == "discharging" {
+    } else if normalized == "discharging" {
         Ok(ChargingState::Discharging)
-    } else if state_str == "full" {
+    } else if normalized == "full" {
         Ok(ChargingState::Full)
+    } else if normalized == "not charging" || normalized == "notcharging" {
+        Ok(ChargingState::NotCharging)
+    } else if normalized == "unknown" {
+        Ok(ChargingState::Unknown)
     } else {
-        Err(AcpiClientError::InvalidInput(std::io::Error::new(
-            std::io::ErrorKind::Other,
-            format!("Unrecognized charging state: {}", state_str),
+        Err(AcpiClientError::InvalidInput(std::io::Error::other(
+            format!("Unrecognized charging state: {}", state_str.trim()),
         )))
     }
 }
 
+/// Infers the state of a battery reporting an unknown state from the sign of its rate, which is
+/// positive while charging and negative while discharging.
+///
+/// # Arguments
+///
+/// * `present_rate` - The rate as reported by the battery, if it reports one.
+fn infer_state_from_rate(present_rate: Option<i32>) -> ChargingState {
+    match present_rate {
+        Some(rate) if rate > 0 => ChargingState::Charging,
+        Some(rate) if rate < 0 => ChargingState::Discharging,
+        _ => ChargingState::Unknown,
+    }
+}
+
+/// Infers the state of a battery which reports neither a known state nor a rate from its coarse
+/// charge level. Only a level of `Full` says anything about the state, mapping to `Full`, while
+/// the other levels leave the state unknown.
+///
+/// # Arguments
+///
+/// * `capacity_level` - The coarse charge level reported by the battery, if it reports one.
+fn infer_state_from_capacity_level(capacity_level: Option<CapacityLevel>) -> ChargingState {
+    match capacity_level {
+        Some(CapacityLevel::Full) => ChargingState::Full,
+        _ => ChargingState::Unknown,
+    }
+}
+
+/// Reads the coarse charge level of a battery, returning `None` if the battery doesn't report one
+/// or reports it as unknown.
+///
+/// # Arguments
+///
+/// * `source` - The source to read the device's attributes from.
+/// * `path` - The path to the ACPI device.
+fn read_capacity_level(source: &dyn AttributeSource, path: &path::Path) -> Option<CapacityLevel> {
+    match read_attribute(source, &path.join(attrs::CAPACITY_LEVEL))
+        .ok()?
+        .to_lowercase()
+        .as_str()
+    {
+        "critical" => Some(CapacityLevel::Critical),
+        "low" => Some(CapacityLevel::Low),
+        "normal" => Some(CapacityLevel::Normal),
+        "high" => Some(CapacityLevel::High),
+        "full" => Some(CapacityLevel::Full),
+        _ => None,
+    }
+}
+
+/// Returns the path of the first of several candidate attribute names the device provides, or of
+/// the first candidate if it provides none of them.
+///
+/// # Arguments
+///
+/// * `path` - The path to the ACPI device.
+/// * `names` - The candidate attribute names in order of preference.
+fn find_attribute(path: &path::Path, names: &[&str]) -> path::PathBuf {
+    names
+        .iter()
+        .map(|name| path.join(name))
+        .find(|attribute_path| attribute_path.exists())
+        .unwrap_or_else(|| path.join(names[0]))
+}
+
+/// Reads the temperature of a battery in degrees Celsius from its `temp` attribute, falling back to
+/// the first hardware monitor directory beneath the device, e.g. `hwmon2`, with a valid
+/// `temp1_input` reading.
+///
+/// # Arguments
+///
+/// * `source` - The source to read the device's attributes from.
+/// * `path` - The path to the ACPI device.
+fn read_temperature(source: &dyn AttributeSource, path: &path::Path) -> Option<f32> {
+    if let Some(temperature) = read_optional_attribute_to_i32(source, &path.join(attrs::TEMP), 1) {
+        return Some(temperature as f32 / 10.);
+    }
+    source
+        .list(path)
+        .ok()?
+        .iter()
+        .filter(|name| name.starts_with(attrs::HWMON_PREFIX))
+        .find_map(|hwmon| {
+            let input_path = path.join(hwmon).join(attrs::HWMON_TEMP1_INPUT);
+            read_optional_attribute_to_i32(source, &input_path, 1)
+                .map(|temperature| temperature as f32 / 1000.)
+        })
+}
+
+/// Reads a percentage, such as a charge threshold, from the first of several candidate attribute
+/// names the device provides, as the naming differs between drivers.
+///
+/// # Arguments
+///
+/// * `source` - The source to read the device's attributes from.
+/// * `path` - The path to the ACPI device.
+/// * `names` - The candidate attribute names in order of preference.
+fn read_threshold(source: &dyn AttributeSource, path: &path::Path, names: &[&str]) -> Option<u8> {
+    names
+        .iter()
+        .find_map(|name| read_optional_attribute_to_i32(source, &path.join(name), 1))
+        .map(|threshold| threshold.clamp(0, 100) as u8)
+}
+
 /// An enumeration of different types of units with which the ACPI subsystem reports capacity.
 #[derive(Clone)]
 enum ReportType {
@@ -216,29 +2525,57 @@ enum ReportType {
     Energy,
 }
 
-/// Checks the filesystem to determine if the battery reports capacity or energy
+/// Checks the filesystem to determine if the battery reports capacity or energy. A few drivers
+/// report both, in which case see `reconcile_reporting_type` for the tie-break.
 ///
 /// # Arguments
 ///
+/// * `source` - The source to read the device's attributes from.
 /// * `path` - The path to the ACPI device.
-fn determine_reporting_type(path: &path::Path) -> Result<ReportType, AcpiClientError> {
-    let capacity_files = vec!["charge_now", "charge_full", "charge_full_design"];
-    let energy_files = vec!["energy_now", "energy_full", "energy_full_design"];
-    if capacity_files.iter().all(|file| {
-        let mut path_buffer = path::Path::new(path).to_path_buf();
-        path_buffer.push(file);
-        path_buffer.exists()
-    }) {
+/// * `zero_as_missing` - Whether a set of attributes whose full capacities read zero is passed
+///   over for the other set when the battery reports both.
+fn determine_reporting_type(
+    source: &dyn AttributeSource,
+    path: &path::Path,
+    zero_as_missing: bool,
+) -> Result<ReportType, AcpiClientError> {
+    let capacity_files = [
+        attrs::CHARGE_NOW,
+        attrs::CHARGE_FULL,
+        attrs::CHARGE_FULL_DESIGN,
+    ];
+    let energy_files = [
+        attrs::ENERGY_NOW,
+        attrs::ENERGY_FULL,
+        attrs::ENERGY_FULL_DESIGN,
+    ];
+    let reports_capacity = capacity_files
+        .iter()
+        .all(|file| source.exists(&path.join(file)));
+    let reports_energy = energy_files
+        .iter()
+        .all(|file| source.exists(&path.join(file)));
+    let reads_zero = |files: [&str; 2]| {
+        zero_as_missing
+            && files
+                .iter()
+                .any(|file| read_optional_attribute_to_i32(source, &path.join(file), 1) == Some(0))
+    };
+    if reports_capacity && reports_energy {
+        match (
+            reads_zero([attrs::CHARGE_FULL, attrs::CHARGE_FULL_DESIGN]),
+            reads_zero([attrs::ENERGY_FULL, attrs::ENERGY_FULL_DESIGN]),
+        ) {
+            (true, false) => Ok(ReportType::Energy),
+            (false, true) => Ok(ReportType::Capacity),
+            _ => Ok(reconcile_reporting_type(source, path)),
+        }
+    } else if reports_capacity {
         Ok(ReportType::Capacity)
-    } else if energy_files.iter().all(|file| {
-        let mut path_buffer = path::Path::new(path).to_path_buf();
-        path_buffer.push(file);
-        path_buffer.exists()
-    }) {
+    } else if reports_energy {
         Ok(ReportType::Energy)
     } else {
-        Err(AcpiClientError::InvalidInput(std::io::Error::new(
-            std::io::ErrorKind::Other,
+        Err(AcpiClientError::InvalidInput(std::io::Error::other(
             "Unrecognized reporting type.",
         )))
     }
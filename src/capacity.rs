@@ -0,0 +1,154 @@
+use std::fmt;
+
+use crate::battery::CapacityUnit;
+
+/// A charge in mAh. Converting it to an energy requires a voltage, so charges and energies can't
+/// be mixed up by accident.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MilliAmpHours(pub u32);
+
+/// An energy in mWh. Converting it to a charge requires a voltage, so charges and energies can't
+/// be mixed up by accident.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub struct MilliWattHours(pub u32);
+
+/// One of a battery's capacities in the units it was read in, which are a charge or an energy
+/// depending on the `CapacityUnit` the battery was read with.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Capacity {
+    /// The capacity is a charge.
+    Charge(MilliAmpHours),
+    /// The capacity is an energy.
+    Energy(MilliWattHours),
+}
+
+impl MilliAmpHours {
+    /// Returns the charge as a plain number of mAh.
+    pub fn value(self) -> u32 {
+        self.0
+    }
+
+    /// Returns the energy held by the charge at a given voltage.
+    ///
+    /// # Arguments
+    ///
+    /// * `voltage` - The voltage in mV.
+    pub fn to_milli_watt_hours(self, voltage: u32) -> MilliWattHours {
+        let energy = u64::from(self.0) * u64::from(voltage) / 1000;
+        MilliWattHours(energy.min(u64::from(u32::MAX)) as u32)
+    }
+}
+
+impl MilliWattHours {
+    /// Returns the energy as a plain number of mWh.
+    pub fn value(self) -> u32 {
+        self.0
+    }
+
+    /// Returns the charge holding the energy at a given voltage, which is zero if the voltage is
+    /// zero.
+    ///
+    /// # Arguments
+    ///
+    /// * `voltage` - The voltage in mV.
+    pub fn to_milli_amp_hours(self, voltage: u32) -> MilliAmpHours {
+        let charge = (u64::from(self.0) * 1000)
+            .checked_div(u64::from(voltage))
+            .unwrap_or(0);
+        MilliAmpHours(charge.min(u64::from(u32::MAX)) as u32)
+    }
+}
+
+impl Capacity {
+    /// Returns a capacity from a plain number in the given units.
+    ///
+    /// # Arguments
+    ///
+    /// * `value` - The capacity in mAh or mWh.
+    /// * `unit` - The units of `value`.
+    pub fn new(value: u32, unit: CapacityUnit) -> Capacity {
+        match unit {
+            CapacityUnit::MilliAmpHours => Capacity::Charge(MilliAmpHours(value)),
+            CapacityUnit::MilliWattHours => Capacity::Energy(MilliWattHours(value)),
+        }
+    }
+
+    /// Returns the capacity as a plain number in its own units.
+    pub fn value(self) -> u32 {
+        match self {
+            Capacity::Charge(charge) => charge.value(),
+            Capacity::Energy(energy) => energy.value(),
+        }
+    }
+
+    /// Returns the units of the capacity.
+    pub fn unit(self) -> CapacityUnit {
+        match self {
+            Capacity::Charge(_) => CapacityUnit::MilliAmpHours,
+            Capacity::Energy(_) => CapacityUnit::MilliWattHours,
+        }
+    }
+
+    /// Returns the capacity as a charge, converted at a given voltage if it is an energy.
+    ///
+    /// # Arguments
+    ///
+    /// * `voltage` - The voltage in mV.
+    pub fn to_milli_amp_hours(self, voltage: u32) -> MilliAmpHours {
+        match self {
+            Capacity::Charge(charge) => charge,
+            Capacity::Energy(energy) => energy.to_milli_amp_hours(voltage),
+        }
+    }
+
+    /// Returns the capacity as an energy, converted at a given voltage if it is a charge.
+    ///
+    /// # Arguments
+    ///
+    /// * `voltage` - The voltage in mV.
+    pub fn to_milli_watt_hours(self, voltage: u32) -> MilliWattHours {
+        match self {
+            Capacity::Charge(charge) => charge.to_milli_watt_hours(voltage),
+            Capacity::Energy(energy) => energy,
+        }
+    }
+}
+
+impl From<MilliAmpHours> for u32 {
+    fn from(charge: MilliAmpHours) -> u32 {
+        charge.0
+    }
+}
+
+impl From<MilliWattHours> for u32 {
+    fn from(energy: MilliWattHours) -> u32 {
+        energy.0
+    }
+}
+
+impl From<Capacity> for u32 {
+    fn from(capacity: Capacity) -> u32 {
+        capacity.value()
+    }
+}
+
+impl fmt::Display for MilliAmpHours {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} mAh", self.0)
+    }
+}
+
+impl fmt::Display for MilliWattHours {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} mWh", self.0)
+    }
+}
+
+impl fmt::Display for Capacity {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Capacity::Charge(charge) => charge.fmt(f),
+            Capacity::Energy(energy) => energy.fmt(f),
+        }
+    }
+}
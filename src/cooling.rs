@@ -1,6 +1,10 @@
-use std::fs::read_dir;
+use std::collections::BTreeMap;
+use std::convert::TryFrom;
 use std::path;
+use std::time;
 
+use crate::attrs;
+use crate::system::DeviceId;
 use crate::utils::*;
 
 /// State information on a cooling device's activity.
@@ -12,14 +16,57 @@ pub struct CoolingStatus {
     pub max_state: i32,
 }
 
+impl CoolingStatus {
+    /// Returns the current level of the device as a percentage of its maximum level, or zero if
+    /// the device has no levels above zero.
+    pub fn percentage(&self) -> f32 {
+        if self.max_state <= 0 {
+            return 0.0;
+        }
+        100. * self.current_state as f32 / self.max_state as f32
+    }
+}
+
+/// The kinds of cooling device the ACPI subsystem commonly reports.
+#[derive(Clone, Debug, PartialEq)]
+pub enum CoolingDeviceKind {
+    /// A processor which can be throttled to reduce heat.
+    Processor,
+    /// A fan.
+    Fan,
+    /// A passive radiator.
+    Radiation,
+    /// Any other kind of device, holding the type string reported by the system.
+    Other(String),
+}
+
+impl From<&str> for CoolingDeviceKind {
+    fn from(device_type: &str) -> CoolingDeviceKind {
+        match device_type.trim().to_lowercase().as_str() {
+            "processor" => CoolingDeviceKind::Processor,
+            "fan" => CoolingDeviceKind::Fan,
+            "radiation" => CoolingDeviceKind::Radiation,
+            _ => CoolingDeviceKind::Other(String::from(device_type.trim())),
+        }
+    }
+}
+
 /// Information about cooling devices available to the system.
 pub struct CoolingDevice {
     /// The name used by ACPI to refer to the device.
     pub name: String,
-    /// The activity state of the device.
+    /// The path to the device's directory.
+    pub path: path::PathBuf,
+    /// The activity state of the device, or `None` if the device reports a negative state or no
+    /// levels above zero.
     pub state: Option<CoolingStatus>,
     /// The type of device the cooling device is attached to.
     pub device_type: String,
+    /// The number of times the device has changed state, if the kernel keeps statistics for it.
+    /// A count which keeps climbing indicates the device is actively throttling.
+    pub total_transitions: Option<u64>,
+    /// The time at which the device was read.
+    pub sampled_at: time::SystemTime,
 }
 
 /// Check the ACPI system for all cooling devices available to the system.
@@ -30,8 +77,7 @@ pub struct CoolingDevice {
 pub fn get_cooling_device_info(path: &path::Path) -> Result<Vec<CoolingDevice>, AcpiClientError> {
     let mut results: Vec<CoolingDevice> = vec![];
 
-    for entry in read_dir(&path)? {
-        let path = entry?.path();
+    for path in read_device_dirs(path)? {
         if !is_thermal_sensor(&path) {
             let device = CoolingDevice::new(&path);
             if device.is_ok() {
@@ -43,6 +89,58 @@ pub fn get_cooling_device_info(path: &path::Path) -> Result<Vec<CoolingDevice>,
     Ok(results)
 }
 
+/// Reads a single cooling device by name without scanning the other entries.
+///
+/// # Arguments
+///
+/// * `path` - The path to the cooling device entries produced by the ACPI subsystem.
+/// * `name` - The name used by ACPI to refer to the device, e.g. `cooling_device0`.
+pub fn get_cooling_device_by_name(
+    path: &path::Path,
+    name: &str,
+) -> Result<CoolingDevice, AcpiClientError> {
+    CoolingDevice::new(&device_path(path, name)?)
+}
+
+/// Returns the average activity of the cooling devices of each type as a percentage of their
+/// maximum levels, keyed by the type reported by the devices, e.g. `Fan`. Devices without a known
+/// state are skipped.
+///
+/// # Arguments
+///
+/// * `devices` - The cooling devices to aggregate.
+pub fn aggregate_cooling_by_type(devices: &[CoolingDevice]) -> BTreeMap<String, f32> {
+    let mut totals: BTreeMap<String, (f32, u32)> = BTreeMap::new();
+    for device in devices {
+        if let Some(status) = device.state {
+            let total = totals.entry(device.device_type.clone()).or_insert((0.0, 0));
+            total.0 += status.percentage();
+            total.1 += 1;
+        }
+    }
+
+    totals
+        .into_iter()
+        .map(|(device_type, (sum, count))| (device_type, sum / count as f32))
+        .collect()
+}
+
+impl TryFrom<&path::Path> for CoolingDevice {
+    type Error = AcpiClientError;
+
+    fn try_from(path: &path::Path) -> Result<CoolingDevice, AcpiClientError> {
+        CoolingDevice::new(path)
+    }
+}
+
+impl TryFrom<path::PathBuf> for CoolingDevice {
+    type Error = AcpiClientError;
+
+    fn try_from(path: path::PathBuf) -> Result<CoolingDevice, AcpiClientError> {
+        CoolingDevice::try_from(path.as_path())
+    }
+}
+
 impl CoolingDevice {
     /// Create a new cooling device object from data from the ACPI subsystem.
     ///
@@ -50,12 +148,15 @@ impl CoolingDevice {
     ///
     /// * `path` - The path to the cooling device entry.
     pub fn new(path: &path::Path) -> Result<CoolingDevice, AcpiClientError> {
+        let sampled_at = time::SystemTime::now();
         let name = get_device_name(path)?;
-        let current_state = parse_file_to_i32(&path.join("cur_state"), 1)?;
-        let max_state = parse_file_to_i32(&path.join("max_state"), 1)?;
-        let device_type = parse_entry_file(&path.join("type"))?;
+        let current_state = parse_file_to_i32(&path.join(attrs::CUR_STATE), 1)?;
+        let max_state = parse_file_to_i32(&path.join(attrs::MAX_STATE), 1)?;
+        let device_type = parse_entry_file(&path.join(attrs::TYPE))?;
+        let total_transitions = read_total_transitions(path);
 
-        let status = if current_state >= 0 {
+        // A device without any levels above zero can't be driven and is likely disabled
+        let status = if current_state >= 0 && max_state > 0 {
             Some(CoolingStatus {
                 current_state,
                 max_state,
@@ -65,8 +166,69 @@ impl CoolingDevice {
         };
         Ok(CoolingDevice {
             name,
+            path: path.to_path_buf(),
             state: status,
             device_type,
+            total_transitions,
+            sampled_at,
         })
     }
+
+    /// Create a new cooling device object from the directory of a device named `name` within a class
+    /// directory, returning a `NotFound` error if there is no such device.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The path to the thermal entries produced by the ACPI subsystem.
+    /// * `name` - The name used by ACPI to refer to the device, e.g. `cooling_device0`.
+    pub fn from_root_and_name(
+        root: &path::Path,
+        name: &str,
+    ) -> Result<CoolingDevice, AcpiClientError> {
+        CoolingDevice::new(&device_path(root, name)?)
+    }
+
+    /// Returns the kind of the device, interpreted from the raw `device_type` string.
+    pub fn kind(&self) -> CoolingDeviceKind {
+        CoolingDeviceKind::from(self.device_type.as_str())
+    }
+
+    /// Sets the activity level of the device by writing its `cur_state` attribute, e.g. to drive a
+    /// fan, which requires root privileges. The device's `max_state` is read again before writing,
+    /// so the state is validated even when the cached status is unknown.
+    ///
+    /// # Arguments
+    ///
+    /// * `state` - The level to set, from zero up to the device's `max_state`.
+    pub fn set_state(&self, state: i32) -> Result<(), AcpiClientError> {
+        let max_state = parse_file_to_i32(&self.path.join(attrs::MAX_STATE), 1)?;
+        if state < 0 || state > max_state {
+            return Err(AcpiClientError::InvalidInput(std::io::Error::other(
+                format!(
+                    "State {} is outside the range 0 to {} of {}.",
+                    state, max_state, self.name
+                ),
+            )));
+        }
+        write_attribute(&self.path.join(attrs::CUR_STATE), &state.to_string())
+    }
+
+    /// Returns the identity of the device, for use as a key across polls.
+    pub fn id(&self) -> DeviceId {
+        DeviceId(self.name.clone())
+    }
+}
+
+/// Reads the number of state transitions from a cooling device's `stats` directory, returning
+/// `None` if the kernel doesn't keep statistics for the device.
+///
+/// # Arguments
+///
+/// * `path` - The path to the cooling device entry.
+fn read_total_transitions(path: &path::Path) -> Option<u64> {
+    let stats_path = path.join(attrs::STATS_TOTAL_TRANS);
+    if !stats_path.exists() {
+        return None;
+    }
+    parse_entry_file(&stats_path).ok()?.parse::<u64>().ok()
 }
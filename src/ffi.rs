@@ -0,0 +1,111 @@
+use std::time;
+
+use crate::battery::*;
+
+/// The size in bytes of the buffer holding a battery's name in `BatteryInfoFfi`, including the
+/// terminating NUL byte.
+pub const FFI_NAME_LEN: usize = 32;
+
+/// A battery's readings laid out as a C struct, for passing a battery across an FFI boundary to
+/// bindings in languages such as C or Python.
+///
+/// The struct is plain old data: it owns no heap memory and holds no pointers, so it can be copied
+/// freely and needs no function to free it. The name is stored inline as NUL-terminated UTF-8,
+/// truncated to fit the buffer, and values which are optional in `BatteryInfo` use `-1` to mean
+/// unknown.
+#[repr(C)]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BatteryInfoFfi {
+    /// The name used by ACPI to refer to the device as NUL-terminated UTF-8.
+    pub name: [u8; FFI_NAME_LEN],
+    /// The state of the battery's charging, see `ffi_state_code` for the values.
+    pub state: i32,
+    /// The ratio of the remaining charge to the full charge.
+    pub percentage: f32,
+    /// The charge remaining in the battery in the units given by `capacity_unit`.
+    pub remaining_capacity: u32,
+    /// The charge available in the battery at the last time it was charged to full.
+    pub last_capacity: u32,
+    /// The charge available in the battery at the time of manufacture.
+    pub design_capacity: u32,
+    /// The units of the capacities, `0` for mAh and `1` for mWh.
+    pub capacity_unit: i32,
+    /// The rate at which the charge of the battery is changing.
+    pub present_rate: u32,
+    /// The units of the present rate, `0` for mA and `1` for mW.
+    pub present_rate_unit: i32,
+    /// The voltage of the battery in mV.
+    pub voltage: u32,
+    /// The seconds remaining until the battery reaches full charge or empty, or `-1` if unknown.
+    pub time_remaining_secs: i64,
+    /// The time at which the battery was read in seconds since the Unix epoch.
+    pub sampled_at_secs: u64,
+}
+
+/// Returns the integer representing a charging state in `BatteryInfoFfi`: `0` for charging, `1`
+/// for discharging, `2` for full, `3` for not charging, and `4` for unknown.
+///
+/// # Arguments
+///
+/// * `state` - The charging state to represent.
+pub fn ffi_state_code(state: ChargingState) -> i32 {
+    match state {
+        ChargingState::Charging => 0,
+        ChargingState::Discharging => 1,
+        ChargingState::Full => 2,
+        ChargingState::NotCharging => 3,
+        ChargingState::Unknown => 4,
+    }
+}
+
+impl BatteryInfoFfi {
+    /// Returns the battery's name from the inline buffer, up to the terminating NUL byte.
+    pub fn name_str(&self) -> &str {
+        let length = self
+            .name
+            .iter()
+            .position(|byte| *byte == 0)
+            .unwrap_or(FFI_NAME_LEN);
+        // The buffer is only ever filled from a string cut on a character boundary
+        std::str::from_utf8(&self.name[..length]).unwrap_or_default()
+    }
+}
+
+impl From<&BatteryInfo> for BatteryInfoFfi {
+    fn from(battery: &BatteryInfo) -> BatteryInfoFfi {
+        let mut name = [0; FFI_NAME_LEN];
+        // Leave room for the terminating NUL byte without splitting a character
+        let mut length = battery.name.len().min(FFI_NAME_LEN - 1);
+        while !battery.name.is_char_boundary(length) {
+            length -= 1;
+        }
+        name[..length].copy_from_slice(&battery.name.as_bytes()[..length]);
+
+        BatteryInfoFfi {
+            name,
+            state: ffi_state_code(battery.state),
+            percentage: battery.percentage,
+            remaining_capacity: battery.remaining_capacity.value(),
+            last_capacity: battery.last_capacity.value(),
+            design_capacity: battery.design_capacity.value(),
+            capacity_unit: match battery.capacity_unit {
+                CapacityUnit::MilliAmpHours => 0,
+                CapacityUnit::MilliWattHours => 1,
+            },
+            present_rate: battery.present_rate,
+            present_rate_unit: match battery.present_rate_unit {
+                RateUnit::MilliAmps => 0,
+                RateUnit::MilliWatts => 1,
+            },
+            voltage: battery.voltage,
+            time_remaining_secs: battery
+                .time_remaining
+                .map_or(-1, |time_remaining| time_remaining.as_secs() as i64),
+            sampled_at_secs: battery
+                .sampled_at
+                .duration_since(time::UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or_default(),
+        }
+    }
+}
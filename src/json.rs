@@ -0,0 +1,90 @@
+use std::path;
+
+use serde_json::{json, Value};
+
+use crate::system::*;
+use crate::thermal_zone::*;
+use crate::utils::*;
+
+/// Returns the state of every device found beneath a root directory as a JSON tree, with the
+/// devices grouped by class under the `batteries`, `ac_adapters`, `thermal`, and `cooling` keys.
+///
+/// # Arguments
+///
+/// * `root` - The directory to treat as the root of the filesystem, usually `/`.
+/// * `units` - The units to report thermal data in.
+pub fn system_json(root: &path::Path, units: Units) -> Result<Value, AcpiClientError> {
+    let devices = scan_all(root, units)?;
+
+    let batteries: Vec<Value> = devices
+        .batteries
+        .iter()
+        .map(|battery| {
+            json!({
+                "name": battery.name,
+                "state": battery.state.to_string(),
+                "percentage": battery.percentage,
+                "time_remaining_seconds": battery.time_remaining.map(|time| time.as_secs()),
+                "remaining_capacity": battery.remaining_capacity.value(),
+                "last_capacity": battery.last_capacity.value(),
+                "design_capacity": battery.design_capacity.value(),
+                "present_rate": battery.present_rate,
+                "voltage_mv": battery.voltage,
+            })
+        })
+        .collect();
+    let ac_adapters: Vec<Value> = devices
+        .ac_adapters
+        .iter()
+        .map(|adapter| {
+            json!({
+                "name": adapter.name,
+                "online": adapter.is_online(),
+                "power_watts": adapter.power_watts,
+            })
+        })
+        .collect();
+    let thermal: Vec<Value> = devices
+        .thermal_sensors
+        .iter()
+        .map(|sensor| {
+            let trip_points: Vec<Value> = sensor
+                .trip_points
+                .iter()
+                .map(|trip_point| {
+                    json!({
+                        "number": trip_point.number,
+                        "type": trip_point.action_type,
+                        "temperature": trip_point.temperature,
+                    })
+                })
+                .collect();
+            json!({
+                "name": sensor.name,
+                "zone_type": sensor.zone_type,
+                "temperature": sensor.current_temperature,
+                "units": sensor.units.name(),
+                "trip_points": trip_points,
+            })
+        })
+        .collect();
+    let cooling: Vec<Value> = devices
+        .cooling_devices
+        .iter()
+        .map(|device| {
+            json!({
+                "name": device.name,
+                "type": device.device_type,
+                "current_state": device.state.map(|state| state.current_state),
+                "max_state": device.state.map(|state| state.max_state),
+            })
+        })
+        .collect();
+
+    Ok(json!({
+        "batteries": batteries,
+        "ac_adapters": ac_adapters,
+        "thermal": thermal,
+        "cooling": cooling,
+    }))
+}
@@ -1,9 +1,36 @@
 pub mod ac_adapter;
 pub use ac_adapter::*;
+pub mod attrs;
 pub mod battery;
 pub use battery::*;
+pub mod capacity;
+pub use capacity::*;
 pub mod cooling;
 pub use cooling::*;
+pub mod ffi;
+pub use ffi::*;
+#[cfg(feature = "json")]
+pub mod json;
+pub mod logger;
+pub use logger::*;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod powercap;
+pub use powercap::*;
+pub mod power_supply;
+pub use power_supply::*;
+pub mod snapshot;
+pub use snapshot::*;
+pub mod source;
+pub use source::*;
+#[cfg(feature = "async")]
+pub mod stream;
+pub mod system;
+pub use system::*;
 pub mod thermal_zone;
 pub use thermal_zone::*;
+#[cfg(feature = "timing")]
+pub mod timing;
+pub mod ups;
+pub use ups::*;
 pub mod utils;
@@ -0,0 +1,109 @@
+use std::fs;
+use std::io::Write;
+use std::path;
+use std::time;
+
+use crate::battery::*;
+use crate::system::*;
+use crate::thermal_zone::*;
+use crate::utils::*;
+
+/// The header row written to a new log file.
+const CSV_HEADER: &str = "timestamp,percentage,state,rate,voltage,temperature_c";
+
+/// Appends readings of the system's battery and temperature to a CSV file at a fixed interval,
+/// for studying a battery's behaviour over a long period.
+pub struct CsvLogger {
+    path: path::PathBuf,
+    interval: time::Duration,
+}
+
+impl CsvLogger {
+    /// Create a new logger which appends to a file, creating it if it doesn't exist.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the CSV file.
+    /// * `interval` - The time to wait between polls when running.
+    pub fn new<P: AsRef<path::Path>>(path: P, interval: time::Duration) -> CsvLogger {
+        CsvLogger {
+            path: path.as_ref().to_path_buf(),
+            interval,
+        }
+    }
+
+    /// Appends a row for a battery reading, writing the header first if the file is new. The row
+    /// is flushed to disk before returning so that a crash doesn't lose it.
+    ///
+    /// # Arguments
+    ///
+    /// * `battery` - The battery reading to log.
+    /// * `temperature` - The temperature in degrees Celsius at the time of the reading, if known.
+    pub fn append(
+        &self,
+        battery: &BatteryInfo,
+        temperature: Option<f32>,
+    ) -> Result<(), AcpiClientError> {
+        let mut file = fs::OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&self.path)?;
+        if file.metadata()?.len() == 0 {
+            writeln!(file, "{}", CSV_HEADER)?;
+        }
+
+        let timestamp = battery
+            .sampled_at
+            .duration_since(time::UNIX_EPOCH)
+            .map(|since_epoch| since_epoch.as_secs())
+            .unwrap_or(0);
+        let temperature = temperature
+            .map(|temperature| format!("{:.1}", temperature))
+            .unwrap_or_default();
+        writeln!(
+            file,
+            "{},{:.1},{},{},{},{}",
+            timestamp,
+            battery.percentage,
+            battery.state,
+            battery.present_rate,
+            battery.voltage,
+            temperature
+        )?;
+        file.sync_data()?;
+
+        Ok(())
+    }
+
+    /// Reads the first battery and the hottest thermal sensor found beneath a root directory and
+    /// appends a row for them.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The directory to treat as the root of the filesystem, usually `/`.
+    pub fn poll(&self, root: &path::Path) -> Result<(), AcpiClientError> {
+        let devices = scan_all(root, Units::Celsius)?;
+        let battery = devices.batteries.first().ok_or_else(|| {
+            AcpiClientError::InvalidInput(std::io::Error::other("No battery found."))
+        })?;
+        let temperature =
+            hottest_sensor(&devices.thermal_sensors).map(|sensor| sensor.current_temperature);
+        self.append(battery, temperature)
+    }
+
+    /// Polls the system a number of times, waiting for the logger's interval between each poll.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The directory to treat as the root of the filesystem, usually `/`.
+    /// * `polls` - The number of rows to append.
+    pub fn run(&self, root: &path::Path, polls: usize) -> Result<(), AcpiClientError> {
+        for poll in 0..polls {
+            if poll > 0 {
+                std::thread::sleep(self.interval);
+            }
+            self.poll(root)?;
+        }
+        Ok(())
+    }
+}
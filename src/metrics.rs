@@ -0,0 +1,177 @@
+use std::fmt::Write;
+use std::path;
+
+use crate::system::*;
+use crate::thermal_zone::*;
+use crate::utils::*;
+
+/// Renders the state of every device found beneath a root directory in the Prometheus text
+/// exposition format, e.g. `acpi_battery_percentage{name="BAT0"} 83`.
+///
+/// # Arguments
+///
+/// * `root` - The directory to treat as the root of the filesystem, usually `/`.
+/// * `units` - The units to report thermal data in.
+pub fn render_prometheus(root: &path::Path, units: Units) -> Result<String, AcpiClientError> {
+    let devices = scan_all(root, units)?;
+    let mut output = String::new();
+
+    write_family(
+        &mut output,
+        "acpi_battery_percentage",
+        "Remaining charge of the battery as a percentage of full charge.",
+        devices
+            .batteries
+            .iter()
+            .map(|battery| (&battery.name, battery.percentage)),
+    );
+    write_family(
+        &mut output,
+        "acpi_battery_remaining_capacity_mah",
+        "Remaining charge of the battery in mAh.",
+        devices
+            .batteries
+            .iter()
+            .map(|battery| (&battery.name, battery.remaining_capacity.value() as f32)),
+    );
+    write_family(
+        &mut output,
+        "acpi_battery_present_rate",
+        "Rate at which the charge of the battery is changing.",
+        devices
+            .batteries
+            .iter()
+            .map(|battery| (&battery.name, battery.present_rate as f32)),
+    );
+    write_family(
+        &mut output,
+        "acpi_battery_voltage_mv",
+        "Present voltage of the battery in mV.",
+        devices
+            .batteries
+            .iter()
+            .map(|battery| (&battery.name, battery.voltage as f32)),
+    );
+    write_family(
+        &mut output,
+        "acpi_battery_time_remaining_seconds",
+        "Time until the battery is full or empty.",
+        devices.batteries.iter().filter_map(|battery| {
+            battery
+                .time_remaining
+                .map(|time_remaining| (&battery.name, time_remaining.as_secs() as f32))
+        }),
+    );
+    write_family(
+        &mut output,
+        "acpi_ac_online",
+        "Whether the AC adapter is plugged in.",
+        devices
+            .ac_adapters
+            .iter()
+            .map(|adapter| (&adapter.name, if adapter.is_online() { 1. } else { 0. })),
+    );
+
+    let thermal_family = match units {
+        Units::Celsius => "acpi_thermal_celsius",
+        Units::Fahrenheit => "acpi_thermal_fahrenheit",
+        Units::Kelvin => "acpi_thermal_kelvin",
+    };
+    write_labeled_family(
+        &mut output,
+        thermal_family,
+        "Current temperature of the thermal zone.",
+        "zone",
+        devices
+            .thermal_sensors
+            .iter()
+            .map(|sensor| (&sensor.name, sensor.current_temperature)),
+    );
+    write_family(
+        &mut output,
+        "acpi_cooling_state",
+        "Current activity level of the cooling device.",
+        devices.cooling_devices.iter().filter_map(|device| {
+            device
+                .state
+                .map(|state| (&device.name, state.current_state as f32))
+        }),
+    );
+    write_family(
+        &mut output,
+        "acpi_cooling_max_state",
+        "Maximum activity level of the cooling device.",
+        devices.cooling_devices.iter().filter_map(|device| {
+            device
+                .state
+                .map(|state| (&device.name, state.max_state as f32))
+        }),
+    );
+
+    Ok(output)
+}
+
+/// Writes a gauge metric family labeled by device name.
+///
+/// # Arguments
+///
+/// * `output` - The string to append the family to.
+/// * `family` - The name of the metric family.
+/// * `help` - A description of the metric.
+/// * `samples` - The device names and values to write.
+fn write_family<'a, I>(output: &mut String, family: &str, help: &str, samples: I)
+where
+    I: Iterator<Item = (&'a String, f32)>,
+{
+    write_labeled_family(output, family, help, "name", samples)
+}
+
+/// Writes a gauge metric family, omitting it entirely if there are no samples.
+///
+/// # Arguments
+///
+/// * `output` - The string to append the family to.
+/// * `family` - The name of the metric family.
+/// * `help` - A description of the metric.
+/// * `label` - The name of the label holding each device's name.
+/// * `samples` - The device names and values to write.
+fn write_labeled_family<'a, I>(
+    output: &mut String,
+    family: &str,
+    help: &str,
+    label: &str,
+    samples: I,
+) where
+    I: Iterator<Item = (&'a String, f32)>,
+{
+    let mut samples = samples.peekable();
+    if samples.peek().is_none() {
+        return;
+    }
+
+    // Writing to a String cannot fail
+    let _ = writeln!(output, "# HELP {} {}", family, help);
+    let _ = writeln!(output, "# TYPE {} gauge", family);
+    for (name, value) in samples {
+        let _ = writeln!(
+            output,
+            "{}{{{}=\"{}\"}} {}",
+            family,
+            label,
+            escape_label_value(name),
+            value
+        );
+    }
+}
+
+/// Escapes a string for use as a label value in the text exposition format.
+///
+/// # Arguments
+///
+/// * `value` - The label value to escape.
+fn escape_label_value(value: &str) -> String {
+    value
+        .replace('\\', "\\\\")
+        .replace('"', "\\\"")
+        .replace('\n', "\\n")
+}
@@ -0,0 +1,143 @@
+use std::path;
+
+use crate::ac_adapter::*;
+use crate::attrs;
+use crate::battery::*;
+use crate::system::DeviceId;
+use crate::utils::*;
+
+/// The kinds of power supply the kernel reports in a device's `type` attribute.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PowerSupplyType {
+    Battery,
+    Mains,
+    Usb,
+    Ups,
+    Wireless,
+    /// Any other kind of supply, holding the type string reported by the system.
+    Other(String),
+}
+
+impl From<&str> for PowerSupplyType {
+    fn from(supply_type: &str) -> PowerSupplyType {
+        if is_battery_type(supply_type) {
+            return PowerSupplyType::Battery;
+        }
+        match supply_type.trim().to_lowercase().as_str() {
+            "mains" => PowerSupplyType::Mains,
+            "usb" => PowerSupplyType::Usb,
+            "ups" => PowerSupplyType::Ups,
+            "wireless" => PowerSupplyType::Wireless,
+            _ => PowerSupplyType::Other(String::from(supply_type.trim())),
+        }
+    }
+}
+
+/// The data specific to the kind of a power supply.
+// Most systems have only a handful of supplies, so the size of a battery isn't worth boxing
+#[allow(clippy::large_enum_variant)]
+pub enum PowerSupplyData {
+    /// The data of a battery.
+    Battery(BatteryInfo),
+    /// The data of a supply which is either online or offline, like an AC adapter.
+    Adapter(ACAdapterInfo),
+}
+
+/// The supply the system is drawing its power from.
+#[derive(Clone, Debug, PartialEq)]
+pub enum PowerSource {
+    /// No external supply is online, so the system is running on its batteries.
+    Battery,
+    /// A mains adapter, holding the name of the supply.
+    Mains(String),
+    /// A USB supply, such as a USB-C PD charger, holding the name of the supply.
+    Usb(String),
+}
+
+/// Information about any device in the power supply class.
+pub struct PowerSupplyInfo {
+    /// The name used by ACPI to refer to the device.
+    pub name: String,
+    /// The path to the device's directory.
+    pub path: path::PathBuf,
+    /// The kind of the power supply.
+    pub kind: PowerSupplyType,
+    /// Whether the power supply is a battery.
+    pub is_battery: bool,
+    /// The data specific to the kind of power supply.
+    pub data: PowerSupplyData,
+}
+
+impl PowerSupplyInfo {
+    /// Returns the identity of the power supply, for use as a key across polls.
+    pub fn id(&self) -> DeviceId {
+        DeviceId(self.name.clone())
+    }
+}
+
+/// Check the ACPI system for every power supply the OS knows about, including batteries, mains
+/// adapters, and USB supplies. Devices which can't be parsed are skipped.
+///
+/// # Arguments
+///
+/// * `path` - The path to power supply entries produced by the ACPI subsystem.
+pub fn get_power_supply_info(path: &path::Path) -> Result<Vec<PowerSupplyInfo>, AcpiClientError> {
+    let mut results: Vec<PowerSupplyInfo> = vec![];
+
+    for path in read_device_dirs(path)? {
+        // A device without a readable type can't be identified, so skip it
+        let kind = match parse_entry_file(&path.join(attrs::TYPE)) {
+            Ok(device_type) => PowerSupplyType::from(device_type.as_str()),
+            Err(_) => continue,
+        };
+        let data = if kind == PowerSupplyType::Battery {
+            BatteryInfo::new(&path).map(PowerSupplyData::Battery)
+        } else {
+            ACAdapterInfo::new(&path).map(PowerSupplyData::Adapter)
+        };
+        if let Ok(data) = data {
+            results.push(PowerSupplyInfo {
+                name: get_device_name(&path)?,
+                path: path.clone(),
+                is_battery: kind == PowerSupplyType::Battery,
+                kind,
+                data,
+            });
+        }
+    }
+
+    Ok(results)
+}
+
+/// Determines which supply is powering the system from the power supplies which report being
+/// online. A mains adapter is preferred over a USB supply when both are online, since a barrel
+/// adapter is usually the primary supply, and supplies of the same kind are chosen between by
+/// name.
+///
+/// # Arguments
+///
+/// * `path` - The path to power supply entries produced by the ACPI subsystem.
+pub fn active_power_source(path: &path::Path) -> Result<PowerSource, AcpiClientError> {
+    let mut online: Vec<PowerSupplyInfo> = get_power_supply_info(path)?
+        .into_iter()
+        .filter(|supply| match supply.data {
+            PowerSupplyData::Adapter(ref adapter) => adapter.is_online(),
+            PowerSupplyData::Battery(_) => false,
+        })
+        .collect();
+    online.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mains = online
+        .iter()
+        .find(|supply| supply.kind == PowerSupplyType::Mains);
+    let usb = online
+        .iter()
+        .find(|supply| supply.kind == PowerSupplyType::Usb);
+    let source = match (mains, usb) {
+        (Some(supply), _) => PowerSource::Mains(supply.name.clone()),
+        (None, Some(supply)) => PowerSource::Usb(supply.name.clone()),
+        (None, None) => PowerSource::Battery,
+    };
+
+    Ok(source)
+}
@@ -0,0 +1,104 @@
+use std::path;
+use std::time;
+
+use crate::attrs;
+use crate::system::DeviceId;
+use crate::utils::*;
+
+/// An energy counter of a RAPL (Running Average Power Limit) domain, e.g. a processor package or
+/// its DRAM, read from the powercap subsystem.
+pub struct RaplDomain {
+    /// The name used by the powercap subsystem to refer to the domain, e.g. `intel-rapl:0`.
+    pub name: String,
+    /// The path to the domain's directory.
+    pub path: path::PathBuf,
+    /// The part of the system the domain measures, e.g. `package-0` or `dram`.
+    pub domain_name: String,
+    /// The energy consumed by the domain in µJ since an arbitrary point in time.
+    pub energy_uj: u64,
+    /// The value at which `energy_uj` wraps around to zero in µJ, if the domain reports it.
+    pub max_energy_range_uj: Option<u64>,
+    /// The time at which the domain was read.
+    pub sampled_at: time::SystemTime,
+}
+
+/// Check the powercap subsystem for all RAPL domains, including the subdomains of a package such
+/// as its cores. Domains which can't be parsed are skipped.
+///
+/// # Arguments
+///
+/// * `path` - The path to the powercap entries, e.g. `/sys/class/powercap`.
+pub fn get_rapl_domains(path: &path::Path) -> Result<Vec<RaplDomain>, AcpiClientError> {
+    let mut results: Vec<RaplDomain> = vec![];
+
+    for path in read_device_dirs(path)? {
+        let is_rapl = get_device_name(&path).is_ok_and(|name| name.starts_with(attrs::RAPL_PREFIX));
+        if is_rapl {
+            if let Ok(domain) = RaplDomain::new(&path) {
+                results.push(domain);
+            }
+        }
+    }
+    results.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(results)
+}
+
+impl RaplDomain {
+    /// Create a new RAPL domain object from data from the powercap subsystem.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the domain's directory.
+    pub fn new(path: &path::Path) -> Result<RaplDomain, AcpiClientError> {
+        let sampled_at = time::SystemTime::now();
+        let name = get_device_name(path)?;
+        let domain_name = parse_entry_file(&path.join(attrs::RAPL_NAME))?;
+        let energy_uj = parse_entry_file(&path.join(attrs::ENERGY_UJ))?.parse::<u64>()?;
+        let max_energy_range_uj = parse_entry_file(&path.join(attrs::MAX_ENERGY_RANGE_UJ))
+            .ok()
+            .and_then(|range| range.parse::<u64>().ok())
+            .filter(|range| *range > 0);
+
+        Ok(RaplDomain {
+            name,
+            path: path.to_path_buf(),
+            domain_name,
+            energy_uj,
+            max_energy_range_uj,
+            sampled_at,
+        })
+    }
+
+    /// Returns the average power consumed by the domain in W between an earlier reading and this
+    /// one, accounting for the counter wrapping around once. Returns `None` if the readings were
+    /// taken at the same time or out of order, or if the counter went backwards and the domain
+    /// doesn't report the range it wraps at.
+    ///
+    /// # Arguments
+    ///
+    /// * `previous` - An earlier reading of the same domain.
+    pub fn power_since(&self, previous: &RaplDomain) -> Option<f32> {
+        let elapsed = self
+            .sampled_at
+            .duration_since(previous.sampled_at)
+            .ok()?
+            .as_secs_f64();
+        if elapsed <= 0. {
+            return None;
+        }
+        let consumed_uj = if self.energy_uj >= previous.energy_uj {
+            self.energy_uj - previous.energy_uj
+        } else {
+            self.max_energy_range_uj?
+                .checked_sub(previous.energy_uj)?
+                .checked_add(self.energy_uj)?
+        };
+        Some((consumed_uj as f64 / 1e6 / elapsed) as f32)
+    }
+
+    /// Returns the identity of the domain, for use as a key across polls.
+    pub fn id(&self) -> DeviceId {
+        DeviceId(self.name.clone())
+    }
+}
@@ -0,0 +1,75 @@
+use std::time;
+
+use crate::battery::*;
+
+/// Conversion of a device into a plain record of its readings, for serializing devices without
+/// depending on the layout of the device structs, which may change between releases.
+pub trait Snapshot {
+    /// The record of the device's readings, holding only strings and numbers.
+    type Snapshot;
+
+    /// Returns a record of the device's readings.
+    fn snapshot(&self) -> Self::Snapshot;
+}
+
+/// A plain record of a battery's readings.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BatterySnapshot {
+    /// The name used by ACPI to refer to the device.
+    pub name: String,
+    /// The state of the battery's charging, e.g. `Discharging`.
+    pub state: String,
+    /// The ratio of the remaining charge to the full charge.
+    pub percentage: f32,
+    /// The charge remaining in the battery in the units given by `capacity_unit`.
+    pub remaining_capacity: u32,
+    /// The charge available in the battery at the last time it was charged to full.
+    pub last_capacity: u32,
+    /// The charge available in the battery at the time of manufacture.
+    pub design_capacity: u32,
+    /// The units of the capacities, either `mAh` or `mWh`.
+    pub capacity_unit: &'static str,
+    /// The rate at which the charge of the battery is changing.
+    pub present_rate: u32,
+    /// The units of the present rate, either `mA` or `mW`.
+    pub present_rate_unit: &'static str,
+    /// The voltage of the battery in mV.
+    pub voltage: u32,
+    /// The seconds remaining until the battery reaches full charge or empty, if known.
+    pub time_remaining_secs: Option<u64>,
+    /// The time at which the battery was read in seconds since the Unix epoch.
+    pub sampled_at_secs: u64,
+}
+
+impl Snapshot for BatteryInfo {
+    type Snapshot = BatterySnapshot;
+
+    fn snapshot(&self) -> BatterySnapshot {
+        BatterySnapshot {
+            name: self.name.clone(),
+            state: self.state.to_string(),
+            percentage: self.percentage,
+            remaining_capacity: self.remaining_capacity.value(),
+            last_capacity: self.last_capacity.value(),
+            design_capacity: self.design_capacity.value(),
+            capacity_unit: match self.capacity_unit {
+                CapacityUnit::MilliAmpHours => "mAh",
+                CapacityUnit::MilliWattHours => "mWh",
+            },
+            present_rate: self.present_rate,
+            present_rate_unit: match self.present_rate_unit {
+                RateUnit::MilliAmps => "mA",
+                RateUnit::MilliWatts => "mW",
+            },
+            voltage: self.voltage,
+            time_remaining_secs: self
+                .time_remaining
+                .map(|time_remaining| time_remaining.as_secs()),
+            sampled_at_secs: self
+                .sampled_at
+                .duration_since(time::UNIX_EPOCH)
+                .map(|elapsed| elapsed.as_secs())
+                .unwrap_or_default(),
+        }
+    }
+}
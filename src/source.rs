@@ -0,0 +1,61 @@
+use std::fs;
+use std::io;
+use std::path;
+
+/// A source of the attribute files the ACPI subsystem exposes for each device.
+///
+/// The library reads from the filesystem by default, but readers can be given another source to
+/// read from, e.g. to simulate failures in tests.
+pub trait AttributeSource: Send + Sync {
+    /// Returns the contents of the attribute file at a path.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the attribute file.
+    fn read(&self, path: &path::Path) -> io::Result<String>;
+
+    /// Returns whether an attribute file exists at a path.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the attribute file.
+    fn exists(&self, path: &path::Path) -> bool;
+
+    /// Returns the names of the entries in the directory at a path, sorted by name. By default the
+    /// directory is listed on the filesystem, skipping entries which can't be read.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the directory.
+    fn list(&self, path: &path::Path) -> io::Result<Vec<String>> {
+        let mut names: Vec<String> = fs::read_dir(path)?
+            .filter_map(|entry| entry.ok())
+            .map(|entry| entry.file_name().to_string_lossy().into_owned())
+            .collect();
+        names.sort();
+        Ok(names)
+    }
+}
+
+/// Reads attribute files directly from the filesystem.
+#[derive(Clone, Copy, Default)]
+pub struct FileSystemSource;
+
+impl AttributeSource for FileSystemSource {
+    fn read(&self, path: &path::Path) -> io::Result<String> {
+        if path.is_file() {
+            fs::read_to_string(path)
+        } else if path.exists() {
+            Err(io::Error::other("Path is not a file."))
+        } else {
+            Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "No such attribute file.",
+            ))
+        }
+    }
+
+    fn exists(&self, path: &path::Path) -> bool {
+        path.exists()
+    }
+}
@@ -0,0 +1,28 @@
+use std::path;
+use std::time;
+
+use tokio_stream::wrappers::IntervalStream;
+use tokio_stream::{Stream, StreamExt};
+
+use crate::battery::*;
+use crate::utils::*;
+
+/// Returns a stream which reads a battery each time an interval elapses, for polling a battery
+/// from an async runtime such as in a `tokio::select!` loop. The first reading is yielded
+/// immediately, and a reading which fails to parse is yielded as an error without ending the
+/// stream. This must be called from within a Tokio runtime with its timer enabled.
+///
+/// # Arguments
+///
+/// * `path` - The path to the ACPI device.
+/// * `interval` - The time to wait between readings of the battery.
+pub fn battery_stream(
+    path: &path::Path,
+    interval: time::Duration,
+) -> impl Stream<Item = Result<BatteryInfo, AcpiClientError>> {
+    let path = path.to_path_buf();
+    let mut interval = tokio::time::interval(interval);
+    // A daemon which falls behind wants the latest reading, not a burst of stale ones
+    interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+    IntervalStream::new(interval).map(move |_| BatteryInfo::new(&path))
+}
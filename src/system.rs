@@ -0,0 +1,652 @@
+use std::fmt;
+use std::path;
+use std::time;
+
+use crate::ac_adapter::*;
+use crate::attrs;
+use crate::battery::*;
+use crate::cooling::*;
+use crate::powercap::*;
+use crate::thermal_zone::*;
+use crate::utils::*;
+
+/// The location of power supply entries relative to the root of the filesystem.
+pub const POWER_SUPPLY_PATH: &str = "sys/class/power_supply";
+/// The location of thermal zone and cooling device entries relative to the root of the
+/// filesystem.
+pub const THERMAL_PATH: &str = "sys/class/thermal";
+/// The location of powercap entries, such as RAPL domains, relative to the root of the filesystem.
+pub const POWERCAP_PATH: &str = "sys/class/powercap";
+
+/// The percentage at or below which `power_state_code` considers a battery without a reported
+/// capacity level critical.
+pub const CRITICAL_PERCENTAGE: f32 = 5.0;
+/// The percentage at or below which `power_state_code` considers a battery without a reported
+/// capacity level low.
+pub const LOW_PERCENTAGE: f32 = 15.0;
+
+/// The identity of a device, which is the name ACPI uses to refer to it. Unlike the device
+/// structs, which hold floating point readings, it can be used as a key in hashed collections.
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct DeviceId(pub String);
+
+impl DeviceId {
+    /// Returns the name of the device.
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl fmt::Display for DeviceId {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+/// The root of a sysfs tree, which may be mounted beneath a prefix, e.g. `/host` when the host's
+/// sysfs is mounted into a container at `/host/sys`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct SysfsRoot {
+    prefix: path::PathBuf,
+}
+
+impl Default for SysfsRoot {
+    fn default() -> SysfsRoot {
+        SysfsRoot::new("/")
+    }
+}
+
+impl SysfsRoot {
+    /// Create a new sysfs root beneath a prefix.
+    ///
+    /// # Arguments
+    ///
+    /// * `prefix` - The directory containing the `sys` directory, `/` for the running system.
+    pub fn new<P: AsRef<path::Path>>(prefix: P) -> SysfsRoot {
+        SysfsRoot {
+            prefix: prefix.as_ref().to_path_buf(),
+        }
+    }
+
+    /// Returns the prefix the sysfs tree is beneath.
+    pub fn prefix(&self) -> &path::Path {
+        &self.prefix
+    }
+
+    /// Returns the directory containing battery and AC adapter entries.
+    pub fn power_supply_path(&self) -> path::PathBuf {
+        self.prefix.join(POWER_SUPPLY_PATH)
+    }
+
+    /// Returns the directory containing thermal zone and cooling device entries.
+    pub fn thermal_path(&self) -> path::PathBuf {
+        self.prefix.join(THERMAL_PATH)
+    }
+
+    /// Returns the directory containing powercap entries, such as RAPL domains.
+    pub fn powercap_path(&self) -> path::PathBuf {
+        self.prefix.join(POWERCAP_PATH)
+    }
+
+    /// Returns the batteries in the system, which is empty if the system has no batteries.
+    pub fn batteries(&self) -> Result<Vec<BatteryInfo>, AcpiClientError> {
+        get_battery_info(&self.power_supply_path())
+    }
+
+    /// Returns the AC adapters in the system.
+    pub fn ac_adapters(&self) -> Result<Vec<ACAdapterInfo>, AcpiClientError> {
+        get_ac_adapter_info(&self.power_supply_path())
+    }
+
+    /// Returns the thermal sensors in the system.
+    ///
+    /// # Arguments
+    ///
+    /// * `units` - The units to report thermal data in.
+    pub fn thermal_sensors(&self, units: Units) -> Result<Vec<ThermalSensor>, AcpiClientError> {
+        get_thermal_sensor_info(&self.thermal_path(), units)
+    }
+
+    /// Returns the cooling devices in the system.
+    pub fn cooling_devices(&self) -> Result<Vec<CoolingDevice>, AcpiClientError> {
+        get_cooling_device_info(&self.thermal_path())
+    }
+
+    /// Returns the RAPL domains in the system.
+    pub fn rapl_domains(&self) -> Result<Vec<RaplDomain>, AcpiClientError> {
+        get_rapl_domains(&self.powercap_path())
+    }
+
+    /// Scans the sysfs tree for every class of device.
+    ///
+    /// A class whose directory doesn't exist in the tree is reported as having no devices.
+    ///
+    /// # Arguments
+    ///
+    /// * `units` - The units to report thermal data in.
+    pub fn scan_all(&self, units: Units) -> Result<SystemDevices, AcpiClientError> {
+        let (batteries, ac_adapters) = if self.power_supply_path().is_dir() {
+            (self.batteries()?, self.ac_adapters()?)
+        } else {
+            (vec![], vec![])
+        };
+        let (thermal_sensors, cooling_devices) = if self.thermal_path().is_dir() {
+            (self.thermal_sensors(units)?, self.cooling_devices()?)
+        } else {
+            (vec![], vec![])
+        };
+
+        Ok(SystemDevices {
+            batteries,
+            ac_adapters,
+            thermal_sensors,
+            cooling_devices,
+        })
+    }
+}
+
+/// All of the devices the ACPI subsystem reports, grouped by class.
+pub struct SystemDevices {
+    /// The batteries in the system.
+    pub batteries: Vec<BatteryInfo>,
+    /// The AC adapters in the system.
+    pub ac_adapters: Vec<ACAdapterInfo>,
+    /// The thermal sensors in the system.
+    pub thermal_sensors: Vec<ThermalSensor>,
+    /// The cooling devices in the system.
+    pub cooling_devices: Vec<CoolingDevice>,
+}
+
+/// The batteries and AC adapters of a system, which can be refreshed in place between polls.
+pub struct SystemPowerStatus {
+    root: SysfsRoot,
+    /// The batteries in the system.
+    pub batteries: Vec<BatteryInfo>,
+    /// The AC adapters in the system.
+    pub ac_adapters: Vec<ACAdapterInfo>,
+}
+
+impl SystemPowerStatus {
+    /// Reads the batteries and AC adapters beneath a sysfs root. A system without a power supply
+    /// directory is reported as having no devices.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The sysfs tree to read the devices from.
+    pub fn snapshot(root: &SysfsRoot) -> Result<SystemPowerStatus, AcpiClientError> {
+        let mut status = SystemPowerStatus {
+            root: root.clone(),
+            batteries: vec![],
+            ac_adapters: vec![],
+        };
+        status.refresh()?;
+        Ok(status)
+    }
+
+    /// Re-reads the devices, updating the existing lists in place. Devices which are still present
+    /// keep their positions, devices which have been removed are dropped, and devices which have
+    /// appeared are added to the end in order of name.
+    pub fn refresh(&mut self) -> Result<(), AcpiClientError> {
+        let (batteries, ac_adapters) = if self.root.power_supply_path().is_dir() {
+            (self.root.batteries()?, self.root.ac_adapters()?)
+        } else {
+            (vec![], vec![])
+        };
+        reconcile_devices(&mut self.batteries, batteries, |battery| &battery.name);
+        reconcile_devices(&mut self.ac_adapters, ac_adapters, |adapter| &adapter.name);
+        Ok(())
+    }
+}
+
+/// Replaces the devices in a list with freshly read ones of the same name, keeping their order,
+/// removing those which weren't read again, and appending new ones sorted by name.
+///
+/// # Arguments
+///
+/// * `devices` - The list of devices to update.
+/// * `fresh` - The devices which were just read.
+/// * `name` - Returns the name of a device.
+fn reconcile_devices<T>(devices: &mut Vec<T>, mut fresh: Vec<T>, name: fn(&T) -> &String) {
+    devices.retain(|device| fresh.iter().any(|other| name(other) == name(device)));
+    for device in devices.iter_mut() {
+        if let Some(index) = fresh.iter().position(|other| name(other) == name(device)) {
+            *device = fresh.swap_remove(index);
+        }
+    }
+    fresh.sort_by(|a, b| name(a).cmp(name(b)));
+    devices.extend(fresh);
+}
+
+/// The classes of device the ACPI subsystem reports.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DeviceClass {
+    Battery,
+    AcAdapter,
+    ThermalSensor,
+    CoolingDevice,
+    /// A device which doesn't belong to any of the known classes.
+    Unknown,
+}
+
+/// Determines the class of the device in a sysfs directory from the attribute files it contains.
+///
+/// # Arguments
+///
+/// * `path` - The path to the device's directory.
+pub fn classify_device(path: &path::Path) -> Result<DeviceClass, AcpiClientError> {
+    if !path.is_dir() {
+        return Err(AcpiClientError::Io(std::io::Error::new(
+            std::io::ErrorKind::NotFound,
+            "Device directory not found.",
+        )));
+    }
+
+    // Thermal zones and cooling devices also have a type attribute, so check for them first
+    let class = if is_thermal_sensor(path) {
+        DeviceClass::ThermalSensor
+    } else if path.join(attrs::CUR_STATE).exists() && path.join(attrs::MAX_STATE).exists() {
+        DeviceClass::CoolingDevice
+    } else if path.join(attrs::TYPE).exists()
+        && is_battery_type(&parse_entry_file(&path.join(attrs::TYPE))?)
+    {
+        DeviceClass::Battery
+    } else if path.join(attrs::ONLINE).exists() {
+        DeviceClass::AcAdapter
+    } else {
+        DeviceClass::Unknown
+    };
+
+    Ok(class)
+}
+
+/// Checks the attributes of the device in a sysfs directory, returning a human-readable warning
+/// for each expected attribute which is missing or malformed and each reading which looks wrong,
+/// e.g. `charge_now exceeds charge_full`. A device without any warnings returns an empty list.
+/// This is intended for diagnosing devices which the library fails to read.
+///
+/// # Arguments
+///
+/// * `path` - The path to the device's directory.
+pub fn validate_device(path: &path::Path) -> Vec<String> {
+    let class = match classify_device(path) {
+        Ok(class) => class,
+        Err(error) => return vec![format!("unable to classify device: {}", error)],
+    };
+    let mut warnings: Vec<String> = vec![];
+    // Checks an integer attribute, recording a warning if it's missing or malformed
+    let mut read = |name: &str| -> Option<i32> {
+        if !path.join(name).exists() {
+            warnings.push(format!("missing {}", name));
+            return None;
+        }
+        let value = parse_file_to_i32(&path.join(name), 1);
+        if value.is_err() {
+            warnings.push(format!("{} is not a number", name));
+        }
+        value.ok()
+    };
+
+    let mut suspicious: Vec<String> = vec![];
+    match class {
+        DeviceClass::Battery => {
+            let reports_energy =
+                !path.join(attrs::CHARGE_NOW).exists() && path.join(attrs::ENERGY_NOW).exists();
+            let (now, full, design) = if reports_energy {
+                (
+                    attrs::ENERGY_NOW,
+                    attrs::ENERGY_FULL,
+                    attrs::ENERGY_FULL_DESIGN,
+                )
+            } else {
+                (
+                    attrs::CHARGE_NOW,
+                    attrs::CHARGE_FULL,
+                    attrs::CHARGE_FULL_DESIGN,
+                )
+            };
+            let (now_value, full_value) = (read(now), read(full));
+            read(design);
+            if !path.join(attrs::VOLTAGE_AVG).exists() {
+                read(attrs::VOLTAGE_NOW);
+            }
+            match (now_value, full_value) {
+                (_, Some(0)) => suspicious.push(format!("{} is zero", full)),
+                (Some(now_value), Some(full_value)) if now_value > full_value => {
+                    suspicious.push(format!("{} exceeds {}", now, full))
+                }
+                _ => {}
+            }
+            let rates = [
+                attrs::CURRENT_NOW,
+                attrs::CURRENT_AVG,
+                attrs::POWER_NOW,
+                attrs::POWER_AVG,
+            ];
+            if !rates.iter().any(|rate| path.join(rate).exists()) {
+                suspicious.push(String::from(
+                    "missing current_now and power_now, so the time remaining is unknown",
+                ));
+            }
+            if !path.join(attrs::STATUS).exists() {
+                suspicious.push(format!("missing {}", attrs::STATUS));
+            }
+        }
+        DeviceClass::AcAdapter => {
+            if let Some(online) = read(attrs::ONLINE) {
+                if online != 0 && online != 1 {
+                    suspicious.push(format!(
+                        "{} is {} rather than 0 or 1",
+                        attrs::ONLINE,
+                        online
+                    ));
+                }
+            }
+        }
+        DeviceClass::ThermalSensor => {
+            if let Some(temperature) = read(attrs::TEMP) {
+                // Below absolute zero or hotter than any chip survives
+                if !(-273_150..=200_000).contains(&temperature) {
+                    suspicious.push(format!(
+                        "{} of {} is out of range",
+                        attrs::TEMP,
+                        temperature
+                    ));
+                }
+            }
+        }
+        DeviceClass::CoolingDevice => {
+            let (current_state, max_state) = (read(attrs::CUR_STATE), read(attrs::MAX_STATE));
+            if let (Some(current_state), Some(max_state)) = (current_state, max_state) {
+                if current_state > max_state {
+                    suspicious.push(format!("{} exceeds {}", attrs::CUR_STATE, attrs::MAX_STATE));
+                }
+            }
+        }
+        DeviceClass::Unknown => suspicious.push(String::from(
+            "unrecognized device, it has no attributes identifying its class",
+        )),
+    }
+
+    warnings.extend(suspicious);
+    warnings
+}
+
+/// Scans the standard sysfs locations beneath a root directory for every class of device.
+///
+/// A class whose directory doesn't exist under the root is reported as having no devices.
+///
+/// # Arguments
+///
+/// * `root` - The directory to treat as the root of the filesystem, usually `/`.
+/// * `units` - The units to report thermal data in.
+///
+/// # Example
+/// ```
+/// let devices = acpi_client::scan_all(std::path::Path::new("/"), acpi_client::Units::Celsius);
+/// ```
+pub fn scan_all(root: &path::Path, units: Units) -> Result<SystemDevices, AcpiClientError> {
+    SysfsRoot::new(root).scan_all(units)
+}
+
+/// Returns the time until the system's batteries are depleted while it runs on battery power, or
+/// `None` if any AC adapter is online or no battery is discharging.
+///
+/// Batteries can discharge at different rates, and some systems drain them one at a time, so the
+/// estimate divides the energy remaining in every battery by the combined power drawn from the
+/// discharging ones.
+///
+/// # Arguments
+///
+/// * `batteries` - The batteries in the system.
+/// * `adapters` - The AC adapters in the system.
+pub fn system_time_remaining(
+    batteries: &[BatteryInfo],
+    adapters: &[ACAdapterInfo],
+) -> Option<time::Duration> {
+    if adapters.iter().any(|adapter| adapter.is_online()) {
+        return None;
+    }
+
+    let remaining_energy_wh: f32 = batteries
+        .iter()
+        .map(|battery| battery.remaining_energy_wh())
+        .sum();
+    let power_w: f32 = batteries
+        .iter()
+        .filter(|battery| battery.state == ChargingState::Discharging)
+        .map(battery_power_w)
+        .sum();
+    if power_w <= 0. {
+        return None;
+    }
+
+    Some(time::Duration::from_secs_f32(
+        remaining_energy_wh / power_w * 3600.,
+    ))
+}
+
+/// The direction energy is moving in across all of a system's batteries, see
+/// `battery_power_flow`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum PowerFlow {
+    /// At least one battery is charging and none are discharging.
+    NetCharging,
+    /// At least one battery is discharging and none are charging.
+    NetDischarging,
+    /// Some batteries are charging while others discharge, as on dual-battery systems which
+    /// charge one pack from the other.
+    InternalTransfer,
+    /// No battery is charging or discharging.
+    Idle,
+}
+
+/// Returns the direction energy is moving in across the system's batteries. A battery only counts
+/// as charging or discharging if it reports that state with a non-zero power, so summing the
+/// batteries' rates isn't mistaken for net charging when one pack is charging from another.
+///
+/// # Arguments
+///
+/// * `batteries` - The batteries in the system.
+pub fn battery_power_flow(batteries: &[BatteryInfo]) -> PowerFlow {
+    let flowing = |state: ChargingState| {
+        batteries
+            .iter()
+            .any(|battery| battery.state == state && battery_power_w(battery) > 0.)
+    };
+    match (
+        flowing(ChargingState::Charging),
+        flowing(ChargingState::Discharging),
+    ) {
+        (true, true) => PowerFlow::InternalTransfer,
+        (true, false) => PowerFlow::NetCharging,
+        (false, true) => PowerFlow::NetDischarging,
+        (false, false) => PowerFlow::Idle,
+    }
+}
+
+/// Returns a single number summarizing how the system is powered, for use as a script's exit code
+/// or a prompt token:
+///
+/// * `5` - On AC power, i.e. an adapter is online and no battery is charging, or the system has
+///   no batteries.
+/// * `4` - A battery is charging.
+/// * `3` - On battery power, with every battery full.
+/// * `2` - On battery power, with the emptiest battery at a normal level.
+/// * `1` - On battery power, with the emptiest battery low.
+/// * `0` - On battery power, with the emptiest battery critical.
+///
+/// A battery's level is taken from its `capacity_level` if it reports one, and otherwise from its
+/// percentage compared against `CRITICAL_PERCENTAGE` and `LOW_PERCENTAGE`.
+///
+/// # Arguments
+///
+/// * `batteries` - The batteries in the system.
+/// * `adapters` - The AC adapters in the system.
+pub fn power_state_code(batteries: &[BatteryInfo], adapters: &[ACAdapterInfo]) -> u8 {
+    if batteries
+        .iter()
+        .any(|battery| battery.state == ChargingState::Charging)
+    {
+        return 4;
+    }
+    if batteries.is_empty() || adapters.iter().any(|adapter| adapter.is_online()) {
+        return 5;
+    }
+
+    batteries
+        .iter()
+        .map(|battery| match battery.capacity_level {
+            Some(CapacityLevel::Critical) => 0,
+            Some(CapacityLevel::Low) => 1,
+            Some(CapacityLevel::Normal) | Some(CapacityLevel::High) => 2,
+            Some(CapacityLevel::Full) => 3,
+            None if battery.state == ChargingState::Full || battery.percentage >= 99.5 => 3,
+            None if battery.percentage <= CRITICAL_PERCENTAGE => 0,
+            None if battery.percentage <= LOW_PERCENTAGE => 1,
+            None => 2,
+        })
+        .min()
+        .unwrap_or(5)
+}
+
+/// Returns a human-readable line describing how the system is powered, e.g.
+/// `On AC (charging), 83%, 42 min to full` or `On battery, 61%, 2h 10m remaining`. The system is
+/// considered to be on AC if any adapter is online or any battery is charging, and a system
+/// without batteries, such as a desktop, is described as `On AC power`. The percentage is that of
+/// the batteries' combined energy.
+///
+/// # Arguments
+///
+/// * `batteries` - The batteries in the system.
+/// * `adapters` - The AC adapters in the system.
+pub fn power_summary(batteries: &[BatteryInfo], adapters: &[ACAdapterInfo]) -> String {
+    if batteries.is_empty() {
+        return String::from("On AC power");
+    }
+
+    let last_energy_wh: f32 = batteries
+        .iter()
+        .map(|battery| battery.last_energy_wh())
+        .sum();
+    let percentage = if last_energy_wh > 0. {
+        let remaining_energy_wh: f32 = batteries
+            .iter()
+            .map(|battery| battery.remaining_energy_wh())
+            .sum();
+        (remaining_energy_wh * 100. / last_energy_wh).min(100.)
+    } else {
+        batteries
+            .iter()
+            .map(|battery| battery.percentage)
+            .sum::<f32>()
+            / batteries.len() as f32
+    };
+    let format_duration = |duration: time::Duration| {
+        let minutes = duration.as_secs() / 60;
+        match minutes / 60 {
+            0 => format!("{} min", minutes),
+            hours => format!("{}h {}m", hours, minutes % 60),
+        }
+    };
+
+    let charging: Vec<&BatteryInfo> = batteries
+        .iter()
+        .filter(|battery| battery.state == ChargingState::Charging)
+        .collect();
+    let on_ac = adapters.iter().any(|adapter| adapter.is_online()) || !charging.is_empty();
+    if !on_ac {
+        return match system_time_remaining(batteries, adapters) {
+            Some(remaining) => format!(
+                "On battery, {:.0}%, {} remaining",
+                percentage,
+                format_duration(remaining)
+            ),
+            None => format!("On battery, {:.0}%", percentage),
+        };
+    }
+
+    // Batteries charge in parallel or one after another, so the slowest sets the time to full
+    let time_to_full = charging
+        .iter()
+        .filter_map(|battery| battery.time_remaining)
+        .max();
+    match time_to_full {
+        Some(time_to_full) => format!(
+            "On AC (charging), {:.0}%, {} to full",
+            percentage,
+            format_duration(time_to_full)
+        ),
+        None if !charging.is_empty() => format!("On AC (charging), {:.0}%", percentage),
+        None if batteries
+            .iter()
+            .all(|battery| battery.state == ChargingState::Full) =>
+        {
+            format!("On AC (fully charged), {:.0}%", percentage)
+        }
+        None => format!("On AC (not charging), {:.0}%", percentage),
+    }
+}
+
+/// Returns a single line summarizing the batteries, AC adapters, and hottest thermal zone found
+/// beneath a root directory, e.g. `BAT0 83% (charging, 0:42) AC online 51°C`. Sections for device
+/// classes which aren't present are left out.
+///
+/// # Arguments
+///
+/// * `root` - The directory to treat as the root of the filesystem, usually `/`.
+/// * `units` - The units to report thermal data in.
+pub fn one_line_summary(root: &path::Path, units: Units) -> Result<String, AcpiClientError> {
+    let devices = scan_all(root, units)?;
+    let mut sections: Vec<String> = vec![];
+
+    for battery in &devices.batteries {
+        let state = battery.state.to_string().to_lowercase();
+        let section = match (battery.state, battery.time_remaining) {
+            (ChargingState::Charging, Some(time_remaining))
+            | (ChargingState::Discharging, Some(time_remaining)) => {
+                let minutes = time_remaining.as_secs() / 60;
+                format!(
+                    "{} {:.0}% ({}, {}:{:02})",
+                    battery.name,
+                    battery.percentage,
+                    state,
+                    minutes / 60,
+                    minutes % 60
+                )
+            }
+            _ => format!("{} {:.0}% ({})", battery.name, battery.percentage, state),
+        };
+        sections.push(section);
+    }
+
+    if !devices.ac_adapters.is_empty() {
+        let online = devices
+            .ac_adapters
+            .iter()
+            .any(|adapter| adapter.is_online());
+        sections.push(format!("AC {}", if online { "online" } else { "offline" }));
+    }
+
+    if let Some(hottest) = hottest_sensor(&devices.thermal_sensors) {
+        sections.push(format!(
+            "{:.0}{}",
+            hottest.current_temperature,
+            units.symbol()
+        ));
+    }
+
+    Ok(sections.join(" "))
+}
+
+/// Returns the power flowing into or out of a battery in W, converting a current using the
+/// battery's present voltage.
+///
+/// # Arguments
+///
+/// * `battery` - The battery to read the power of.
+fn battery_power_w(battery: &BatteryInfo) -> f32 {
+    match battery.present_rate_unit {
+        RateUnit::MilliWatts => battery.present_rate as f32 / 1000.,
+        RateUnit::MilliAmps => battery.present_rate as f32 * battery.voltage as f32 / 1e6,
+    }
+}
@@ -1,16 +1,130 @@
+use std::collections::{BTreeMap, BTreeSet, VecDeque};
+use std::convert::TryFrom;
+use std::env;
 use std::fs;
 use std::path;
+use std::str::FromStr;
+use std::time;
 
+use crate::attrs;
+use crate::source::*;
+use crate::system::DeviceId;
 use crate::utils::*;
 
 /// An enumeration of the units with which the applications is displaying temperature data.
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Debug, PartialEq)]
 pub enum Units {
     Fahrenheit,
     Celsius,
     Kelvin,
 }
 
+impl Units {
+    /// Returns the symbol used to label a temperature in these units, e.g. `°C`.
+    pub fn symbol(&self) -> &'static str {
+        match *self {
+            Units::Fahrenheit => "°F",
+            Units::Celsius => "°C",
+            Units::Kelvin => "K",
+        }
+    }
+
+    /// Returns the name of these units, e.g. `Celsius`.
+    pub fn name(&self) -> &'static str {
+        match *self {
+            Units::Fahrenheit => "Fahrenheit",
+            Units::Celsius => "Celsius",
+            Units::Kelvin => "Kelvin",
+        }
+    }
+}
+
+impl FromStr for Units {
+    type Err = AcpiClientError;
+
+    fn from_str(units: &str) -> Result<Units, AcpiClientError> {
+        match units.trim().to_lowercase().as_str() {
+            "fahrenheit" | "f" => Ok(Units::Fahrenheit),
+            "celsius" | "c" => Ok(Units::Celsius),
+            "kelvin" | "k" => Ok(Units::Kelvin),
+            _ => Err(AcpiClientError::InvalidInput(std::io::Error::other(
+                format!("Unrecognized temperature units: {}", units),
+            ))),
+        }
+    }
+}
+
+/// The environment variable which sets the default units for displaying temperature data, e.g.
+/// `ACPI_TEMP_UNITS=fahrenheit`.
+pub const UNITS_ENV_VAR: &str = "ACPI_TEMP_UNITS";
+
+/// Resolves the units to display temperature data in. Units given explicitly, e.g. by a
+/// command-line flag, take precedence over the `ACPI_TEMP_UNITS` environment variable, which takes
+/// precedence over the default of Celsius. An unrecognized value in the environment variable is
+/// ignored.
+///
+/// # Arguments
+///
+/// * `explicit` - The units given explicitly, if any.
+pub fn resolve_units(explicit: Option<Units>) -> Units {
+    explicit
+        .or_else(|| env::var(UNITS_ENV_VAR).ok()?.parse().ok())
+        .unwrap_or(Units::Celsius)
+}
+
+/// Whether the kernel is monitoring a thermal zone and acting on its trip points.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ThermalMode {
+    Enabled,
+    Disabled,
+}
+
+/// The component of the system a thermal zone monitors, as guessed from the zone's type.
+#[derive(Clone, Debug, PartialEq)]
+pub enum ThermalCategory {
+    /// The processor, e.g. `x86_pkg_temp` or `cpu-thermal`.
+    Cpu,
+    /// The graphics processor, e.g. `gpu-thermal`.
+    Gpu,
+    /// The system as a whole as reported by the firmware, e.g. `acpitz` or `pch_cannonlake`.
+    System,
+    /// The air or surface temperature of the device, e.g. `ambient` or `skin`.
+    Ambient,
+    /// A battery, e.g. `battery`.
+    Battery,
+    /// A wireless adapter, e.g. `iwlwifi_1`.
+    Wireless,
+    /// A storage device, e.g. `nvme`.
+    Storage,
+    /// Any other kind of zone, holding the type reported by the system, which is empty if the
+    /// zone doesn't report one.
+    Other(String),
+}
+
+impl From<&str> for ThermalCategory {
+    fn from(zone_type: &str) -> ThermalCategory {
+        let normalized = zone_type.trim().to_lowercase();
+        let has = |pattern: &str| normalized.contains(pattern);
+        if has("pkg_temp") || has("cpu") || has("coretemp") || has("k10temp") || has("x86") {
+            ThermalCategory::Cpu
+        } else if has("gpu") || has("amdgpu") || has("nouveau") {
+            ThermalCategory::Gpu
+        } else if has("acpitz") || has("pch") {
+            ThermalCategory::System
+        } else if has("ambient") || has("skin") {
+            ThermalCategory::Ambient
+        } else if has("battery") {
+            ThermalCategory::Battery
+        } else if has("wifi") || has("wlan") {
+            ThermalCategory::Wireless
+        } else if has("nvme") {
+            ThermalCategory::Storage
+        } else {
+            ThermalCategory::Other(String::from(zone_type.trim()))
+        }
+    }
+}
+
 /// Information about the temperature at which the system takes action to reduce the temperature of a thermal zone.
 pub struct TripPoint {
     /// A numerical identifier for the trip point.
@@ -27,12 +141,154 @@ pub struct TripPoint {
 pub struct ThermalSensor {
     /// The name used by ACPI to refer to the sensor.
     pub name: String,
+    /// The path to the sensor's directory.
+    pub path: path::PathBuf,
+    /// The kind of zone the sensor monitors, e.g. `x86_pkg_temp` or `acpitz`, if reported.
+    pub zone_type: Option<String>,
     /// The current temperature measured by the sensor.
     pub current_temperature: f32,
+    /// The temperature the zone has been set to emulate for testing, if any.
+    pub emulated_temperature: Option<f32>,
     /// The units of the temperature data.
     pub units: Units,
+    /// Whether the kernel is monitoring the zone, if it reports its mode. A disabled zone still
+    /// reports its temperature, but its trip points aren't acted on.
+    pub mode: Option<ThermalMode>,
     /// A list of the trip points configured for the zone.
     pub trip_points: Vec<TripPoint>,
+    /// The names of the cooling devices bound to the zone.
+    pub bound_cooling_devices: Vec<String>,
+    /// The names of the cooling devices bound to the zone paired with the number of the trip
+    /// point each responds to, or -1 for a device which isn't bound to a particular trip point.
+    pub cooling_device_trip_points: Vec<(String, i32)>,
+    /// The temperatures measured by the hardware monitors linked beneath the zone, e.g. one for
+    /// each core, paired with each input's label or otherwise its location, e.g. `hwmon1/temp2`.
+    /// This is empty for zones without a hardware monitor.
+    pub sub_temperatures: Vec<(String, f32)>,
+    /// The time at which the sensor was read.
+    pub sampled_at: time::SystemTime,
+}
+
+/// Tracks the temperature of each thermal zone over successive readings to estimate how quickly
+/// it is changing, e.g. to predict throttling before a trip point is reached. The rate is taken
+/// between the oldest and newest readings in a window, which smooths over the sensor's jitter.
+#[derive(Clone, Debug, PartialEq)]
+pub struct TemperatureTracker {
+    window: usize,
+    readings: BTreeMap<String, VecDeque<(time::SystemTime, f32)>>,
+}
+
+impl TemperatureTracker {
+    /// Create a new tracker.
+    ///
+    /// # Arguments
+    ///
+    /// * `window` - The number of most recent readings of each zone to consider, at least two.
+    pub fn new(window: usize) -> TemperatureTracker {
+        TemperatureTracker {
+            window: window.max(2),
+            readings: BTreeMap::new(),
+        }
+    }
+
+    /// Records a new reading of a zone and returns the zone's rate of change, see `rate`.
+    ///
+    /// # Arguments
+    ///
+    /// * `sensor` - The latest reading of the zone.
+    pub fn update(&mut self, sensor: &ThermalSensor) -> Option<f32> {
+        let readings = self.readings.entry(sensor.name.clone()).or_default();
+        if readings.len() == self.window {
+            readings.pop_front();
+        }
+        readings.push_back((sensor.sampled_at, sensor.current_temperature));
+        self.rate(&sensor.name)
+    }
+
+    /// Returns the rate of change of a zone's temperature in degrees per minute, in the units the
+    /// zone was read in, or `None` until two readings taken at different times have been recorded.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name used by ACPI to refer to the zone, e.g. `thermal_zone0`.
+    pub fn rate(&self, name: &str) -> Option<f32> {
+        let readings = self.readings.get(name)?;
+        let (oldest_at, oldest) = readings.front()?;
+        let (newest_at, newest) = readings.back()?;
+        let minutes = newest_at.duration_since(*oldest_at).ok()?.as_secs_f32() / 60.;
+        if minutes <= 0. {
+            return None;
+        }
+        Some((newest - oldest) / minutes)
+    }
+}
+
+/// Tracks which thermal zones have reached a critical trip point over successive readings, so that
+/// a zone is reported once as it enters the critical range rather than on every reading it stays
+/// there. A zone which cools below its critical trip points is reported again if it returns.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct CriticalWatcher {
+    critical: BTreeSet<String>,
+}
+
+impl CriticalWatcher {
+    /// Create a new watcher which hasn't seen any zone reach a critical trip point.
+    pub fn new() -> CriticalWatcher {
+        CriticalWatcher::default()
+    }
+
+    /// Records new readings of the zones and returns those which have reached a critical trip
+    /// point since the previous readings.
+    ///
+    /// # Arguments
+    ///
+    /// * `sensors` - The latest readings of the zones.
+    pub fn update<'a>(&mut self, sensors: &'a [ThermalSensor]) -> Vec<&'a ThermalSensor> {
+        let mut entered = vec![];
+        for sensor in sensors {
+            if sensor.is_critical() {
+                if self.critical.insert(sensor.name.clone()) {
+                    entered.push(sensor);
+                }
+            } else {
+                self.critical.remove(&sensor.name);
+            }
+        }
+        entered
+    }
+}
+
+/// Polls every thermal zone at a fixed interval and calls a function whenever a zone reaches a
+/// critical trip point. The function is called once as a zone enters the critical range, see
+/// `CriticalWatcher`. This blocks until `stop` returns true, or until the zones can't be read,
+/// returning the error.
+///
+/// # Arguments
+///
+/// * `path` - The path to thermal zone entries produced by the ACPI subsystem.
+/// * `units` - The units to read the zones' temperatures in.
+/// * `poll` - The time to wait between readings of the zones.
+/// * `callback` - The function to call with each zone which reaches a critical trip point.
+/// * `stop` - The function called after each reading of the zones to decide whether to stop
+///   watching.
+pub fn watch_thermal_critical(
+    path: &path::Path,
+    units: Units,
+    poll: time::Duration,
+    mut callback: impl FnMut(&ThermalSensor),
+    mut stop: impl FnMut() -> bool,
+) -> Result<(), AcpiClientError> {
+    let mut watcher = CriticalWatcher::new();
+    loop {
+        let sensors = get_thermal_sensor_info(path, units)?;
+        for sensor in watcher.update(&sensors) {
+            callback(sensor);
+        }
+        if stop() {
+            return Ok(());
+        }
+        std::thread::sleep(poll);
+    }
 }
 
 /// Check the ACPI system for all thermal sensors the OS knows about.
@@ -43,13 +299,29 @@ pub struct ThermalSensor {
 pub fn get_thermal_sensor_info(
     path: &path::Path,
     units: Units,
+) -> Result<Vec<ThermalSensor>, AcpiClientError> {
+    get_thermal_sensor_info_from_source(&FileSystemSource, path, units)
+}
+
+/// Check a source of attribute files for all thermal sensors in a directory. The sensors are
+/// returned sorted by name.
+///
+/// # Arguments
+///
+/// * `source` - The source to read the zones' attributes from.
+/// * `path` - The path to thermal zone entries produced by the ACPI subsystem.
+/// * `units` - The units to report thermal data in.
+pub fn get_thermal_sensor_info_from_source(
+    source: &dyn AttributeSource,
+    path: &path::Path,
+    units: Units,
 ) -> Result<Vec<ThermalSensor>, AcpiClientError> {
     let mut results: Vec<ThermalSensor> = vec![];
 
-    for entry in fs::read_dir(&path)? {
-        let path = entry?.path();
-        if is_thermal_sensor(&path) {
-            let tz = ThermalSensor::new(&path, units);
+    for name in source.list(path)? {
+        let path = path.join(name);
+        if source.exists(&path.join(attrs::TEMP)) {
+            let tz = ThermalSensor::from_source(source, &path, units);
             if tz.is_ok() {
                 results.push(tz?);
             }
@@ -59,6 +331,58 @@ pub fn get_thermal_sensor_info(
     Ok(results)
 }
 
+/// Check the ACPI system for all thermal sensors the OS knows about, reading each sensor on its own
+/// thread. This is faster than `get_thermal_sensor_info` on systems with many thermal zones. The
+/// sensors are returned sorted by name.
+///
+/// # Arguments
+///
+/// * `path` - The path to thermal zone entries produced by the ACPI subsystem.
+/// * `units` - The units to report thermal data in.
+pub fn get_thermal_sensor_info_parallel(
+    path: &path::Path,
+    units: Units,
+) -> Result<Vec<ThermalSensor>, AcpiClientError> {
+    let mut paths: Vec<path::PathBuf> = vec![];
+    for path in read_device_dirs(path)? {
+        if is_thermal_sensor(&path) {
+            paths.push(path);
+        }
+    }
+
+    let mut results: Vec<ThermalSensor> = std::thread::scope(|scope| {
+        let handles: Vec<_> = paths
+            .iter()
+            .map(|path| scope.spawn(move || ThermalSensor::new(path, units)))
+            .collect();
+        handles
+            .into_iter()
+            .filter_map(|handle| handle.join().ok().and_then(|tz| tz.ok()))
+            .collect()
+    });
+    results.sort_by(|a, b| a.name.cmp(&b.name));
+
+    Ok(results)
+}
+
+impl TryFrom<&path::Path> for ThermalSensor {
+    type Error = AcpiClientError;
+
+    /// Reads the sensor with its temperatures in degrees Celsius. Use `ThermalSensor::new` to
+    /// read it in other units.
+    fn try_from(path: &path::Path) -> Result<ThermalSensor, AcpiClientError> {
+        ThermalSensor::new(path, Units::Celsius)
+    }
+}
+
+impl TryFrom<path::PathBuf> for ThermalSensor {
+    type Error = AcpiClientError;
+
+    fn try_from(path: path::PathBuf) -> Result<ThermalSensor, AcpiClientError> {
+        ThermalSensor::try_from(path.as_path())
+    }
+}
+
 impl ThermalSensor {
     /// Create a new thermal sensor object from data from the ACPI subsystem.
     ///
@@ -66,42 +390,450 @@ impl ThermalSensor {
     ///
     /// * `path` - The path to the ACPI device.
     pub fn new(path: &path::Path, units: Units) -> Result<ThermalSensor, AcpiClientError> {
+        ThermalSensor::from_source(&FileSystemSource, path, units)
+    }
+
+    /// Create a new thermal sensor object from attribute files read from a source.
+    ///
+    /// # Arguments
+    ///
+    /// * `source` - The source to read the zone's attributes from.
+    /// * `path` - The path to the ACPI device.
+    /// * `units` - The units to report temperature data in.
+    pub fn from_source(
+        source: &dyn AttributeSource,
+        path: &path::Path,
+        units: Units,
+    ) -> Result<ThermalSensor, AcpiClientError> {
+        let sampled_at = time::SystemTime::now();
         let name = get_device_name(path)?;
+        let zone_type = read_attribute(source, &path.join(attrs::TYPE)).ok();
         let mut trip_points: Vec<TripPoint> = vec![];
         let current_temperature = convert_from_celsius(
-            (parse_file_to_i32(&path.join("temp"), 1)? as f32) / 1000.,
+            (read_attribute_to_i32(source, &path.join(attrs::TEMP), 1)? as f32) / 1000.,
             units,
         );
+        let emulated_temperature = read_attribute_to_i32(source, &path.join(attrs::EMUL_TEMP), 1)
+            .ok()
+            .filter(|temperature| *temperature != 0)
+            .map(|temperature| convert_from_celsius(temperature as f32 / 1000., units));
+        let mode = read_attribute(source, &path.join(attrs::MODE))
+            .ok()
+            .and_then(|mode| match mode.trim().to_lowercase().as_str() {
+                "enabled" => Some(ThermalMode::Enabled),
+                "disabled" => Some(ThermalMode::Disabled),
+                _ => None,
+            });
 
-        let mut trip_point_counter: u8 = 0;
-        loop {
-            if path
-                .join(format!("trip_point_{}_temp", trip_point_counter))
-                .exists()
-            {
-                let tp = TripPoint::new(&path, trip_point_counter, units);
-                if tp.is_ok() {
-                    trip_points.push(tp?);
-                    trip_point_counter = trip_point_counter + 1;
-                } else {
-                    break;
-                }
-            } else {
+        // Some trip points are write-only or disabled, so skip them rather than stopping the scan
+        for number in 0..=u8::MAX {
+            if !source.exists(&path.join(attrs::trip_point_temp(number))) {
                 break;
             }
+            if let Ok(trip_point) = read_trip_point(source, path, number, units) {
+                trip_points.push(trip_point);
+            }
         }
 
+        let links = read_bound_cooling_devices(source, path)?;
+        let cooling_device_trip_points = links
+            .iter()
+            .filter_map(|(index, name)| {
+                let trip_point =
+                    read_attribute_to_i32(source, &path.join(attrs::cdev_trip_point(*index)), 1);
+                trip_point.ok().map(|trip_point| (name.clone(), trip_point))
+            })
+            .collect();
+        let bound_cooling_devices = links.into_iter().map(|(_, name)| name).collect();
+        let sub_temperatures = read_sub_temperatures(source, path, units);
+
         Ok(ThermalSensor {
             name,
+            path: path.to_path_buf(),
+            zone_type,
             current_temperature,
+            emulated_temperature,
             units,
+            mode,
             trip_points,
+            bound_cooling_devices,
+            cooling_device_trip_points,
+            sub_temperatures,
+            sampled_at,
+        })
+    }
+
+    /// Create a new thermal sensor object from the directory of a device named `name` within a class
+    /// directory, returning a `NotFound` error if there is no such device.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The path to the thermal entries produced by the ACPI subsystem.
+    /// * `name` - The name used by ACPI to refer to the device, e.g. `thermal_zone0`.
+    /// * `units` - The units to report temperature data in.
+    pub fn from_root_and_name(
+        root: &path::Path,
+        name: &str,
+        units: Units,
+    ) -> Result<ThermalSensor, AcpiClientError> {
+        ThermalSensor::new(&device_path(root, name)?, units)
+    }
+
+    /// Returns the trip point with a given number, or `None` if the zone has no such trip point.
+    /// Trip points are matched by their number rather than their position, so gaps in the
+    /// numbering are handled.
+    ///
+    /// # Arguments
+    ///
+    /// * `number` - The numerical id of the trip point.
+    pub fn trip_point(&self, number: u8) -> Option<&TripPoint> {
+        self.trip_points
+            .iter()
+            .find(|trip_point| trip_point.number == number)
+    }
+
+    /// Returns the component the zone monitors, guessed from the zone's type, for grouping zones
+    /// in a display.
+    pub fn category(&self) -> ThermalCategory {
+        ThermalCategory::from(self.zone_type.as_deref().unwrap_or_default())
+    }
+
+    /// Returns the trip point the zone most recently crossed, i.e. the one with the highest
+    /// temperature at or below the current temperature, or `None` if the zone is below all of its
+    /// trip points. The temperatures are compared in degrees Celsius, so the result is correct even
+    /// if the trip points are in different units from the sensor.
+    pub fn last_crossed_trip_point(&self) -> Option<&TripPoint> {
+        let current = self.temperature_in(Units::Celsius);
+        self.trip_points
+            .iter()
+            .filter(|trip_point| trip_point.temperature_in(Units::Celsius) <= current)
+            .max_by(|a, b| {
+                a.temperature_in(Units::Celsius)
+                    .total_cmp(&b.temperature_in(Units::Celsius))
+            })
+    }
+
+    /// Returns the current temperature of the sensor converted to the given units, regardless of
+    /// the units it is stored in.
+    ///
+    /// # Arguments
+    ///
+    /// * `units` - The units to convert the temperature to.
+    pub fn temperature_in(&self, units: Units) -> f32 {
+        convert_from_celsius(
+            convert_to_celsius(self.current_temperature, self.units),
+            units,
+        )
+    }
+
+    /// Returns the current temperature of the sensor in degrees Celsius and Fahrenheit, in that
+    /// order, regardless of the units it is stored in, for displays showing both.
+    pub fn dual(&self) -> (f32, f32) {
+        (
+            self.temperature_in(Units::Celsius),
+            self.temperature_in(Units::Fahrenheit),
+        )
+    }
+
+    /// Returns whether the zone has reached the temperature of any of its critical trip points.
+    /// The temperatures are compared in degrees Celsius, so the result is correct even if the
+    /// trip points are in different units from the sensor.
+    pub fn is_critical(&self) -> bool {
+        let current = self.temperature_in(Units::Celsius);
+        self.trip_points
+            .iter()
+            .filter(|trip_point| trip_point.action_type == "critical")
+            .any(|trip_point| current >= trip_point.temperature_in(Units::Celsius))
+    }
+
+    /// Returns the line the `acpi -t` tool prints for this zone, e.g.
+    /// `Thermal 0: ok, 47.0 degrees C`. The status is `critical` if the zone has reached a critical
+    /// trip point, `active` if it has reached any other trip point, and `ok` otherwise. The
+    /// temperature is given in the units the sensor was read in.
+    ///
+    /// # Arguments
+    ///
+    /// * `index` - The number `acpi` would assign the zone, counting from zero.
+    pub fn to_acpi_line(&self, index: usize) -> String {
+        let status = self.trip_status();
+        let units = match self.units {
+            Units::Celsius => "degrees C",
+            Units::Fahrenheit => "degrees F",
+            Units::Kelvin => "kelvin",
+        };
+        format!(
+            "Thermal {}: {}, {:.1} {}",
+            index, status, self.current_temperature, units
+        )
+    }
+
+    /// Returns `critical` if the zone has reached a critical trip point, `active` if it has reached
+    /// any other trip point, and `ok` otherwise.
+    fn trip_status(&self) -> &'static str {
+        let current = self.temperature_in(Units::Celsius);
+        if self.is_critical() {
+            "critical"
+        } else if self
+            .trip_points
+            .iter()
+            .any(|trip_point| current >= trip_point.temperature_in(Units::Celsius))
+        {
+            "active"
+        } else {
+            "ok"
+        }
+    }
+
+    /// Makes the zone report a temperature instead of its measured one by writing its `emul_temp`
+    /// attribute, for testing how the system responds to heat. This requires root privileges and
+    /// a kernel built with thermal emulation, and zones without an `emul_temp` attribute are
+    /// reported as `NotFound`.
+    ///
+    /// # Arguments
+    ///
+    /// * `temp_celsius` - The temperature to emulate in degrees Celsius, or `None` to stop
+    ///   emulating and report the measured temperature again.
+    pub fn set_emulated_temperature(
+        &self,
+        temp_celsius: Option<f32>,
+    ) -> Result<(), AcpiClientError> {
+        let millidegrees = match temp_celsius {
+            Some(temperature) => {
+                // Zero is reserved for clearing the emulated temperature, so a temperature that
+                // rounds to it is nudged by one millidegree in its own direction
+                let millidegrees = try_convert_to_celsius(temperature, Units::Celsius)? * 1000.;
+                match millidegrees.round() as i32 {
+                    0 if millidegrees < 0. => -1,
+                    0 => 1,
+                    millidegrees => millidegrees,
+                }
+            }
+            None => 0,
+        };
+        write_attribute(&self.path.join(attrs::EMUL_TEMP), &millidegrees.to_string())
+    }
+
+    /// Returns the identity of the sensor, for use as a key across polls.
+    pub fn id(&self) -> DeviceId {
+        DeviceId(self.name.clone())
+    }
+}
+
+/// Returns the sensor with the highest current temperature, or `None` if there are no sensors.
+///
+/// # Arguments
+///
+/// * `sensors` - The sensors to compare, all reporting in the same units.
+pub fn hottest_sensor(sensors: &[ThermalSensor]) -> Option<&ThermalSensor> {
+    sensors
+        .iter()
+        .max_by(|a, b| a.current_temperature.total_cmp(&b.current_temperature))
+}
+
+/// Returns the sensor with the lowest current temperature, or `None` if there are no sensors.
+///
+/// # Arguments
+///
+/// * `sensors` - The sensors to compare, all reporting in the same units.
+pub fn coolest_sensor(sensors: &[ThermalSensor]) -> Option<&ThermalSensor> {
+    sensors
+        .iter()
+        .min_by(|a, b| a.current_temperature.total_cmp(&b.current_temperature))
+}
+
+/// Returns a compact line describing the hottest zone for a status bar, e.g.
+/// `47°C (thermal_zone0, ok)`, or `no sensors` if there are none. The zone's status is
+/// `critical`, `active`, or `ok` as in `ThermalSensor::to_acpi_line`.
+///
+/// # Arguments
+///
+/// * `sensors` - The sensors to consider, which may report in different units.
+/// * `units` - The units to report the temperature in.
+pub fn thermal_status_line(sensors: &[ThermalSensor], units: Units) -> String {
+    let hottest = sensors.iter().max_by(|a, b| {
+        a.temperature_in(Units::Celsius)
+            .total_cmp(&b.temperature_in(Units::Celsius))
+    });
+    match hottest {
+        Some(sensor) => format!(
+            "{:.0}{} ({}, {})",
+            sensor.temperature_in(units),
+            units.symbol(),
+            sensor.name,
+            sensor.trip_status()
+        ),
+        None => String::from("no sensors"),
+    }
+}
+
+/// Returns the sensors sorted from the highest current temperature to the lowest, keeping sensors
+/// with equal temperatures in their original order.
+///
+/// # Arguments
+///
+/// * `sensors` - The sensors to sort, all reporting in the same units.
+pub fn thermal_sensors_sorted(mut sensors: Vec<ThermalSensor>) -> Vec<ThermalSensor> {
+    sensors.sort_by(|a, b| b.current_temperature.total_cmp(&a.current_temperature));
+    sensors
+}
+
+/// Returns how far the zone closest to overheating is from its critical trip point in degrees
+/// Celsius, i.e. the smallest difference between a zone's current temperature and its lowest
+/// critical trip point. A zone at or past its critical trip point has a headroom of zero. Returns
+/// `None` if no zone has a critical trip point.
+///
+/// # Arguments
+///
+/// * `sensors` - The sensors to check, which may report in different units.
+pub fn thermal_headroom(sensors: &[ThermalSensor]) -> Option<f32> {
+    sensors
+        .iter()
+        .flat_map(|sensor| {
+            let current = sensor.temperature_in(Units::Celsius);
+            sensor
+                .trip_points
+                .iter()
+                .filter(|trip_point| trip_point.action_type == "critical")
+                .map(move |trip_point| {
+                    (trip_point.temperature_in(Units::Celsius) - current).max(0.)
+                })
         })
+        .min_by(|a, b| a.total_cmp(b))
+}
+
+/// Returns the mean current temperature of the sensors in the given units, weighting every zone
+/// equally, or `None` if there are no sensors.
+///
+/// # Arguments
+///
+/// * `sensors` - The sensors to average, which may report in different units.
+/// * `units` - The units to report the average in.
+pub fn average_temperature(sensors: &[ThermalSensor], units: Units) -> Option<f32> {
+    if sensors.is_empty() {
+        return None;
+    }
+    let total: f32 = sensors
+        .iter()
+        .map(|sensor| sensor.temperature_in(units))
+        .sum();
+    Some(total / sensors.len() as f32)
+}
+
+/// Reads every `tempM_input` temperature of the hardware monitor directories beneath a thermal
+/// zone, e.g. `hwmon1`, ordered by directory and then by M. Inputs which can't be read are skipped.
+///
+/// # Arguments
+///
+/// * `source` - The source to read the zone's attributes from.
+/// * `path` - The path to the thermal zone entry.
+/// * `units` - The units to report the temperatures in.
+fn read_sub_temperatures(
+    source: &dyn AttributeSource,
+    path: &path::Path,
+    units: Units,
+) -> Vec<(String, f32)> {
+    let mut results = vec![];
+    for hwmon in source
+        .list(path)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|name| name.starts_with(attrs::HWMON_PREFIX))
+    {
+        let hwmon_path = path.join(&hwmon);
+        let mut indices: Vec<u32> = source
+            .list(&hwmon_path)
+            .unwrap_or_default()
+            .iter()
+            .filter_map(|name| {
+                name.strip_prefix("temp")?
+                    .strip_suffix("_input")?
+                    .parse()
+                    .ok()
+            })
+            .collect();
+        indices.sort_unstable();
+        for index in indices {
+            let input_path = hwmon_path.join(attrs::hwmon_temp_input(index));
+            let temperature = match read_attribute_to_i32(source, &input_path, 1) {
+                Ok(temperature) => convert_from_celsius(temperature as f32 / 1000., units),
+                Err(_) => continue,
+            };
+            let label = read_attribute(source, &hwmon_path.join(attrs::hwmon_temp_label(index)))
+                .ok()
+                .filter(|label| !label.is_empty())
+                .unwrap_or_else(|| format!("{}/temp{}", hwmon, index));
+            results.push((label, temperature));
+        }
+    }
+    results
+}
+
+/// Returns the N and the name of each cooling device linked to a thermal zone through its `cdevN`
+/// symlinks, ordered by N. The zone's entries are listed through the source, but the symlinks
+/// themselves are resolved on the filesystem.
+///
+/// # Arguments
+///
+/// * `source` - The source to list the zone's entries through.
+/// * `path` - The path to the ACPI thermal zone.
+fn read_bound_cooling_devices(
+    source: &dyn AttributeSource,
+    path: &path::Path,
+) -> Result<Vec<(u32, String)>, AcpiClientError> {
+    let mut links: Vec<(u32, String)> = vec![];
+
+    for name in source.list(path)? {
+        let index = match name
+            .strip_prefix(attrs::CDEV_PREFIX)
+            .and_then(|index| index.parse::<u32>().ok())
+        {
+            Some(index) => index,
+            None => continue,
+        };
+        if let Ok(target) = fs::read_link(path.join(&name)) {
+            links.push((index, get_device_name(&target)?));
+        }
+    }
+    links.sort();
+
+    Ok(links)
+}
+
+/// Reads a trip point of a thermal zone from a source. See `TripPoint::new` for details.
+///
+/// # Arguments
+///
+/// * `source` - The source to read the trip point's attributes from.
+/// * `path` - The path to the ACPI device trip points are configured for.
+/// * `number` - The numerical id of the trip point.
+/// * `units` - The units to convert the temperature data to.
+fn read_trip_point(
+    source: &dyn AttributeSource,
+    path: &path::Path,
+    number: u8,
+    units: Units,
+) -> Result<TripPoint, AcpiClientError> {
+    let action_type = read_attribute(source, &path.join(attrs::trip_point_type(number)))?;
+    let temperature = read_attribute_to_i32(source, &path.join(attrs::trip_point_temp(number)), 1)?;
+    // Drivers report a temperature of -1 for trip points which are disabled
+    if temperature == -1 {
+        return Err(AcpiClientError::InvalidInput(std::io::Error::other(
+            format!("Trip point {} is disabled.", number),
+        )));
     }
+    let temperature_c = temperature as f32 / 1000.;
+
+    Ok(TripPoint {
+        number,
+        action_type,
+        temperature: convert_from_celsius(temperature_c, units),
+        units,
+    })
 }
 
 impl TripPoint {
-    /// Create a new trip point object from data from the ACPI subsystem.
+    /// Create a new trip point object from data from the ACPI subsystem. Trip points which are
+    /// disabled, reporting a temperature of -1, are an error.
     ///
     /// # Arguments
     ///
@@ -109,32 +841,86 @@ impl TripPoint {
     /// * `number` - The numerical id of the trip point.
     /// * `units` - The units to convert the temperature data to.
     pub fn new(path: &path::Path, number: u8, units: Units) -> Result<TripPoint, AcpiClientError> {
-        let action_type = String::from(parse_entry_file(
-            &path.join(format!("trip_point_{}_type", number)),
-        )?);
-        let temperature_c =
-            (parse_file_to_i32(&path.join(format!("trip_point_{}_temp", number)), 1)? as f32)
-                / 1000.;
-
-        Ok(TripPoint {
-            number,
-            action_type,
-            temperature: convert_from_celsius(temperature_c, units),
-            units,
-        })
+        read_trip_point(&FileSystemSource, path, number, units)
+    }
+
+    /// Returns the temperature of the trip point converted to the given units, regardless of the
+    /// units it is stored in.
+    ///
+    /// # Arguments
+    ///
+    /// * `units` - The units to convert the temperature to.
+    pub fn temperature_in(&self, units: Units) -> f32 {
+        convert_from_celsius(convert_to_celsius(self.temperature, self.units), units)
     }
 }
 
+/// The lowest physically possible temperature in degrees Celsius.
+pub const ABSOLUTE_ZERO_CELSIUS: f32 = -273.15;
+
 /// Convert a temperature value to a different scale from degrees Celsius.
 ///
 /// # Arguments
 ///
 /// * `temperature` - The measurement to convert in Celsius.
 /// * `units` - The measurement scale to convert to.
-fn convert_from_celsius(temperature: f32, units: Units) -> f32 {
+pub fn convert_from_celsius(temperature: f32, units: Units) -> f32 {
     match units {
         Units::Celsius => temperature,
         Units::Fahrenheit => (temperature * 1.8) + 32.,
         Units::Kelvin => temperature + 273.15,
     }
 }
+
+/// Convert a temperature value from a different scale to degrees Celsius.
+///
+/// # Arguments
+///
+/// * `temperature` - The measurement to convert.
+/// * `units` - The measurement scale the temperature is in.
+pub fn convert_to_celsius(temperature: f32, units: Units) -> f32 {
+    match units {
+        Units::Celsius => temperature,
+        Units::Fahrenheit => (temperature - 32.) / 1.8,
+        Units::Kelvin => temperature - 273.15,
+    }
+}
+
+/// Convert a temperature value to a different scale from degrees Celsius, returning an error if
+/// the temperature is below absolute zero.
+///
+/// # Arguments
+///
+/// * `temperature` - The measurement to convert in Celsius.
+/// * `units` - The measurement scale to convert to.
+pub fn try_convert_from_celsius(temperature: f32, units: Units) -> Result<f32, AcpiClientError> {
+    check_above_absolute_zero(temperature)?;
+    Ok(convert_from_celsius(temperature, units))
+}
+
+/// Convert a temperature value from a different scale to degrees Celsius, returning an error if
+/// the temperature is below absolute zero.
+///
+/// # Arguments
+///
+/// * `temperature` - The measurement to convert.
+/// * `units` - The measurement scale the temperature is in.
+pub fn try_convert_to_celsius(temperature: f32, units: Units) -> Result<f32, AcpiClientError> {
+    let celsius = convert_to_celsius(temperature, units);
+    check_above_absolute_zero(celsius)?;
+    Ok(celsius)
+}
+
+/// Returns an error if a temperature in degrees Celsius is physically impossible.
+///
+/// # Arguments
+///
+/// * `celsius` - The measurement to check in Celsius.
+fn check_above_absolute_zero(celsius: f32) -> Result<(), AcpiClientError> {
+    if celsius < ABSOLUTE_ZERO_CELSIUS || celsius.is_nan() {
+        return Err(AcpiClientError::InvalidInput(std::io::Error::other(
+            format!("Temperature below absolute zero: {} degrees C", celsius),
+        )));
+    }
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use std::path;
+use std::time;
+
+use crate::attrs;
+use crate::battery::*;
+use crate::thermal_zone::*;
+use crate::utils::*;
+
+/// The time taken to parse each device during a scan, keyed by the device's name.
+pub type DeviceTimings = Vec<(String, time::Duration)>;
+
+/// Check the ACPI system for all batteries the OS knows about, recording how long each device
+/// took to parse. Devices which fail to parse are skipped but still have their timing recorded.
+///
+/// # Arguments
+///
+/// * `path` - The path to battery entries produced by the ACPI subsystem.
+pub fn get_battery_info_timed(
+    path: &path::Path,
+) -> Result<(Vec<BatteryInfo>, DeviceTimings), AcpiClientError> {
+    let mut results: Vec<BatteryInfo> = vec![];
+    let mut timings: DeviceTimings = vec![];
+
+    for path in read_device_dirs(path)? {
+        let device_type = match parse_entry_file(&path.join(attrs::TYPE)) {
+            Ok(device_type) => device_type,
+            Err(_) => continue,
+        };
+        if is_battery_type(&device_type) {
+            let start = time::Instant::now();
+            let battery = BatteryInfo::new(&path);
+            timings.push((get_device_name(&path)?, start.elapsed()));
+            if let Ok(battery) = battery {
+                results.push(battery);
+            }
+        }
+    }
+
+    Ok((results, timings))
+}
+
+/// Check the ACPI system for all thermal sensors the OS knows about, recording how long each
+/// device took to parse. Devices which fail to parse are skipped but still have their timing
+/// recorded.
+///
+/// # Arguments
+///
+/// * `path` - The path to thermal zone entries produced by the ACPI subsystem.
+/// * `units` - The units to report thermal data in.
+pub fn get_thermal_sensor_info_timed(
+    path: &path::Path,
+    units: Units,
+) -> Result<(Vec<ThermalSensor>, DeviceTimings), AcpiClientError> {
+    let mut results: Vec<ThermalSensor> = vec![];
+    let mut timings: DeviceTimings = vec![];
+
+    for path in read_device_dirs(path)? {
+        if is_thermal_sensor(&path) {
+            let start = time::Instant::now();
+            let sensor = ThermalSensor::new(&path, units);
+            timings.push((get_device_name(&path)?, start.elapsed()));
+            if let Ok(sensor) = sensor {
+                results.push(sensor);
+            }
+        }
+    }
+
+    Ok((results, timings))
+}
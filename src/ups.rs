@@ -0,0 +1,96 @@
+use std::path;
+use std::time;
+
+use crate::attrs;
+use crate::battery::*;
+use crate::power_supply::PowerSupplyType;
+use crate::system::DeviceId;
+use crate::utils::*;
+
+/// Information about an uninterruptible power supply reported as a power supply of type `UPS`.
+pub struct UpsInfo {
+    /// The name used by ACPI to refer to the device.
+    pub name: String,
+    /// The path to the device's directory.
+    pub path: path::PathBuf,
+    /// The model name reported by the UPS, if it reports one.
+    pub model_name: Option<String>,
+    /// The charge of the UPS's battery as a percentage.
+    pub percentage: f32,
+    /// The state of the UPS's battery.
+    pub state: ChargingState,
+    /// The time the UPS can power the system for, if it reports it.
+    pub time_to_empty: Option<time::Duration>,
+    /// The time at which the UPS was read.
+    pub sampled_at: time::SystemTime,
+}
+
+/// Check the ACPI system for all UPSes the OS knows about. Devices which can't be parsed are
+/// skipped.
+///
+/// # Arguments
+///
+/// * `path` - The path to power supply entries produced by the ACPI subsystem.
+pub fn get_ups_info(path: &path::Path) -> Result<Vec<UpsInfo>, AcpiClientError> {
+    let mut results: Vec<UpsInfo> = vec![];
+
+    for path in read_device_dirs(path)? {
+        let is_ups = parse_entry_file(&path.join(attrs::TYPE)).is_ok_and(|device_type| {
+            PowerSupplyType::from(device_type.as_str()) == PowerSupplyType::Ups
+        });
+        if is_ups {
+            if let Ok(ups) = UpsInfo::new(&path) {
+                results.push(ups);
+            }
+        }
+    }
+
+    Ok(results)
+}
+
+impl UpsInfo {
+    /// Create a new UPS object from data from the ACPI subsystem.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the ACPI device.
+    pub fn new(path: &path::Path) -> Result<UpsInfo, AcpiClientError> {
+        let sampled_at = time::SystemTime::now();
+        let name = get_device_name(path)?;
+        let percentage = parse_file_to_i32(&path.join(attrs::CAPACITY), 1)?.clamp(0, 100) as f32;
+        let state = parse_charging_state(&parse_entry_file(&path.join(attrs::STATUS))?)?;
+        let time_to_empty = parse_file_to_i32(&path.join(attrs::TIME_TO_EMPTY_NOW), 1)
+            .ok()
+            .filter(|seconds| *seconds > 0)
+            .map(|seconds| time::Duration::from_secs(seconds as u64));
+        let model_name = parse_entry_file(&path.join(attrs::MODEL_NAME))
+            .ok()
+            .filter(|model_name| !model_name.is_empty());
+
+        Ok(UpsInfo {
+            name,
+            path: path.to_path_buf(),
+            model_name,
+            percentage,
+            state,
+            time_to_empty,
+            sampled_at,
+        })
+    }
+
+    /// Create a new UPS object from the directory of a device named `name` within a class
+    /// directory, returning a `NotFound` error if there is no such device.
+    ///
+    /// # Arguments
+    ///
+    /// * `root` - The path to the power supply entries produced by the ACPI subsystem.
+    /// * `name` - The name used by ACPI to refer to the device, e.g. `ups`.
+    pub fn from_root_and_name(root: &path::Path, name: &str) -> Result<UpsInfo, AcpiClientError> {
+        UpsInfo::new(&device_path(root, name)?)
+    }
+
+    /// Returns the identity of the UPS, for use as a key across polls.
+    pub fn id(&self) -> DeviceId {
+        DeviceId(self.name.clone())
+    }
+}
@@ -1,9 +1,10 @@
 use std::fmt;
-use std::fs;
 use std::io;
-use std::io::prelude::*;
 use std::path;
 
+use crate::attrs;
+use crate::source::*;
+
 #[derive(Debug)]
 pub enum AcpiClientError {
     Parse(std::num::ParseIntError),
@@ -22,15 +23,7 @@ impl fmt::Display for AcpiClientError {
 }
 
 impl std::error::Error for AcpiClientError {
-    fn description(&self) -> &str {
-        match *self {
-            AcpiClientError::Parse(ref err) => std::error::Error::description(err),
-            AcpiClientError::Io(ref err) => err.description(),
-            AcpiClientError::InvalidInput(ref err) => err.description(),
-        }
-    }
-
-    fn cause(&self) -> Option<&dyn std::error::Error> {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
         match *self {
             AcpiClientError::Parse(ref err) => Some(err),
             AcpiClientError::Io(ref err) => Some(err),
@@ -51,24 +44,112 @@ impl From<std::num::ParseIntError> for AcpiClientError {
     }
 }
 
+/// Returns whether the contents of a power supply's `type` attribute identify it as a battery,
+/// ignoring case and surrounding whitespace, e.g. `Battery`, `BATTERY`, or ` battery\n`.
+///
+/// # Arguments
+///
+/// * `device_type` - The contents of the `type` attribute.
+pub fn is_battery_type(device_type: &str) -> bool {
+    device_type.trim().eq_ignore_ascii_case("battery")
+}
+
+/// Returns whether the contents of a power supply's `type` attribute identify it as a battery. See
+/// `is_battery_type`, which this is kept for compatibility with.
 pub fn determine_is_battery(data: String) -> bool {
-    data.to_lowercase() == "battery"
+    is_battery_type(&data)
 }
 
 pub fn is_thermal_sensor(device_path: &path::Path) -> bool {
-    let temperature_file_path = device_path.to_path_buf().join("temp");
+    let temperature_file_path = device_path.to_path_buf().join(attrs::TEMP);
     temperature_file_path.exists()
 }
 
+/// Returns whether a device name matches a glob pattern, e.g. `BAT*`. A `*` in the pattern matches
+/// any run of characters, including none, and a `?` matches exactly one character. Every other
+/// character must match exactly.
+///
+/// # Arguments
+///
+/// * `pattern` - The pattern to match against.
+/// * `name` - The name of the device.
+pub fn name_matches(pattern: &str, name: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let name: Vec<char> = name.chars().collect();
+    let (mut p, mut n) = (0, 0);
+    // The position of the last `*` seen and the position in the name it was matched up to
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while n < name.len() {
+        if p < pattern.len() && (pattern[p] == '?' || pattern[p] == name[n]) {
+            p += 1;
+            n += 1;
+        } else if p < pattern.len() && pattern[p] == '*' {
+            backtrack = Some((p, n));
+            p += 1;
+        } else if let Some((star, matched)) = backtrack {
+            // Let the last `*` consume one more character and try again
+            p = star + 1;
+            n = matched + 1;
+            backtrack = Some((star, n));
+        } else {
+            return false;
+        }
+    }
+
+    pattern[p..].iter().all(|c| *c == '*')
+}
+
+/// Returns the paths of the device directories in a class directory. Entries which aren't
+/// directories, or whose metadata can't be resolved, such as broken symlinks, are skipped rather
+/// than failing the scan. Device symlinks are resolved only to check their targets, and are not
+/// descended into.
+///
+/// # Arguments
+///
+/// * `path` - The path to the class directory, e.g. `/sys/class/power_supply`.
+pub fn read_device_dirs(path: &path::Path) -> Result<Vec<path::PathBuf>, AcpiClientError> {
+    let mut results: Vec<path::PathBuf> = vec![];
+
+    for entry in std::fs::read_dir(path)? {
+        let device_path = match entry {
+            Ok(entry) => entry.path(),
+            Err(_) => continue,
+        };
+        match std::fs::metadata(&device_path) {
+            Ok(metadata) if metadata.is_dir() => results.push(device_path),
+            _ => continue,
+        }
+    }
+
+    Ok(results)
+}
+
+/// Returns the path of a device directory given the class directory containing it and the name of
+/// the device, returning a `NotFound` error naming the device if the directory doesn't exist.
+///
+/// # Arguments
+///
+/// * `root` - The path to the class directory, e.g. `/sys/class/power_supply`.
+/// * `name` - The name of the device, e.g. `BAT0`.
+pub fn device_path(root: &path::Path, name: &str) -> Result<path::PathBuf, AcpiClientError> {
+    let path = root.join(name);
+    if !path.is_dir() {
+        return Err(AcpiClientError::Io(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("Device {} not found in {}.", name, root.display()),
+        )));
+    }
+    Ok(path)
+}
+
 pub fn get_device_name(path: &path::Path) -> Result<String, AcpiClientError> {
-    let filename = path.file_name().ok_or(AcpiClientError::Io(io::Error::new(
-        io::ErrorKind::Other,
-        "Path is not a file.",
-    )))?;
-    let filename_str = filename.to_str().ok_or(AcpiClientError::Io(io::Error::new(
-        io::ErrorKind::Other,
-        "Filename contains Unicode characters.",
-    )))?;
+    let filename = path
+        .file_name()
+        .ok_or_else(|| AcpiClientError::Io(io::Error::other("Path is not a file.")))?;
+    let filename_str = filename.to_str().ok_or_else(|| {
+        AcpiClientError::Io(io::Error::other("Filename contains Unicode characters."))
+    })?;
     Ok(String::from(filename_str))
 }
 
@@ -78,19 +159,17 @@ pub fn get_device_name(path: &path::Path) -> Result<String, AcpiClientError> {
 ///
 /// * `path` - A path to the file to parse
 pub fn parse_entry_file(path: &path::Path) -> Result<String, AcpiClientError> {
-    let mut result = String::new();
-
-    if path.is_file() {
-        let mut f = fs::File::open(path)?;
-        f.read_to_string(&mut result)?;
-        let result = result.trim();
-        return Ok(String::from(result));
-    }
+    read_attribute(&FileSystemSource, path)
+}
 
-    Err(AcpiClientError::Io(io::Error::new(
-        io::ErrorKind::Other,
-        "Path is not a file.",
-    )))
+/// Returns the value of a key from the `uevent` file in a device's directory.
+///
+/// # Arguments
+///
+/// * `path` - The path to the device directory containing the `uevent` file
+/// * `key` - The name of the key to look up, e.g. `POWER_SUPPLY_STATUS`
+pub fn parse_uevent_entry(path: &path::Path, key: &str) -> Result<String, AcpiClientError> {
+    read_uevent_entry(&FileSystemSource, path, key)
 }
 
 /// Parses a file and converts the resulting contents to an integer.
@@ -100,5 +179,126 @@ pub fn parse_entry_file(path: &path::Path) -> Result<String, AcpiClientError> {
 /// * `path` - A path to the file to parse
 /// * `scalar` - A number to divide the output by before returning it
 pub fn parse_file_to_i32(path: &path::Path, scalar: i32) -> Result<i32, AcpiClientError> {
-    Ok(parse_entry_file(path)?.parse::<i32>()? / scalar)
+    read_attribute_to_i32(&FileSystemSource, path, scalar)
+}
+
+/// Returns the trimmed contents of an attribute file read from a source.
+///
+/// # Arguments
+///
+/// * `source` - The source to read the attribute from
+/// * `path` - A path to the file to parse
+pub fn read_attribute(
+    source: &dyn AttributeSource,
+    path: &path::Path,
+) -> Result<String, AcpiClientError> {
+    Ok(String::from(source.read(path)?.trim()))
+}
+
+/// Returns the value of a key from the `uevent` file in a device's directory read from a source.
+///
+/// # Arguments
+///
+/// * `source` - The source to read the attribute from
+/// * `path` - The path to the device directory containing the `uevent` file
+/// * `key` - The name of the key to look up, e.g. `POWER_SUPPLY_STATUS`
+pub fn read_uevent_entry(
+    source: &dyn AttributeSource,
+    path: &path::Path,
+    key: &str,
+) -> Result<String, AcpiClientError> {
+    read_attribute(source, &path.join(attrs::UEVENT))?
+        .lines()
+        .filter_map(|line| line.split_once('='))
+        .find(|(entry_key, _)| entry_key.trim() == key)
+        .map(|(_, value)| String::from(value.trim()))
+        .ok_or_else(|| {
+            AcpiClientError::InvalidInput(io::Error::other(format!("{} not found in uevent.", key)))
+        })
+}
+
+/// Writes a value to an attribute file. Attributes the device doesn't have are reported as
+/// `NotFound` rather than being created, and since writing attributes generally requires root
+/// privileges, a permission failure is reported with a message saying so.
+///
+/// # Arguments
+///
+/// * `path` - A path to the file to write
+/// * `value` - The value to write to the file
+pub fn write_attribute(path: &path::Path, value: &str) -> Result<(), AcpiClientError> {
+    if !path.exists() {
+        return Err(AcpiClientError::Io(io::Error::new(
+            io::ErrorKind::NotFound,
+            format!("{} is not supported by the device.", path.display()),
+        )));
+    }
+    std::fs::write(path, value).map_err(|err| match err.kind() {
+        io::ErrorKind::PermissionDenied => AcpiClientError::Io(io::Error::new(
+            io::ErrorKind::PermissionDenied,
+            format!(
+                "Permission denied writing {}, which requires root privileges.",
+                path.display()
+            ),
+        )),
+        _ => AcpiClientError::Io(err),
+    })
+}
+
+/// Reads an attribute file from a source and converts the contents to an integer. Values which
+/// don't fit in an `i32` after dividing by `scalar` are clamped to its range rather than rejected,
+/// since some drivers report absurdly large values.
+///
+/// # Arguments
+///
+/// * `source` - The source to read the attribute from
+/// * `path` - A path to the file to parse
+/// * `scalar` - A number to divide the output by before returning it
+pub fn read_attribute_to_i32(
+    source: &dyn AttributeSource,
+    path: &path::Path,
+    scalar: i32,
+) -> Result<i32, AcpiClientError> {
+    let value = parse_i64(&read_attribute(source, path)?)? / i64::from(scalar);
+    Ok(value.clamp(i64::from(i32::MIN), i64::from(i32::MAX)) as i32)
+}
+
+/// Parses an integer from the contents of an attribute file. Values written with a decimal point,
+/// e.g. `5850000.0`, are rounded to the nearest integer, and values too large for an `i64` are
+/// clamped to its range. A units suffix after the number, e.g. the ` uV` of `15045000 uV`, is
+/// ignored.
+///
+/// # Arguments
+///
+/// * `value` - The trimmed contents of an attribute file
+fn parse_i64(value: &str) -> Result<i64, AcpiClientError> {
+    let value = value
+        .trim_end_matches(|c: char| c.is_alphabetic() || c.is_whitespace())
+        .trim_end();
+    match value.parse::<i64>() {
+        Ok(value) => Ok(value),
+        Err(err) => match value.parse::<f64>() {
+            Ok(value) if value.is_finite() => Ok(value.round() as i64),
+            _ => Err(AcpiClientError::Parse(err)),
+        },
+    }
+}
+
+/// Reads an optional attribute file from a source and converts the contents to an integer.
+///
+/// Optional attributes which are missing, unreadable due to insufficient permissions, or
+/// malformed are reported as `None` rather than as an error. Drivers commonly fail reads of
+/// attributes a device doesn't support with other errors such as `ENODEV` or `EIO`, so those are
+/// reported as `None` as well.
+///
+/// # Arguments
+///
+/// * `source` - The source to read the attribute from
+/// * `path` - A path to the file to parse
+/// * `scalar` - A number to divide the output by before returning it
+pub fn read_optional_attribute_to_i32(
+    source: &dyn AttributeSource,
+    path: &path::Path,
+    scalar: i32,
+) -> Option<i32> {
+    read_attribute_to_i32(source, path, scalar).ok()
 }
@@ -0,0 +1,194 @@
+// Each test crate only uses some of the helpers
+#![allow(dead_code)]
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::fmt::Display;
+use std::io::{self, Write};
+use std::path;
+
+/// A temporary directory laid out like a sysfs power supply or thermal class directory, for
+/// building mock devices without writing each attribute file by hand.
+pub struct MockSysfs {
+    dir: tempfile::TempDir,
+}
+
+impl MockSysfs {
+    /// Creates an empty mock directory.
+    pub fn new() -> MockSysfs {
+        MockSysfs {
+            dir: tempfile::tempdir().unwrap(),
+        }
+    }
+
+    /// Returns the path of the mock directory, to pass to the `get_*_info` functions.
+    pub fn path(&self) -> &path::Path {
+        self.dir.path()
+    }
+
+    /// Starts building a device with no attributes.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the device's directory.
+    pub fn device(&self, name: &str) -> MockDevice {
+        MockDevice {
+            path: self.dir.path().join(name),
+            attributes: vec![],
+        }
+    }
+
+    /// Starts building a battery, with its `type` attribute already set.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the battery's directory, e.g. `BAT0`.
+    pub fn battery(&self, name: &str) -> MockDevice {
+        self.device(name).attribute("type", "Battery")
+    }
+
+    /// Starts building a mains AC adapter, with its `type` attribute already set.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the adapter's directory, e.g. `ACAD`.
+    pub fn adapter(&self, name: &str) -> MockDevice {
+        self.device(name).attribute("type", "Mains")
+    }
+
+    /// Removes the mock directory.
+    pub fn close(self) {
+        self.dir.close().unwrap();
+    }
+}
+
+/// A device being built in a `MockSysfs`. Nothing is written until `build` is called.
+pub struct MockDevice {
+    path: path::PathBuf,
+    attributes: Vec<(String, String)>,
+}
+
+impl MockDevice {
+    /// Sets an attribute file to hold a value. The name may include a subdirectory, e.g.
+    /// `device/model`.
+    ///
+    /// # Arguments
+    ///
+    /// * `name` - The name of the attribute file.
+    /// * `value` - The contents of the attribute file.
+    pub fn attribute<T: Display>(mut self, name: &str, value: T) -> MockDevice {
+        self.attributes
+            .push((String::from(name), value.to_string()));
+        self
+    }
+
+    pub fn status(self, status: &str) -> MockDevice {
+        self.attribute("status", status)
+    }
+
+    pub fn charge_now(self, charge: u32) -> MockDevice {
+        self.attribute("charge_now", charge)
+    }
+
+    pub fn charge_full(self, charge: u32) -> MockDevice {
+        self.attribute("charge_full", charge)
+    }
+
+    pub fn charge_full_design(self, charge: u32) -> MockDevice {
+        self.attribute("charge_full_design", charge)
+    }
+
+    pub fn energy_now(self, energy: u32) -> MockDevice {
+        self.attribute("energy_now", energy)
+    }
+
+    pub fn energy_full(self, energy: u32) -> MockDevice {
+        self.attribute("energy_full", energy)
+    }
+
+    pub fn energy_full_design(self, energy: u32) -> MockDevice {
+        self.attribute("energy_full_design", energy)
+    }
+
+    pub fn current_now(self, current: u32) -> MockDevice {
+        self.attribute("current_now", current)
+    }
+
+    pub fn power_now(self, power: u32) -> MockDevice {
+        self.attribute("power_now", power)
+    }
+
+    pub fn voltage_now(self, voltage: u32) -> MockDevice {
+        self.attribute("voltage_now", voltage)
+    }
+
+    pub fn online(self, online: bool) -> MockDevice {
+        self.attribute("online", if online { 1 } else { 0 })
+    }
+
+    /// Writes the device's directory and attribute files, overwriting any attributes written by
+    /// an earlier build of the same device, and returns the path of the directory.
+    pub fn build(self) -> path::PathBuf {
+        for (name, value) in &self.attributes {
+            let attribute_path = self.path.join(name);
+            std::fs::create_dir_all(attribute_path.parent().unwrap()).unwrap();
+            let mut file = std::fs::File::create(attribute_path).unwrap();
+            writeln!(file, "{}", value).unwrap();
+        }
+        std::fs::create_dir_all(&self.path).unwrap();
+        self.path
+    }
+}
+
+/// An attribute source holding every attribute file in memory, for scanning devices which don't
+/// exist on the filesystem.
+#[derive(Default)]
+pub struct MemorySource {
+    files: BTreeMap<path::PathBuf, String>,
+}
+
+impl MemorySource {
+    /// Sets an attribute file to hold a value, creating the directories above it.
+    ///
+    /// # Arguments
+    ///
+    /// * `path` - The path to the attribute file.
+    /// * `value` - The contents of the attribute file.
+    pub fn attribute<P: AsRef<path::Path>, T: Display>(
+        mut self,
+        path: P,
+        value: T,
+    ) -> MemorySource {
+        self.files
+            .insert(path.as_ref().to_path_buf(), format!("{}\n", value));
+        self
+    }
+}
+
+impl acpi_client::AttributeSource for MemorySource {
+    fn read(&self, path: &path::Path) -> io::Result<String> {
+        self.files
+            .get(path)
+            .cloned()
+            .ok_or_else(|| io::Error::new(io::ErrorKind::NotFound, "No such attribute file."))
+    }
+
+    fn exists(&self, path: &path::Path) -> bool {
+        self.files.keys().any(|file| file.starts_with(path))
+    }
+
+    fn list(&self, path: &path::Path) -> io::Result<Vec<String>> {
+        let names: BTreeSet<String> = self
+            .files
+            .keys()
+            .filter_map(|file| file.strip_prefix(path).ok()?.iter().next())
+            .map(|name| name.to_string_lossy().into_owned())
+            .collect();
+        if names.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                "No such directory.",
+            ));
+        }
+        Ok(names.into_iter().collect())
+    }
+}
@@ -39,4 +39,152 @@ mod tests {
         drop(file);
         dir.close().unwrap();
     }
+
+    #[test]
+    fn adapter_online_status() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("ACAD");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Mains").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("online")).unwrap();
+        writeln!(file, "1").unwrap();
+
+        let acad = acpi_client::ACAdapterInfo::new(&mock_path).unwrap();
+        assert!(acad.is_online());
+
+        let mut file = std::fs::File::create(mock_path.join("online")).unwrap();
+        writeln!(file, "0").unwrap();
+
+        let acad = acpi_client::ACAdapterInfo::new(&mock_path).unwrap();
+        assert!(!acad.is_online());
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn skip_usb_supplies_by_default() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("ACAD");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Mains").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("online")).unwrap();
+        writeln!(file, "1").unwrap();
+
+        let mock_path = dir.path().join("ucsi-source-psy-USBC000:001");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "USB").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("online")).unwrap();
+        writeln!(file, "0").unwrap();
+
+        let adapters = acpi_client::get_ac_adapter_info(dir.path()).unwrap();
+        assert_eq!(adapters.len(), 1);
+        assert_eq!(adapters[0].name, "ACAD");
+
+        let adapters = acpi_client::get_adapter_info(dir.path(), true).unwrap();
+        assert_eq!(adapters.len(), 2);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn try_from_path() {
+        use std::convert::TryFrom;
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("ACAD");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Mains").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("online")).unwrap();
+        writeln!(file, "1").unwrap();
+
+        let adapter = acpi_client::ACAdapterInfo::try_from(mock_path.as_path()).unwrap();
+        assert!(adapter.is_online());
+        let adapter = acpi_client::ACAdapterInfo::try_from(mock_path.clone()).unwrap();
+        assert_eq!(adapter.name, "ACAD");
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn parse_textual_online_values() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("ACAD");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Mains").unwrap();
+
+        for (value, online) in &[
+            ("1", true),
+            ("0", false),
+            ("Yes", true),
+            ("no", false),
+            ("TRUE", true),
+            ("False", false),
+        ] {
+            let mut file = std::fs::File::create(mock_path.join("online")).unwrap();
+            writeln!(file, "{}", value).unwrap();
+            let adapter = acpi_client::ACAdapterInfo::new(&mock_path).unwrap();
+            assert_eq!(adapter.is_online(), *online, "online value {}", value);
+        }
+
+        let mut file = std::fs::File::create(mock_path.join("online")).unwrap();
+        writeln!(file, "maybe").unwrap();
+        assert!(acpi_client::ACAdapterInfo::new(&mock_path).is_err());
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn parse_usb_c_adapter_power() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("ucsi-source-psy-USBC000:001");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "USB").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("online")).unwrap();
+        writeln!(file, "1").unwrap();
+
+        let adapter = acpi_client::ACAdapterInfo::new(&mock_path).unwrap();
+        assert_eq!(adapter.power_watts, None);
+
+        let mut file = std::fs::File::create(mock_path.join("input_current_limit")).unwrap();
+        writeln!(file, "3250000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("input_voltage_limit")).unwrap();
+        writeln!(file, "20000000").unwrap();
+
+        let adapter = acpi_client::ACAdapterInfo::new(&mock_path).unwrap();
+        assert!((adapter.power_watts.unwrap() - 65.).abs() < 0.01);
+
+        // Adapters which report no power draw still have their limits
+        let mut file = std::fs::File::create(mock_path.join("power_now")).unwrap();
+        writeln!(file, "0").unwrap();
+        let adapter = acpi_client::ACAdapterInfo::new(&mock_path).unwrap();
+        assert!((adapter.power_watts.unwrap() - 65.).abs() < 0.01);
+
+        let mut file = std::fs::File::create(mock_path.join("power_now")).unwrap();
+        writeln!(file, "45000000").unwrap();
+
+        let adapter = acpi_client::ACAdapterInfo::new(&mock_path).unwrap();
+        assert!((adapter.power_watts.unwrap() - 45.).abs() < 0.01);
+
+        drop(file);
+        dir.close().unwrap();
+    }
 }
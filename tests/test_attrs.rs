@@ -0,0 +1,57 @@
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn parse_battery_from_attribute_names() {
+        use acpi_client::attrs;
+        use std::io::Write;
+
+        assert_eq!(attrs::CHARGE_NOW, "charge_now");
+        assert_eq!(attrs::trip_point_temp(2), "trip_point_2_temp");
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join(attrs::CHARGE_FULL)).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join(attrs::CHARGE_FULL_DESIGN)).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join(attrs::CHARGE_NOW)).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join(attrs::CURRENT_NOW)).unwrap();
+        writeln!(file, "599000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join(attrs::STATUS)).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join(attrs::VOLTAGE_NOW)).unwrap();
+        writeln!(file, "15045000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join(attrs::HEALTH)).unwrap();
+        writeln!(file, "Good").unwrap();
+        let mut file = std::fs::File::create(mock_path.join(attrs::UEVENT)).unwrap();
+        writeln!(file, "{}=5B10W13930", attrs::UEVENT_MODEL_NAME).unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.remaining_capacity.value(), 1000);
+        assert_eq!(battery.present_rate, 599);
+        assert_eq!(battery.voltage, 15045);
+        assert_eq!(
+            battery.reported_health,
+            Some(acpi_client::BatteryHealthStatus::Good)
+        );
+        assert_eq!(battery.model_name, Some(String::from("5B10W13930")));
+
+        let mock_path = dir.path().join("thermal_zone0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join(attrs::TEMP)).unwrap();
+        writeln!(file, "47000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join(attrs::trip_point_temp(0))).unwrap();
+        writeln!(file, "105000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join(attrs::trip_point_type(0))).unwrap();
+        writeln!(file, "critical").unwrap();
+
+        let sensor =
+            acpi_client::ThermalSensor::new(&mock_path, acpi_client::Units::Celsius).unwrap();
+        assert_eq!(sensor.trip_points.len(), 1);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+}
@@ -1,32 +1,3203 @@
+mod common;
+
 #[cfg(test)]
 mod tests {
+    use crate::common::MockSysfs;
+
     #[test]
     fn verify_mock_file_coulomb_parse() {
+        let sysfs = MockSysfs::new();
+        sysfs
+            .battery("BAT1")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .current_now(599000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+
+        let batteries = acpi_client::get_battery_info(sysfs.path());
+        assert!(batteries.is_ok());
+        assert_eq!(batteries.unwrap().len(), 1);
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn smoothed_rate_blends_time_remaining() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .current_now(500000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+
+        let previous = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+
+        sysfs.battery("BAT0").current_now(2000000).build();
+        let raw = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        let smoothed = acpi_client::BatteryReader::new()
+            .previous(Some(&previous))
+            .read(&mock_path)
+            .unwrap();
+
+        assert!(smoothed.present_rate > previous.present_rate);
+        assert!(smoothed.present_rate < raw.present_rate);
+        assert!(smoothed.time_remaining < previous.time_remaining);
+        assert!(smoothed.time_remaining > raw.time_remaining);
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn status_falls_back_to_uevent() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "599000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("uevent")).unwrap();
+        writeln!(file, "POWER_SUPPLY_NAME=BAT0").unwrap();
+        writeln!(file, "POWER_SUPPLY_STATUS=Charging").unwrap();
+        writeln!(file, "POWER_SUPPLY_PRESENT=1").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.state, acpi_client::ChargingState::Charging);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn capacity_levels_are_ordered() {
+        use acpi_client::CapacityLevel;
+
+        assert!(CapacityLevel::Critical < CapacityLevel::Normal);
+        assert!(CapacityLevel::Critical < CapacityLevel::Low);
+        assert!(CapacityLevel::High < CapacityLevel::Full);
+        assert!(CapacityLevel::Low >= CapacityLevel::Low);
+    }
+
+    #[test]
+    fn time_to_stop_threshold() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "599000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Charging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.charge_stop_threshold, None);
+        assert_eq!(battery.time_to_threshold(), battery.time_remaining);
+
+        let mut file =
+            std::fs::File::create(mock_path.join("charge_control_end_threshold")).unwrap();
+        writeln!(file, "80").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.charge_stop_threshold, Some(80));
+        // 600 mAh to go until 80% at 599 mA
+        assert_eq!(
+            battery.time_to_threshold(),
+            Some(std::time::Duration::from_secs(3600))
+        );
+        assert!(battery.time_to_threshold() < battery.time_remaining);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn full_battery_without_current() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Full").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Battery").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let batteries = acpi_client::get_battery_info(dir.path()).unwrap();
+        assert_eq!(batteries.len(), 1);
+        assert_eq!(batteries[0].present_rate, 0);
+        assert_eq!(batteries[0].state, acpi_client::ChargingState::Full);
+
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.present_rate, 0);
+        assert_eq!(battery.time_remaining, None);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn reading_records_sample_time() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "599000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let before = std::time::SystemTime::now();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        let after = std::time::SystemTime::now();
+        assert!(battery.sampled_at >= before);
+        assert!(battery.sampled_at <= after);
+        assert!(
+            after.duration_since(battery.sampled_at).unwrap() < std::time::Duration::from_secs(1)
+        );
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    struct DenyingSource {
+        denied: &'static str,
+    }
+
+    impl acpi_client::AttributeSource for DenyingSource {
+        fn read(&self, path: &std::path::Path) -> std::io::Result<String> {
+            if path.ends_with(self.denied) {
+                Err(std::io::Error::new(
+                    std::io::ErrorKind::PermissionDenied,
+                    "Permission denied",
+                ))
+            } else {
+                acpi_client::FileSystemSource.read(path)
+            }
+        }
+
+        fn exists(&self, path: &std::path::Path) -> bool {
+            acpi_client::FileSystemSource.exists(path)
+        }
+    }
+
+    #[test]
+    fn permission_denied_on_optional_attribute() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "599000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Charging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+        let mut file =
+            std::fs::File::create(mock_path.join("charge_control_end_threshold")).unwrap();
+        writeln!(file, "80").unwrap();
+
+        let battery = acpi_client::BatteryReader::new()
+            .source(DenyingSource {
+                denied: "charge_control_end_threshold",
+            })
+            .read(&mock_path)
+            .unwrap();
+        assert_eq!(battery.charge_stop_threshold, None);
+
+        let battery = acpi_client::BatteryReader::new()
+            .source(DenyingSource {
+                denied: "charge_now",
+            })
+            .read(&mock_path);
+        assert!(battery.is_err());
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn diff_state_transition() {
+        use acpi_client::ChargingState;
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "1500000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Charging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let previous = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "980000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "600000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+
+        let current = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        let diff = current.diff(&previous);
+        assert_eq!(
+            diff.state_transition,
+            Some((ChargingState::Charging, ChargingState::Discharging))
+        );
+        assert!((diff.percentage_delta + 1.0).abs() < 0.01);
+        assert_eq!(diff.rate_delta, -900);
+        assert_eq!(current.diff(&current).state_transition, None);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn capacity_level_without_rate() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "150000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("capacity_level")).unwrap();
+        writeln!(file, "Low").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.time_remaining, None);
+        assert_eq!(battery.present_rate, 0);
+        assert_eq!(
+            battery.capacity_level,
+            Some(acpi_client::CapacityLevel::Low)
+        );
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    struct FlakySource {
+        failures_remaining: std::sync::atomic::AtomicUsize,
+    }
+
+    impl acpi_client::AttributeSource for FlakySource {
+        fn read(&self, path: &std::path::Path) -> std::io::Result<String> {
+            use std::sync::atomic::Ordering;
+
+            if self
+                .failures_remaining
+                .fetch_update(Ordering::SeqCst, Ordering::SeqCst, |n| n.checked_sub(1))
+                .is_ok()
+            {
+                Err(std::io::Error::other("Resource temporarily unavailable"))
+            } else {
+                acpi_client::FileSystemSource.read(path)
+            }
+        }
+
+        fn exists(&self, path: &std::path::Path) -> bool {
+            acpi_client::FileSystemSource.exists(path)
+        }
+    }
+
+    #[test]
+    fn retry_transient_failure() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "599000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let battery = acpi_client::BatteryReader::new()
+            .source(FlakySource {
+                failures_remaining: std::sync::atomic::AtomicUsize::new(1),
+            })
+            .read(&mock_path);
+        assert!(battery.is_err());
+
+        let battery = acpi_client::BatteryReader::new()
+            .source(FlakySource {
+                failures_remaining: std::sync::atomic::AtomicUsize::new(1),
+            })
+            .with_retries(3, std::time::Duration::from_millis(1))
+            .read(&mock_path);
+        assert!(battery.is_ok());
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn energy_wh_from_capacity_battery() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "599000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15000000").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert!((battery.design_energy_wh() - 42.0).abs() < 0.01);
+        assert!((battery.last_energy_wh() - 30.0).abs() < 0.01);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn energy_wh_from_energy_battery() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("energy_full")).unwrap();
+        writeln!(file, "45000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("energy_full_design")).unwrap();
+        writeln!(file, "57000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("energy_now")).unwrap();
+        writeln!(file, "30000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("power_now")).unwrap();
+        writeln!(file, "9500000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "11400000").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert!((battery.design_energy_wh() - 57.0).abs() < 0.01);
+        assert!((battery.last_energy_wh() - 45.0).abs() < 0.01);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn acpi_line_matches_acpi_output() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "599000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(
+            battery.to_acpi_line(0),
+            "Battery 0: Discharging, 50%, 01:40:00 remaining"
+        );
+
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Charging").unwrap();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(
+            battery.to_acpi_line(1),
+            "Battery 1: Charging, 50%, 01:40:00 until charged"
+        );
+
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Full").unwrap();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.to_acpi_line(0), "Battery 0: Full, 100%");
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn rate_unit_for_each_path() {
+        use acpi_client::RateUnit;
         use std::io::Write;
 
         let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "599000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.present_rate_unit, RateUnit::MilliAmps);
+
         let mock_path = dir.path().join("BAT1");
-        let _mock_adapter = std::fs::create_dir(&mock_path).unwrap();
-        let mut file = std::fs::File::create(&mock_path.join("charge_full")).unwrap();
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("energy_full")).unwrap();
+        writeln!(file, "45000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("energy_full_design")).unwrap();
+        writeln!(file, "57000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("energy_now")).unwrap();
+        writeln!(file, "30000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("power_now")).unwrap();
+        writeln!(file, "9500000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "11400000").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.present_rate, 9500);
+        assert_eq!(battery.present_rate_unit, RateUnit::MilliWatts);
+
+        std::fs::remove_file(mock_path.join("power_now")).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "833000").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.present_rate, 833);
+        assert_eq!(battery.present_rate_unit, RateUnit::MilliAmps);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn zero_capacities_produce_finite_values() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("energy_full")).unwrap();
+        writeln!(file, "0").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("energy_full_design")).unwrap();
+        writeln!(file, "0").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("energy_now")).unwrap();
+        writeln!(file, "0").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("power_now")).unwrap();
+        writeln!(file, "0").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "0").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert!(battery.percentage.is_finite());
+        assert!(battery.design_energy_wh().is_finite());
+        assert!(battery.last_energy_wh().is_finite());
+        let display = battery.to_string();
+        assert!(!display.contains("NaN"));
+        assert!(!display.contains("inf"));
+        assert_eq!(display, "BAT0: Discharging, 0.0%, 0:00 remaining");
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn model_name_falls_back_to_uevent() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
         writeln!(file, "2000000").unwrap();
-        let mut file = std::fs::File::create(&mock_path.join("charge_full_design")).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
         writeln!(file, "2800000").unwrap();
-        let mut file = std::fs::File::create(&mock_path.join("charge_now")).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
         writeln!(file, "1000000").unwrap();
-        let mut file = std::fs::File::create(&mock_path.join("current_now")).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
         writeln!(file, "599000").unwrap();
-        let mut file = std::fs::File::create(&mock_path.join("status")).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
         writeln!(file, "Discharging").unwrap();
-        let mut file = std::fs::File::create(&mock_path.join("type")).unwrap();
-        writeln!(file, "Battery").unwrap();
-        let mut file = std::fs::File::create(&mock_path.join("voltage_now")).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
         writeln!(file, "15045000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("uevent")).unwrap();
+        writeln!(file, "POWER_SUPPLY_NAME=BAT0").unwrap();
+        writeln!(file, "POWER_SUPPLY_MODEL_NAME=5B10W13930").unwrap();
 
-        let batteries = acpi_client::get_battery_info(&dir.path());
-        assert!(batteries.is_ok());
-        assert_eq!(batteries.unwrap().len(), 1);
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.model_name, Some(String::from("5B10W13930")));
 
         drop(file);
         dir.close().unwrap();
     }
+
+    #[test]
+    fn relax_required_voltage() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "599000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+
+        assert!(acpi_client::BatteryInfo::new(&mock_path).is_err());
+
+        let battery = acpi_client::BatteryReader::new()
+            .optional_attribute("voltage_now")
+            .read(&mock_path)
+            .unwrap();
+        assert_eq!(battery.voltage, 0);
+        assert_eq!(battery.remaining_capacity.value(), 1000);
+        assert!((battery.percentage - 50.).abs() < 0.01);
+
+        assert!(acpi_client::BatteryReader::new()
+            .optional_attribute("voltage_now")
+            .required_attribute("voltage_now")
+            .read(&mock_path)
+            .is_err());
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn parse_health_strings() {
+        use acpi_client::BatteryHealthStatus;
+
+        assert_eq!(BatteryHealthStatus::from("Good"), BatteryHealthStatus::Good);
+        assert_eq!(
+            BatteryHealthStatus::from("Overheat\n"),
+            BatteryHealthStatus::Overheat
+        );
+        assert_eq!(BatteryHealthStatus::from("dead"), BatteryHealthStatus::Dead);
+        assert_eq!(BatteryHealthStatus::from("Cold"), BatteryHealthStatus::Cold);
+        assert_eq!(
+            BatteryHealthStatus::from("Over voltage"),
+            BatteryHealthStatus::OverVoltage
+        );
+        assert_eq!(
+            BatteryHealthStatus::from("Calibration required"),
+            BatteryHealthStatus::Other(String::from("Calibration required"))
+        );
+    }
+
+    #[test]
+    fn read_reported_health() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "599000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.reported_health, None);
+
+        let mut file = std::fs::File::create(mock_path.join("health")).unwrap();
+        writeln!(file, "Good").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(
+            battery.reported_health,
+            Some(acpi_client::BatteryHealthStatus::Good)
+        );
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn capacities_in_each_unit() {
+        use acpi_client::{BatteryReader, CapacityUnit};
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "500000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15000000").unwrap();
+
+        let battery = BatteryReader::new().read(&mock_path).unwrap();
+        assert_eq!(battery.capacity_unit, CapacityUnit::MilliAmpHours);
+        assert_eq!(battery.remaining_capacity.value(), 1000);
+        assert_eq!(battery.last_capacity.value(), 2000);
+
+        let battery = BatteryReader::new()
+            .capacity_unit(CapacityUnit::MilliWattHours)
+            .read(&mock_path)
+            .unwrap();
+        assert_eq!(battery.capacity_unit, CapacityUnit::MilliWattHours);
+        assert_eq!(battery.remaining_capacity.value(), 15000);
+        assert_eq!(battery.design_capacity.value(), 42000);
+        assert_eq!(battery.last_capacity.value(), 30000);
+        assert!((battery.design_energy_wh() - 42.0).abs() < 0.01);
+        assert_eq!(battery.time_remaining.unwrap().as_secs() / 60, 119);
+
+        let mock_path = dir.path().join("BAT1");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("energy_full")).unwrap();
+        writeln!(file, "45000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("energy_full_design")).unwrap();
+        writeln!(file, "60000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("energy_now")).unwrap();
+        writeln!(file, "30000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("power_now")).unwrap();
+        writeln!(file, "10000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "12000000").unwrap();
+
+        let battery = BatteryReader::new()
+            .capacity_unit(CapacityUnit::MilliWattHours)
+            .read(&mock_path)
+            .unwrap();
+        assert_eq!(battery.remaining_capacity.value(), 30000);
+        assert_eq!(battery.design_capacity.value(), 60000);
+        assert_eq!(battery.last_capacity.value(), 45000);
+        assert_eq!(battery.time_remaining.unwrap().as_secs() / 60, 179);
+
+        let battery = BatteryReader::new().read(&mock_path).unwrap();
+        assert_eq!(battery.capacity_unit, CapacityUnit::MilliAmpHours);
+        assert_eq!(battery.remaining_capacity.value(), 2500);
+        assert_eq!(battery.design_capacity.value(), 5000);
+        assert_eq!(battery.last_capacity.value(), 3750);
+        assert!((battery.design_energy_wh() - 60.0).abs() < 0.01);
+        assert_eq!(battery.time_remaining.unwrap().as_secs() / 60, 179);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    struct ChargingSource {
+        reads: std::sync::atomic::AtomicU32,
+    }
+
+    impl acpi_client::AttributeSource for ChargingSource {
+        fn read(&self, path: &std::path::Path) -> std::io::Result<String> {
+            use std::sync::atomic::Ordering;
+
+            if path.ends_with("charge_now") {
+                // Charge by 10% of the full capacity on every reading
+                let reads = self.reads.fetch_add(1, Ordering::SeqCst);
+                Ok(format!("{}\n", 1000000 + reads * 200000))
+            } else {
+                acpi_client::FileSystemSource.read(path)
+            }
+        }
+
+        fn exists(&self, path: &std::path::Path) -> bool {
+            acpi_client::FileSystemSource.exists(path)
+        }
+    }
+
+    #[test]
+    fn wait_for_charging_battery() {
+        use std::io::Write;
+        use std::sync::atomic::AtomicU32;
+        use std::time::Duration;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "599000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Charging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let battery = acpi_client::BatteryReader::new().source(ChargingSource {
+            reads: AtomicU32::new(0),
+        });
+        let result = battery.wait_for_percentage(
+            &mock_path,
+            80.,
+            Duration::from_millis(1),
+            Duration::from_secs(10),
+        );
+        assert!(result.is_ok());
+
+        let result = acpi_client::wait_for_percentage(
+            &mock_path,
+            80.,
+            Duration::from_millis(1),
+            Duration::from_millis(5),
+        );
+        match result {
+            Err(acpi_client::utils::AcpiClientError::Io(error)) => {
+                assert_eq!(error.kind(), std::io::ErrorKind::TimedOut)
+            }
+            _ => panic!("Expected the wait to time out"),
+        }
+
+        // Durations too long to add to the current instant must not panic
+        let result =
+            acpi_client::wait_for_percentage(&mock_path, 40., Duration::MAX, Duration::MAX);
+        assert!(result.is_ok());
+
+        let result = acpi_client::wait_for_percentage(
+            &mock_path,
+            80.,
+            Duration::MAX,
+            Duration::from_millis(5),
+        );
+        match result {
+            Err(acpi_client::utils::AcpiClientError::Io(error)) => {
+                assert_eq!(error.kind(), std::io::ErrorKind::TimedOut)
+            }
+            _ => panic!("Expected the wait to time out"),
+        }
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn energy_now_above_energy_full() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("energy_full")).unwrap();
+        writeln!(file, "45000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("energy_full_design")).unwrap();
+        writeln!(file, "57000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("energy_now")).unwrap();
+        writeln!(file, "46000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("power_now")).unwrap();
+        writeln!(file, "1500000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Charging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "11400000").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert!(battery.remaining_capacity.value() > battery.last_capacity.value());
+        assert_eq!(battery.time_remaining, Some(std::time::Duration::new(0, 0)));
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn charge_now_above_charge_full() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "2100000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "100000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Charging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.time_remaining, Some(std::time::Duration::new(0, 0)));
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn charging_with_oversized_charge_full() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "99999999999999").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "100000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Charging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path);
+        assert!(battery.is_ok());
+        assert!(battery.unwrap().time_remaining.is_some());
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn discharging_with_oversized_charge_now() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "99999999999999").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "100000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path);
+        assert!(battery.is_ok());
+        assert!(battery.unwrap().time_remaining.is_some());
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn prefer_averaged_rate() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_avg")).unwrap();
+        writeln!(file, "500000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "950000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.present_rate, 500);
+
+        let battery = acpi_client::BatteryReader::new()
+            .averaged_rate(false)
+            .read(&mock_path)
+            .unwrap();
+        assert_eq!(battery.present_rate, 950);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn percentage_of_design_for_worn_battery() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "4000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "599000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert!((battery.percentage - 50.).abs() < 0.01);
+        assert!((battery.percentage_of_design() - 25.).abs() < 0.01);
+
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "4200000").unwrap();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert!((battery.percentage_of_design() - 100.).abs() < 0.01);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn parse_not_charging_variations() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1600000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        for status in &[
+            "Not charging",
+            "Not_Charging",
+            "NOT CHARGING",
+            "not   charging",
+        ] {
+            let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+            writeln!(file, "{}", status).unwrap();
+
+            let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+            assert_eq!(battery.state, acpi_client::ChargingState::NotCharging);
+            assert_eq!(battery.to_acpi_line(0), "Battery 0: Not charging, 80%");
+        }
+
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Sideways").unwrap();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.state, acpi_client::ChargingState::Unknown);
+        assert_eq!(battery.raw_status, "Sideways");
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn infer_unknown_state_from_rate_sign() {
+        use acpi_client::ChargingState;
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "-599000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Unknown").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.state, ChargingState::Discharging);
+        assert_eq!(battery.present_rate, 599);
+
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "599000").unwrap();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.state, ChargingState::Charging);
+
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "0").unwrap();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.state, ChargingState::Unknown);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn remaining_energy_for_each_reporting_type() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "599000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15000000").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert!((battery.remaining_energy_wh() - 15.0).abs() < 0.01);
+
+        let mock_path = dir.path().join("BAT1");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("energy_full")).unwrap();
+        writeln!(file, "45000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("energy_full_design")).unwrap();
+        writeln!(file, "57000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("energy_now")).unwrap();
+        writeln!(file, "30500000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("power_now")).unwrap();
+        writeln!(file, "9500000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "11400000").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert!((battery.remaining_energy_wh() - 30.5).abs() < 0.01);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn no_batteries_versus_failed_batteries() {
+        use acpi_client::utils::AcpiClientError;
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let (batteries, errors) = acpi_client::get_battery_info_with_errors(dir.path()).unwrap();
+        assert!(batteries.is_empty());
+        assert!(errors.is_empty());
+        assert!(acpi_client::get_battery_info(dir.path())
+            .unwrap()
+            .is_empty());
+
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Battery").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+
+        let (batteries, errors) = acpi_client::get_battery_info_with_errors(dir.path()).unwrap();
+        assert!(batteries.is_empty());
+        assert_eq!(errors.len(), 1);
+        assert_eq!(errors[0].0, "BAT0");
+        assert!(matches!(errors[0].1, AcpiClientError::InvalidInput(_)));
+        // The failing battery is skipped rather than failing the scan
+        assert!(acpi_client::get_battery_info(dir.path())
+            .unwrap()
+            .is_empty());
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn prefer_averaged_voltage() {
+        use acpi_client::{BatteryReader, CapacityUnit};
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("energy_full")).unwrap();
+        writeln!(file, "45000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("energy_full_design")).unwrap();
+        writeln!(file, "60000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("energy_now")).unwrap();
+        writeln!(file, "30000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("power_now")).unwrap();
+        writeln!(file, "10000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "11000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_avg")).unwrap();
+        writeln!(file, "12000000").unwrap();
+
+        let battery = BatteryReader::new()
+            .capacity_unit(CapacityUnit::MilliAmpHours)
+            .read(&mock_path)
+            .unwrap();
+        assert_eq!(battery.voltage, 12000);
+        assert_eq!(battery.remaining_capacity.value(), 2500);
+
+        // An unreadable average falls back to the instantaneous voltage
+        let mut file = std::fs::File::create(mock_path.join("voltage_avg")).unwrap();
+        writeln!(file, "n/a").unwrap();
+        let battery = BatteryReader::new().read(&mock_path).unwrap();
+        assert_eq!(battery.voltage, 11000);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn detect_stuck_gauge() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "599000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        let mut detector = acpi_client::StuckDetector::new(3);
+        assert!(!detector.update(&battery));
+        assert!(!detector.update(&battery));
+        assert!(!detector.update(&battery));
+        assert!(detector.update(&battery));
+
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "990000").unwrap();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert!(!detector.update(&battery));
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn flatten_battery_to_map() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "599000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Charging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        let map = battery.as_map();
+        assert_eq!(map["name"], "BAT0");
+        assert_eq!(map["percentage"], "50");
+        assert_eq!(map["state"], "Charging");
+        assert_eq!(map["time_remaining"], "1:40:00");
+        assert_eq!(map["remaining_capacity"], "1000");
+        assert_eq!(map["model_name"], "");
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn skip_broken_symlink() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "599000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Battery").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+        std::os::unix::fs::symlink(
+            "../../devices/platform/missing/BAT1",
+            dir.path().join("BAT1"),
+        )
+        .unwrap();
+
+        let batteries = acpi_client::get_battery_info(dir.path()).unwrap();
+        assert_eq!(batteries.len(), 1);
+        assert_eq!(batteries[0].name, "BAT0");
+        let adapters = acpi_client::get_ac_adapter_info(dir.path()).unwrap();
+        assert!(adapters.is_empty());
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn charge_inhibited_by_threshold() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1600000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Not charging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+        let mut file =
+            std::fs::File::create(mock_path.join("charge_control_end_threshold")).unwrap();
+        writeln!(file, "80").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert!(battery.is_charge_inhibited());
+
+        let mut file =
+            std::fs::File::create(mock_path.join("charge_control_end_threshold")).unwrap();
+        writeln!(file, "90").unwrap();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert!(!battery.is_charge_inhibited());
+
+        // Between the start and stop thresholds charging stays held, as in the high level state
+        let mut file =
+            std::fs::File::create(mock_path.join("charge_control_start_threshold")).unwrap();
+        writeln!(file, "75").unwrap();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert!(battery.is_charge_inhibited());
+        assert_eq!(
+            battery.high_level_state(),
+            acpi_client::BatteryState::HeldByThreshold
+        );
+
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert!(!battery.is_charge_inhibited());
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn model_falls_back_to_device_dir() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.model_name, None);
+        assert_eq!(battery.manufacturer, None);
+
+        std::fs::create_dir(mock_path.join("device")).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("device").join("model")).unwrap();
+        writeln!(file, "Back-UPS ES 700").unwrap();
+        let mut file =
+            std::fs::File::create(mock_path.join("device").join("manufacturer")).unwrap();
+        writeln!(file, "American Power Conversion").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.model_name, Some(String::from("Back-UPS ES 700")));
+        assert_eq!(
+            battery.manufacturer,
+            Some(String::from("American Power Conversion"))
+        );
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn hold_percentage_within_hysteresis() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut previous = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert!((previous.percentage - 50.).abs() < 0.01);
+
+        // Readings jittering by a point either side are held at the previous percentage
+        for charge_now in &["1020000", "980000", "1010000"] {
+            let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+            writeln!(file, "{}", charge_now).unwrap();
+            previous = acpi_client::BatteryReader::new()
+                .previous(Some(&previous))
+                .percentage_hysteresis(1.5)
+                .read(&mock_path)
+                .unwrap();
+            assert!((previous.percentage - 50.).abs() < 0.01);
+        }
+
+        // A larger change is reported
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "960000").unwrap();
+        let battery = acpi_client::BatteryReader::new()
+            .previous(Some(&previous))
+            .percentage_hysteresis(1.5)
+            .read(&mock_path)
+            .unwrap();
+        assert!((battery.percentage - 48.).abs() < 0.01);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn parse_capacity_error_margin() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.capacity_error_margin, None);
+
+        let mut file = std::fs::File::create(mock_path.join("capacity_error_margin")).unwrap();
+        writeln!(file, "3").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.capacity_error_margin, Some(3));
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn try_from_path() {
+        use std::convert::TryFrom;
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let battery = acpi_client::BatteryInfo::try_from(mock_path.as_path()).unwrap();
+        assert_eq!(battery.name, "BAT0");
+        let battery = acpi_client::BatteryInfo::try_from(mock_path.clone()).unwrap();
+        assert_eq!(battery.remaining_capacity.value(), 1000);
+        assert!(acpi_client::BatteryInfo::try_from(dir.path().join("BAT1")).is_err());
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn parse_constant_charge_voltage() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Charging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "4100000").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.constant_charge_voltage, None);
+        assert_eq!(battery.constant_charge_voltage_max, None);
+
+        let mut file = std::fs::File::create(mock_path.join("constant_charge_voltage")).unwrap();
+        writeln!(file, "4200000").unwrap();
+        let mut file =
+            std::fs::File::create(mock_path.join("constant_charge_voltage_max")).unwrap();
+        writeln!(file, "4350000").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.constant_charge_voltage, Some(4200));
+        assert_eq!(battery.constant_charge_voltage_max, Some(4350));
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn time_to_custom_percentage() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .current_now(499999)
+            .status("Charging")
+            .voltage_now(15045000)
+            .build();
+
+        // 800 mAh to reach 90% at 500 mA
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        let time = battery.time_to_percentage(90.).unwrap();
+        assert_eq!(time.as_secs(), 5760);
+        assert!(battery.time_to_percentage(40.).is_none());
+
+        // 600 mAh to fall to 20% at 500 mA
+        sysfs.battery("BAT0").status("Discharging").build();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        let time = battery.time_to_percentage(20.).unwrap();
+        assert_eq!(time.as_secs(), 4320);
+        assert!(battery.time_to_percentage(60.).is_none());
+
+        sysfs.battery("BAT0").status("Full").build();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert!(battery.time_to_percentage(90.).is_none());
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn design_energy_for_spec_comparison() {
+        let sysfs = MockSysfs::new();
+        let charge_path = sysfs
+            .battery("BAT0")
+            .charge_full(4000000)
+            .charge_full_design(5000000)
+            .charge_now(2000000)
+            .status("Discharging")
+            .voltage_now(12600000)
+            .attribute("voltage_min_design", 11400000)
+            .build();
+        let energy_path = sysfs
+            .battery("BAT1")
+            .energy_full(50000000)
+            .energy_full_design(57000000)
+            .energy_now(25000000)
+            .status("Discharging")
+            .voltage_now(12600000)
+            .build();
+
+        // 5000 mAh at the nominal 11.4 V rather than the present 12.6 V
+        let battery = acpi_client::BatteryInfo::new(&charge_path).unwrap();
+        assert_eq!(battery.design_voltage, Some(11400));
+        assert!((battery.design_energy_wh() - 57.).abs() < 0.01);
+
+        let battery = acpi_client::BatteryInfo::new(&energy_path).unwrap();
+        assert_eq!(battery.design_voltage, None);
+        assert!((battery.design_energy_wh() - 57.).abs() < 0.01);
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn full_capacity_overrides_stale_status() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(2000000)
+            .status("Charging")
+            .voltage_now(15045000)
+            .attribute("capacity", 100)
+            .build();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.state, acpi_client::ChargingState::Charging);
+
+        let reader = acpi_client::BatteryReader::new().full_at_capacity(true);
+        let battery = reader.read(&mock_path).unwrap();
+        assert_eq!(battery.state, acpi_client::ChargingState::Full);
+
+        sysfs.battery("BAT0").attribute("capacity", 99).build();
+        let battery = reader.read(&mock_path).unwrap();
+        assert_eq!(battery.state, acpi_client::ChargingState::Charging);
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn flag_uevent_name_mismatch() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.uevent_name, None);
+        assert!(!battery.has_name_mismatch());
+
+        sysfs
+            .battery("BAT0")
+            .attribute("uevent", "POWER_SUPPLY_NAME=BAT0")
+            .build();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.uevent_name, Some(String::from("BAT0")));
+        assert!(!battery.has_name_mismatch());
+
+        sysfs
+            .battery("BAT0")
+            .attribute("uevent", "POWER_SUPPLY_NAME=BAT1")
+            .build();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.uevent_name, Some(String::from("BAT1")));
+        assert!(battery.has_name_mismatch());
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn parse_charging_state_from_borrowed_str() {
+        use acpi_client::ChargingState;
+
+        let status = String::from("Discharging\n");
+        assert_eq!(
+            acpi_client::parse_charging_state(&status).unwrap(),
+            ChargingState::Discharging
+        );
+        assert_eq!(
+            acpi_client::parse_charging_state("Not charging").unwrap(),
+            ChargingState::NotCharging
+        );
+        assert_eq!(
+            acpi_client::parse_charging_state("  FULL ").unwrap(),
+            ChargingState::Full
+        );
+        assert_eq!(
+            acpi_client::parse_charging_state(&status[3..]).unwrap(),
+            ChargingState::Charging
+        );
+        assert!(acpi_client::parse_charging_state("Discharg").is_err());
+    }
+
+    #[test]
+    fn energy_per_percent() {
+        let sysfs = MockSysfs::new();
+        let energy_path = sysfs
+            .battery("BAT0")
+            .energy_full(50000000)
+            .energy_full_design(57000000)
+            .energy_now(25000000)
+            .status("Discharging")
+            .voltage_now(12600000)
+            .build();
+        let charge_path = sysfs
+            .battery("BAT1")
+            .charge_full(4000000)
+            .charge_full_design(5000000)
+            .charge_now(2000000)
+            .status("Discharging")
+            .voltage_now(12500000)
+            .build();
+
+        let battery = acpi_client::BatteryInfo::new(&energy_path).unwrap();
+        assert!((battery.wh_per_percent() - 0.5).abs() < 0.001);
+        let battery = acpi_client::BatteryInfo::new(&charge_path).unwrap();
+        assert!((battery.wh_per_percent() - 0.5).abs() < 0.001);
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn format_time_remaining_for_people() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+
+        // The rate is one less than the divisor, since the time is computed against rate + 1
+        for (current_now, expected) in &[
+            ("443443", "2 hr 15 min"),
+            ("499999", "2 hr"),
+            ("999999", "1 hr"),
+            ("1332000", "45 min"),
+            ("59999999", "1 min"),
+            ("99999999", "less than a minute"),
+        ] {
+            sysfs
+                .battery("BAT0")
+                .attribute("current_now", current_now)
+                .build();
+            let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+            assert_eq!(
+                battery.time_remaining_human(),
+                Some(String::from(*expected)),
+                "current_now {}",
+                current_now
+            );
+        }
+
+        sysfs.battery("BAT0").status("Full").build();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.time_remaining_human(), None);
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn typed_capacity_accessors() {
+        use acpi_client::{Capacity, CapacityUnit, MilliAmpHours, MilliWattHours};
+
+        let sysfs = MockSysfs::new();
+        let charge_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Discharging")
+            .voltage_now(15000000)
+            .build();
+        let energy_path = sysfs
+            .battery("BAT1")
+            .energy_full(30000000)
+            .energy_full_design(42000000)
+            .energy_now(15000000)
+            .status("Discharging")
+            .voltage_now(15000000)
+            .build();
+
+        for path in &[&charge_path, &energy_path] {
+            let battery = acpi_client::BatteryInfo::new(path).unwrap();
+            assert_eq!(battery.remaining_charge(), MilliAmpHours(1000));
+            assert_eq!(battery.remaining_energy_mwh(), MilliWattHours(15000));
+            assert_eq!(battery.last_charge(), MilliAmpHours(2000));
+            assert_eq!(battery.last_energy_mwh(), MilliWattHours(30000));
+            assert_eq!(battery.design_charge(), MilliAmpHours(2800));
+            assert_eq!(battery.design_energy_mwh(), MilliWattHours(42000));
+        }
+
+        // The capacities carry the units the reader was configured with
+        let battery = acpi_client::BatteryInfo::new(&energy_path).unwrap();
+        assert_eq!(
+            battery.remaining_capacity,
+            Capacity::Charge(MilliAmpHours(1000))
+        );
+        assert_eq!(battery.last_capacity_raw(), 2000);
+        let battery = acpi_client::BatteryReader::new()
+            .capacity_unit(CapacityUnit::MilliWattHours)
+            .read(&charge_path)
+            .unwrap();
+        assert_eq!(
+            battery.design_capacity,
+            Capacity::Energy(MilliWattHours(42000))
+        );
+        assert_eq!(battery.remaining_capacity_raw(), 15000);
+        assert_eq!(battery.design_capacity_raw(), 42000);
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn parse_charge_type() {
+        use acpi_client::ChargeType;
+
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Charging")
+            .voltage_now(15045000)
+            .build();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.charge_type, None);
+
+        for (value, expected) in &[
+            ("Fast", Some(ChargeType::Fast)),
+            ("Standard", Some(ChargeType::Standard)),
+            ("Trickle", Some(ChargeType::Trickle)),
+            (
+                "Adaptive",
+                Some(ChargeType::Other(String::from("Adaptive"))),
+            ),
+            ("N/A", None),
+        ] {
+            sysfs
+                .battery("BAT0")
+                .attribute("charge_type", value)
+                .build();
+            let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+            assert_eq!(battery.charge_type, *expected);
+        }
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn select_primary_battery() {
+        let sysfs = MockSysfs::new();
+        sysfs.adapter("ACAD").online(true).build();
+        assert!(acpi_client::primary_battery(sysfs.path())
+            .unwrap()
+            .is_none());
+
+        sysfs
+            .battery("BAT1")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+        let battery = acpi_client::primary_battery(sysfs.path()).unwrap().unwrap();
+        assert_eq!(battery.name, "BAT1");
+
+        sysfs
+            .battery("BAT0")
+            .charge_full(4000000)
+            .charge_full_design(4000000)
+            .charge_now(3000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+        let battery = acpi_client::primary_battery(sysfs.path()).unwrap().unwrap();
+        assert_eq!(battery.name, "BAT0");
+
+        // A peripheral's battery is never the primary one
+        sysfs.battery("BAT0").attribute("scope", "Device").build();
+        let battery = acpi_client::primary_battery(sysfs.path()).unwrap().unwrap();
+        assert_eq!(battery.name, "BAT1");
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn skip_device_without_type() {
+        let sysfs = MockSysfs::new();
+        sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+        sysfs.adapter("ACAD").online(true).build();
+        sysfs
+            .device("hidpp_battery_0")
+            .attribute("status", "Discharging")
+            .build();
+
+        let batteries = acpi_client::get_battery_info(sysfs.path()).unwrap();
+        assert_eq!(batteries.len(), 1);
+        assert_eq!(batteries[0].name, "BAT0");
+        let adapters = acpi_client::get_ac_adapter_info(sysfs.path()).unwrap();
+        assert_eq!(adapters.len(), 1);
+        let supplies = acpi_client::get_power_supply_info(sysfs.path()).unwrap();
+        assert_eq!(supplies.len(), 2);
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn get_batteries_matching_pattern() {
+        let sysfs = MockSysfs::new();
+        for name in &["BAT0", "BAT1", "BAT10", "CMB0"] {
+            sysfs
+                .battery(name)
+                .charge_full(2000000)
+                .charge_full_design(2800000)
+                .charge_now(1000000)
+                .status("Discharging")
+                .voltage_now(15045000)
+                .build();
+        }
+
+        let names = |pattern: &str| {
+            let mut names: Vec<String> = acpi_client::get_batteries_matching(sysfs.path(), pattern)
+                .unwrap()
+                .into_iter()
+                .map(|battery| battery.name)
+                .collect();
+            names.sort();
+            names
+        };
+        assert_eq!(names("BAT*"), vec!["BAT0", "BAT1", "BAT10"]);
+        assert_eq!(names("BAT?"), vec!["BAT0", "BAT1"]);
+        assert_eq!(names("CMB0"), vec!["CMB0"]);
+        assert!(names("BAT2").is_empty());
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn write_alarm() {
+        let sysfs = MockSysfs::new();
+        let energy_path = sysfs
+            .battery("BAT0")
+            .energy_full(50000000)
+            .energy_full_design(57000000)
+            .energy_now(25000000)
+            .status("Discharging")
+            .voltage_now(12500000)
+            .attribute("alarm", 0)
+            .build();
+        let charge_path = sysfs
+            .battery("BAT1")
+            .charge_full(4000000)
+            .charge_full_design(5000000)
+            .charge_now(2000000)
+            .status("Discharging")
+            .voltage_now(12500000)
+            .build();
+
+        let battery = acpi_client::BatteryInfo::new(&energy_path).unwrap();
+        battery.set_alarm(2500).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(energy_path.join("alarm")).unwrap(),
+            "2500000"
+        );
+
+        // A battery without an alarm attribute doesn't get one created
+        let battery = acpi_client::BatteryInfo::new(&charge_path).unwrap();
+        match battery.set_alarm(2500) {
+            Err(acpi_client::utils::AcpiClientError::Io(err)) => {
+                assert_eq!(err.kind(), std::io::ErrorKind::NotFound)
+            }
+            _ => panic!("expected a not found error"),
+        }
+        assert!(!charge_path.join("alarm").exists());
+
+        // Batteries reporting charge are written in µAh
+        sysfs.battery("BAT1").attribute("alarm", 0).build();
+        battery.set_alarm(2500).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(charge_path.join("alarm")).unwrap(),
+            "200000"
+        );
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn write_charge_thresholds() {
+        let sysfs = MockSysfs::new();
+        let new_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .attribute("charge_control_start_threshold", 40)
+            .attribute("charge_control_end_threshold", 60)
+            .build();
+        let old_path = sysfs
+            .battery("BAT1")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .attribute("charge_start_threshold", 40)
+            .attribute("charge_stop_threshold", 60)
+            .build();
+        let read =
+            |path: &std::path::Path, name: &str| std::fs::read_to_string(path.join(name)).unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&new_path).unwrap();
+        battery.set_charge_thresholds(Some(75), 80).unwrap();
+        assert_eq!(read(&new_path, "charge_control_start_threshold"), "75");
+        assert_eq!(read(&new_path, "charge_control_end_threshold"), "80");
+
+        let battery = acpi_client::BatteryInfo::new(&old_path).unwrap();
+        battery.set_charge_thresholds(None, 90).unwrap();
+        assert_eq!(read(&old_path, "charge_start_threshold"), "40\n");
+        assert_eq!(read(&old_path, "charge_stop_threshold"), "90");
+
+        assert!(battery.set_charge_thresholds(Some(50), 101).is_err());
+        assert!(battery.set_charge_thresholds(Some(80), 80).is_err());
+        assert_eq!(read(&old_path, "charge_stop_threshold"), "90");
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn record_state_transitions() {
+        use acpi_client::ChargingState;
+
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .voltage_now(15045000)
+            .build();
+
+        let mut history = acpi_client::StateHistory::new(8);
+        for status in &["Charging", "Charging", "Full", "Full", "Discharging"] {
+            sysfs.battery("BAT0").status(status).build();
+            history.update(&acpi_client::BatteryInfo::new(&mock_path).unwrap());
+        }
+        let states: Vec<ChargingState> = history.transitions().map(|(_, state)| *state).collect();
+        assert_eq!(
+            states,
+            vec![
+                ChargingState::Charging,
+                ChargingState::Full,
+                ChargingState::Discharging
+            ]
+        );
+        let times: Vec<std::time::SystemTime> =
+            history.transitions().map(|(time, _)| *time).collect();
+        assert!(times.windows(2).all(|pair| pair[0] <= pair[1]));
+
+        // Only the most recent transitions are kept
+        let mut history = acpi_client::StateHistory::new(2);
+        for status in &["Charging", "Full", "Discharging"] {
+            sysfs.battery("BAT0").status(status).build();
+            history.update(&acpi_client::BatteryInfo::new(&mock_path).unwrap());
+        }
+        let states: Vec<ChargingState> = history.transitions().map(|(_, state)| *state).collect();
+        assert_eq!(
+            states,
+            vec![ChargingState::Full, ChargingState::Discharging]
+        );
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn parse_open_circuit_voltage() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Discharging")
+            .voltage_now(3650000)
+            .build();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.voltage_ocv, None);
+
+        sysfs
+            .battery("BAT0")
+            .attribute("voltage_ocv", 3812000)
+            .build();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.voltage_ocv, Some(3812));
+        assert_eq!(battery.voltage, 3650);
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn infer_full_from_capacity_level() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(2000000)
+            .voltage_now(15045000)
+            .attribute("capacity_level", "Full")
+            .build();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.state, acpi_client::ChargingState::Full);
+        assert_eq!(battery.time_remaining, Some(std::time::Duration::new(0, 0)));
+
+        sysfs
+            .battery("BAT0")
+            .attribute("capacity_level", "Normal")
+            .build();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.state, acpi_client::ChargingState::Unknown);
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn min_design_capacity_filters_phantom_batteries() {
+        let sysfs = MockSysfs::new();
+        sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .current_now(500000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+        sysfs
+            .battery("hid-mouse-battery")
+            .charge_full(50000)
+            .charge_full_design(50000)
+            .charge_now(40000)
+            .status("Discharging")
+            .voltage_now(3700000)
+            .build();
+
+        let batteries = acpi_client::get_battery_info(sysfs.path()).unwrap();
+        assert_eq!(batteries.len(), 2);
+
+        let batteries = acpi_client::BatteryReader::new()
+            .min_design_capacity_mah(100)
+            .read_all(sysfs.path())
+            .unwrap();
+        assert_eq!(batteries.len(), 1);
+        assert_eq!(batteries[0].name, "BAT0");
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn min_design_capacity_keeps_energy_battery_without_voltage() {
+        let sysfs = MockSysfs::new();
+        sysfs
+            .battery("BAT0")
+            .energy_full(50000000)
+            .energy_full_design(57000000)
+            .energy_now(25000000)
+            .power_now(10000000)
+            .status("Discharging")
+            .voltage_now(0)
+            .build();
+
+        let batteries = acpi_client::BatteryReader::new()
+            .min_design_capacity_mah(100)
+            .read_all(sysfs.path())
+            .unwrap();
+        assert_eq!(batteries.len(), 1);
+        assert_eq!(batteries[0].name, "BAT0");
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn label_omits_missing_fields() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.label(), "BAT0");
+
+        sysfs
+            .battery("BAT0")
+            .attribute("model_name", "45N1773")
+            .build();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.label(), "45N1773 (BAT0)");
+
+        sysfs
+            .battery("BAT0")
+            .attribute("manufacturer", "SANYO")
+            .build();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.label(), "SANYO 45N1773 (BAT0)");
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn detect_swapped_battery_by_serial_number() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .attribute("serial_number", "12345")
+            .attribute("manufacture_year", "2019")
+            .attribute("manufacture_month", "4")
+            .attribute("manufacture_day", "17")
+            .build();
+
+        let first = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(first.serial_number, Some(String::from("12345")));
+        assert_eq!(first.manufacture_date, Some(String::from("2019-04-17")));
+        let second = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert!(first.is_same_physical_battery(&second));
+
+        sysfs
+            .battery("BAT0")
+            .attribute("serial_number", "67890")
+            .build();
+        let swapped = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert!(!first.is_same_physical_battery(&swapped));
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn c_rate_relative_to_full_capacity() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .current_now(1000000)
+            .status("Charging")
+            .voltage_now(15045000)
+            .build();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.c_rate(), Some(0.5));
+
+        sysfs.battery("BAT0").charge_full(0).build();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.c_rate(), None);
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn zero_rate_while_charging_has_no_time_remaining() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .current_now(0)
+            .status("Charging")
+            .voltage_now(15045000)
+            .build();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.state, acpi_client::ChargingState::Charging);
+        assert_eq!(battery.time_remaining, None);
+        assert!(battery.time_to_percentage(90.).is_none());
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn read_temperature_from_hwmon() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.temperature, None);
+
+        sysfs
+            .battery("BAT0")
+            .attribute("hwmon1/name", "BAT0")
+            .attribute("hwmon2/temp1_input", "31500")
+            .build();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.temperature, Some(31.5));
+
+        sysfs.battery("BAT0").attribute("temp", "298").build();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.temperature, Some(29.8));
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn fraction_of_full_charge() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1500000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.percentage, 75.);
+        assert_eq!(battery.fraction(), 0.75);
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn time_since_last_full_charge() {
+        use std::time::Duration;
+
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1900000)
+            .current_now(500000)
+            .status("Charging")
+            .voltage_now(15045000)
+            .build();
+
+        let mut tracker = acpi_client::LastFullTracker::new();
+        let start = acpi_client::BatteryInfo::new(&mock_path)
+            .unwrap()
+            .sampled_at;
+        let reading = |charge_now: u32, status: &str, elapsed: u64| {
+            sysfs
+                .battery("BAT0")
+                .charge_now(charge_now)
+                .status(status)
+                .build();
+            let mut battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+            battery.sampled_at = start + Duration::from_secs(elapsed);
+            battery
+        };
+
+        tracker.update(&reading(1900000, "Charging", 0));
+        assert_eq!(tracker.time_since_full(), None);
+        tracker.update(&reading(2000000, "Full", 600));
+        assert_eq!(tracker.time_since_full(), Some(Duration::from_secs(0)));
+        tracker.update(&reading(1800000, "Discharging", 1500));
+        tracker.update(&reading(1700000, "Discharging", 2400));
+        assert_eq!(tracker.time_since_full(), Some(Duration::from_secs(1800)));
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn read_battery_from_root_and_name() {
+        let sysfs = MockSysfs::new();
+        sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+
+        let battery = acpi_client::BatteryInfo::from_root_and_name(sysfs.path(), "BAT0").unwrap();
+        assert_eq!(battery.name, "BAT0");
+        assert_eq!(battery.percentage, 50.);
+
+        match acpi_client::BatteryInfo::from_root_and_name(sysfs.path(), "BAT1") {
+            Err(acpi_client::utils::AcpiClientError::Io(err)) => {
+                assert_eq!(err.kind(), std::io::ErrorKind::NotFound);
+                assert!(err.to_string().contains("BAT1"));
+            }
+            _ => panic!("expected a not found error"),
+        }
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn reconcile_charge_and_energy_with_capacity() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .energy_full(30000000)
+            .energy_full_design(42000000)
+            .energy_now(18000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+
+        // Without a capacity attribute to cross-check against, energy is preferred
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.percentage, 60.);
+        assert!(battery.remaining_energy.is_some());
+
+        sysfs.battery("BAT0").attribute("capacity", "50").build();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.percentage, 50.);
+        assert!(battery.remaining_energy.is_none());
+
+        sysfs.battery("BAT0").attribute("capacity", "61").build();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.percentage, 60.);
+
+        sysfs.close();
+    }
+
+    struct UnpluggedSource {
+        removed: std::path::PathBuf,
+    }
+
+    impl acpi_client::AttributeSource for UnpluggedSource {
+        fn read(&self, path: &std::path::Path) -> std::io::Result<String> {
+            if path.starts_with(&self.removed) {
+                Err(std::io::Error::new(
+                    std::io::ErrorKind::NotFound,
+                    "No such file or directory",
+                ))
+            } else {
+                acpi_client::FileSystemSource.read(path)
+            }
+        }
+
+        fn exists(&self, path: &std::path::Path) -> bool {
+            !path.starts_with(&self.removed) && acpi_client::FileSystemSource.exists(path)
+        }
+    }
+
+    #[test]
+    fn skip_battery_removed_during_scan() {
+        let sysfs = MockSysfs::new();
+        for name in &["BAT0", "BAT1"] {
+            sysfs
+                .battery(name)
+                .charge_full(2000000)
+                .charge_full_design(2800000)
+                .charge_now(1000000)
+                .status("Discharging")
+                .voltage_now(15045000)
+                .build();
+        }
+
+        let reader = acpi_client::BatteryReader::new().source(UnpluggedSource {
+            removed: sysfs.path().join("BAT1"),
+        });
+        let batteries = reader.read_all(sysfs.path()).unwrap();
+        assert_eq!(batteries.len(), 1);
+        assert_eq!(batteries[0].name, "BAT0");
+
+        let reader = acpi_client::BatteryReader::new().source(UnpluggedSource {
+            removed: sysfs.path().to_path_buf(),
+        });
+        assert!(reader.read_all(sysfs.path()).unwrap().is_empty());
+
+        sysfs.close();
+    }
+
+    struct VirtualHwmonSource {
+        battery: std::path::PathBuf,
+    }
+
+    impl acpi_client::AttributeSource for VirtualHwmonSource {
+        fn read(&self, path: &std::path::Path) -> std::io::Result<String> {
+            if path == self.battery.join("hwmon0/temp1_input") {
+                Ok(String::from("29000\n"))
+            } else {
+                acpi_client::FileSystemSource.read(path)
+            }
+        }
+
+        fn exists(&self, path: &std::path::Path) -> bool {
+            path == self.battery.join("hwmon0/temp1_input")
+                || acpi_client::FileSystemSource.exists(path)
+        }
+
+        fn list(&self, path: &std::path::Path) -> std::io::Result<Vec<String>> {
+            let mut names = acpi_client::FileSystemSource.list(path)?;
+            if path == self.battery {
+                names.insert(0, String::from("hwmon0"));
+            }
+            Ok(names)
+        }
+    }
+
+    #[test]
+    fn hwmon_temperature_read_through_source() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.temperature, None);
+
+        let battery = acpi_client::BatteryReader::new()
+            .source(VirtualHwmonSource {
+                battery: mock_path.clone(),
+            })
+            .read(&mock_path)
+            .unwrap();
+        assert_eq!(battery.temperature, Some(29.));
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn scan_batteries_from_source() {
+        use crate::common::MemorySource;
+
+        let root = std::path::Path::new("/nonexistent/power_supply");
+        let source = MemorySource::default()
+            .attribute(root.join("AC/type"), "Mains")
+            .attribute(root.join("AC/online"), 1)
+            .attribute(root.join("BAT0/type"), "Battery")
+            .attribute(root.join("BAT0/status"), "Discharging")
+            .attribute(root.join("BAT0/charge_now"), 1000000)
+            .attribute(root.join("BAT0/charge_full"), 2000000)
+            .attribute(root.join("BAT0/charge_full_design"), 2800000)
+            .attribute(root.join("BAT0/current_now"), 500000)
+            .attribute(root.join("BAT0/voltage_now"), 15045000);
+
+        let batteries = acpi_client::BatteryReader::new()
+            .source(source)
+            .read_all(root)
+            .unwrap();
+        assert_eq!(batteries.len(), 1);
+        assert_eq!(batteries[0].name, "BAT0");
+        assert_eq!(batteries[0].percentage, 50.);
+    }
+
+    #[test]
+    fn read_rated_maximums() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.current_max, None);
+        assert_eq!(battery.voltage_max, None);
+
+        sysfs
+            .battery("BAT0")
+            .attribute("current_max", "3000000")
+            .attribute("voltage_max", "17400000")
+            .build();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.current_max, Some(3000));
+        assert_eq!(battery.voltage_max, Some(17400));
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn detect_implausible_voltage() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.voltage_is_plausible(), None);
+
+        sysfs
+            .battery("BAT0")
+            .attribute("voltage_min_design", "14400000")
+            .attribute("voltage_max_design", "16800000")
+            .build();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.voltage_is_plausible(), Some(true));
+
+        sysfs.battery("BAT0").voltage_now(65535000).build();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.voltage_is_plausible(), Some(false));
+
+        sysfs.battery("BAT0").voltage_now(1200000).build();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.voltage_is_plausible(), Some(false));
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn power_trend_over_window() {
+        use acpi_client::Trend;
+
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+
+        let mut tracker = acpi_client::PowerTrend::new(4, 100);
+        let mut update = |current_now: u32| {
+            sysfs.battery("BAT0").current_now(current_now).build();
+            let battery = acpi_client::BatteryReader::new()
+                .averaged_rate(false)
+                .read(&mock_path)
+                .unwrap();
+            tracker.update(&battery)
+        };
+
+        assert_eq!(update(500000), Trend::Steady);
+        assert_eq!(update(550000), Trend::Steady);
+        assert_eq!(update(900000), Trend::Increasing);
+        assert_eq!(update(1200000), Trend::Increasing);
+        assert_eq!(update(1200000), Trend::Increasing);
+        assert_eq!(update(1200000), Trend::Increasing);
+        assert_eq!(update(1200000), Trend::Steady);
+        assert_eq!(update(600000), Trend::Decreasing);
+        assert_eq!(update(500000), Trend::Decreasing);
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn derive_high_level_state() {
+        use acpi_client::BatteryState;
+
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1600000)
+            .voltage_now(15045000)
+            .build();
+        let state = |status: &str, charge_now: u32| {
+            sysfs
+                .battery("BAT0")
+                .status(status)
+                .charge_now(charge_now)
+                .build();
+            acpi_client::BatteryInfo::new(&mock_path)
+                .unwrap()
+                .high_level_state()
+        };
+
+        assert_eq!(state("Charging", 1600000), BatteryState::Charging);
+        assert_eq!(state("Discharging", 1600000), BatteryState::Discharging);
+        assert_eq!(state("Unknown", 1600000), BatteryState::Unknown);
+        assert_eq!(state("Full", 2000000), BatteryState::FullyCharged);
+        assert_eq!(state("Not charging", 2000000), BatteryState::FullyCharged);
+        // Firmware reporting full slightly short of 100% is trusted
+        assert_eq!(state("Full", 1940000), BatteryState::FullyCharged);
+        assert_eq!(state("Not charging", 1600000), BatteryState::Unknown);
+
+        sysfs
+            .battery("BAT0")
+            .attribute("charge_control_end_threshold", "80")
+            .build();
+        assert_eq!(state("Full", 1600000), BatteryState::HeldByThreshold);
+        assert_eq!(
+            state("Not charging", 1600000),
+            BatteryState::HeldByThreshold
+        );
+        assert_eq!(state("Not charging", 1000000), BatteryState::Unknown);
+
+        sysfs
+            .battery("BAT0")
+            .attribute("charge_control_start_threshold", "40")
+            .build();
+        assert_eq!(
+            state("Not charging", 1000000),
+            BatteryState::HeldByThreshold
+        );
+        assert_eq!(state("Not charging", 600000), BatteryState::Unknown);
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn stale_after_max_age() {
+        use std::time::Duration;
+
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+
+        let mut battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert!(!battery.is_stale(Duration::from_secs(60)));
+
+        battery.sampled_at -= Duration::from_secs(120);
+        assert!(battery.age() >= Duration::from_secs(120));
+        assert!(battery.is_stale(Duration::from_secs(60)));
+        assert!(!battery.is_stale(Duration::from_secs(600)));
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn percentage_sources_pick_in_order() {
+        use acpi_client::PercentageSource;
+
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .energy_full(30000000)
+            .energy_full_design(42000000)
+            .energy_now(18000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .attribute("capacity", "57")
+            .build();
+
+        let read = |sources: &[PercentageSource]| {
+            acpi_client::BatteryReader::new()
+                .percentage_sources(sources)
+                .read(&mock_path)
+                .unwrap()
+                .percentage
+        };
+        assert_eq!(read(&[PercentageSource::Charge]), 50.);
+        assert_eq!(read(&[PercentageSource::Energy]), 60.);
+        assert_eq!(read(&[PercentageSource::CapacityAttribute]), 57.);
+        assert_eq!(
+            read(&[
+                PercentageSource::CapacityAttribute,
+                PercentageSource::Charge
+            ]),
+            57.
+        );
+
+        // Sources the battery doesn't support are skipped
+        sysfs
+            .battery("BAT1")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1500000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+        let battery = acpi_client::BatteryReader::new()
+            .percentage_sources(&[PercentageSource::Energy, PercentageSource::Charge])
+            .read(&sysfs.path().join("BAT1"))
+            .unwrap();
+        assert_eq!(battery.percentage, 75.);
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn read_charger_currents() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Charging")
+            .voltage_now(15045000)
+            .build();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.precharge_current, None);
+        assert_eq!(battery.charge_term_current, None);
+
+        sysfs
+            .battery("BAT0")
+            .attribute("precharge_current", "128000")
+            .attribute("charge_term_current", "64000")
+            .build();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.precharge_current, Some(128));
+        assert_eq!(battery.charge_term_current, Some(64));
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn detect_empty_battery() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(20000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+
+        // 1% remaining while discharging
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert!(battery.is_empty());
+
+        sysfs.battery("BAT0").status("Charging").build();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert!(!battery.is_empty());
+
+        sysfs
+            .battery("BAT0")
+            .status("Unknown")
+            .charge_now(0)
+            .build();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert!(battery.is_empty());
+
+        sysfs
+            .battery("BAT0")
+            .status("Discharging")
+            .charge_now(1000000)
+            .build();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert!(!battery.is_empty());
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn preserve_raw_status() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.raw_status, "Discharging");
+        assert_eq!(battery.state, acpi_client::ChargingState::Discharging);
+
+        sysfs.battery("BAT0").status("Pending charge").build();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.raw_status, "Pending charge");
+        assert_eq!(battery.state, acpi_client::ChargingState::Unknown);
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn runtime_at_hypothetical_power() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .energy_full(50000000)
+            .energy_full_design(57000000)
+            .energy_now(40000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+
+        // 40 Wh at 20 W lasts two hours
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(
+            battery.runtime_at_power(20.),
+            Some(std::time::Duration::from_secs(7200))
+        );
+        assert_eq!(battery.runtime_at_power(0.), None);
+        assert_eq!(battery.runtime_at_power(f32::MIN_POSITIVE), None);
+
+        let mut battery = battery;
+        battery.remaining_energy = None;
+        battery.capacity_unit = acpi_client::CapacityUnit::MilliAmpHours;
+        battery.voltage = 0;
+        assert_eq!(battery.runtime_at_power(20.), None);
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn reconstruct_remaining_charge_from_capacity() {
+        let sysfs = MockSysfs::new();
+        let naive_path = sysfs
+            .battery("BAT0")
+            .energy_full(50000000)
+            .energy_full_design(57000000)
+            .energy_now(33333000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+        let reported_path = sysfs
+            .battery("BAT1")
+            .energy_full(50000000)
+            .energy_full_design(57000000)
+            .energy_now(33333000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .attribute("capacity", "67")
+            .build();
+
+        // Without a capacity attribute the charge is the energy divided by the voltage
+        let naive = acpi_client::BatteryInfo::new(&naive_path).unwrap();
+        assert_eq!(naive.last_capacity.value(), 3323);
+        assert_eq!(naive.remaining_capacity.value(), 2215);
+
+        let reported = acpi_client::BatteryInfo::new(&reported_path).unwrap();
+        assert_eq!(reported.last_capacity.value(), 3323);
+        assert_eq!(reported.remaining_capacity.value(), 2226);
+        assert_eq!(reported.remaining_energy, Some(33333));
+
+        // Energies are reported as read
+        let reported = acpi_client::BatteryReader::new()
+            .capacity_unit(acpi_client::CapacityUnit::MilliWattHours)
+            .read(&reported_path)
+            .unwrap();
+        assert_eq!(reported.remaining_capacity.value(), 33333);
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn estimate_cycle_life_used() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.cycle_count, None);
+        assert_eq!(battery.cycle_life_used(500), None);
+
+        sysfs
+            .battery("BAT0")
+            .attribute("cycle_count", "150")
+            .build();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.cycle_count, Some(150));
+        assert_eq!(battery.cycle_life_used(500), Some(30.));
+        assert_eq!(battery.cycle_life_used(100), Some(100.));
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn treat_zero_capacity_as_missing() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(0)
+            .charge_full_design(2800000)
+            .charge_now(1400000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.last_capacity.value(), 0);
+        assert_eq!(battery.percentage, 0.);
+
+        let reader = acpi_client::BatteryReader::new().zero_capacity_as_missing(true);
+        let battery = reader.read(&mock_path).unwrap();
+        assert_eq!(battery.last_capacity.value(), 2800);
+        assert_eq!(battery.percentage, 50.);
+
+        // The energy attributes are preferred over a zero full charge
+        sysfs
+            .battery("BAT0")
+            .energy_full(36000000)
+            .energy_full_design(42000000)
+            .energy_now(9000000)
+            .build();
+        let battery = reader.read(&mock_path).unwrap();
+        assert!(battery.remaining_energy.is_some());
+        assert_eq!(battery.percentage, 25.);
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn draw_percentage_sparkline() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(2000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+
+        let mut history = acpi_client::PercentageHistory::new(6);
+        assert_eq!(history.sparkline(), "");
+        for charge_now in &[2000000, 1700000, 1400000, 1000000, 600000, 300000, 0] {
+            sysfs.battery("BAT0").charge_now(*charge_now).build();
+            history.update(&acpi_client::BatteryInfo::new(&mock_path).unwrap());
+        }
+        // Only the six most recent readings are kept
+        assert_eq!(history.percentages(), vec![85., 70., 50., 30., 15., 0.]);
+        assert_eq!(history.sparkline(), "▇▆▅▃▂▁");
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn prefer_charge_counter() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.charge_counter, None);
+        assert_eq!(battery.remaining_capacity.value(), 1000);
+
+        sysfs
+            .battery("BAT0")
+            .attribute("charge_counter", "1234567")
+            .build();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.charge_counter, Some(1234567));
+        assert_eq!(battery.remaining_capacity.value(), 1234);
+
+        // Partial mAh are truncated, as for charge_now
+        sysfs
+            .battery("BAT0")
+            .attribute("charge_counter", "1234999")
+            .build();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.remaining_capacity.value(), 1234);
+
+        // A negative count isn't a remaining charge, so charge_now is used instead
+        sysfs
+            .battery("BAT0")
+            .attribute("charge_counter", "-52000")
+            .build();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(battery.charge_counter, None);
+        assert_eq!(battery.remaining_capacity.value(), 1000);
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn apply_previous_readings_by_name() {
+        let sysfs = MockSysfs::new();
+        for (name, current_now) in &[("BAT0", 1000000), ("BAT1", 2000000)] {
+            sysfs
+                .battery(name)
+                .charge_full(2000000)
+                .charge_full_design(2800000)
+                .charge_now(1000000)
+                .current_now(*current_now)
+                .status("Discharging")
+                .voltage_now(15045000)
+                .build();
+        }
+
+        let previous = acpi_client::BatteryInfo::new(&sysfs.path().join("BAT0")).unwrap();
+        sysfs.battery("BAT0").current_now(2000000).build();
+        sysfs.battery("BAT1").charge_now(1500000).build();
+        let mut batteries = acpi_client::BatteryReader::new()
+            .previous(Some(&previous))
+            .smoothing_factor(0.5)
+            .percentage_hysteresis(100.)
+            .read_all(sysfs.path())
+            .unwrap();
+        batteries.sort_by(|a, b| a.name.cmp(&b.name));
+
+        // Only BAT0 is blended with its previous reading
+        assert_eq!(batteries[0].present_rate, 1500);
+        assert_eq!(batteries[1].present_rate, 2000);
+        assert_eq!(batteries[0].percentage, 50.);
+        assert_eq!(batteries[1].percentage, 75.);
+
+        sysfs.close();
+    }
 }
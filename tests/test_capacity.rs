@@ -0,0 +1,44 @@
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn convert_between_charge_and_energy() {
+        use acpi_client::{MilliAmpHours, MilliWattHours};
+
+        let charge = MilliAmpHours(2000);
+        assert_eq!(charge.to_milli_watt_hours(15000), MilliWattHours(30000));
+        let energy = MilliWattHours(30000);
+        assert_eq!(energy.to_milli_amp_hours(15000), MilliAmpHours(2000));
+        assert_eq!(energy.to_milli_amp_hours(0), MilliAmpHours(0));
+        assert_eq!(
+            MilliAmpHours(u32::MAX).to_milli_watt_hours(20000),
+            MilliWattHours(u32::MAX)
+        );
+
+        assert_eq!(u32::from(charge), 2000);
+        assert_eq!(energy.value(), 30000);
+        assert_eq!(charge.to_string(), "2000 mAh");
+        assert_eq!(energy.to_string(), "30000 mWh");
+    }
+
+    #[test]
+    fn capacity_in_either_unit() {
+        use acpi_client::{Capacity, CapacityUnit, MilliAmpHours, MilliWattHours};
+
+        let charge = Capacity::new(2000, CapacityUnit::MilliAmpHours);
+        assert_eq!(charge, Capacity::Charge(MilliAmpHours(2000)));
+        assert_eq!(charge.unit(), CapacityUnit::MilliAmpHours);
+        assert_eq!(charge.to_milli_amp_hours(15000), MilliAmpHours(2000));
+        assert_eq!(charge.to_milli_watt_hours(15000), MilliWattHours(30000));
+
+        let energy = Capacity::new(30000, CapacityUnit::MilliWattHours);
+        assert_eq!(energy, Capacity::Energy(MilliWattHours(30000)));
+        assert_eq!(energy.unit(), CapacityUnit::MilliWattHours);
+        assert_eq!(energy.to_milli_amp_hours(15000), MilliAmpHours(2000));
+        assert_eq!(energy.to_milli_watt_hours(15000), MilliWattHours(30000));
+
+        assert_eq!(u32::from(energy), 30000);
+        assert_eq!(charge.value(), 2000);
+        assert_eq!(charge.to_string(), "2000 mAh");
+        assert_eq!(energy.to_string(), "30000 mWh");
+    }
+}
@@ -0,0 +1,233 @@
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn parse_known_device_kind() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("cooling_device0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("cur_state")).unwrap();
+        writeln!(file, "0").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("max_state")).unwrap();
+        writeln!(file, "3").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Fan").unwrap();
+
+        let device = acpi_client::CoolingDevice::new(&mock_path).unwrap();
+        assert_eq!(device.device_type, String::from("Fan"));
+        assert_eq!(device.kind(), acpi_client::CoolingDeviceKind::Fan);
+        assert_eq!(
+            acpi_client::CoolingDeviceKind::from("Processor"),
+            acpi_client::CoolingDeviceKind::Processor
+        );
+        assert_eq!(
+            acpi_client::CoolingDeviceKind::from("radiation"),
+            acpi_client::CoolingDeviceKind::Radiation
+        );
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn parse_unknown_device_kind() {
+        assert_eq!(
+            acpi_client::CoolingDeviceKind::from("intel_powerclamp"),
+            acpi_client::CoolingDeviceKind::Other(String::from("intel_powerclamp"))
+        );
+    }
+
+    #[test]
+    fn parse_total_transitions() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("cooling_device0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("cur_state")).unwrap();
+        writeln!(file, "1").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("max_state")).unwrap();
+        writeln!(file, "3").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Processor").unwrap();
+
+        let device = acpi_client::CoolingDevice::new(&mock_path).unwrap();
+        assert_eq!(device.total_transitions, None);
+
+        std::fs::create_dir(mock_path.join("stats")).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("stats").join("total_trans")).unwrap();
+        writeln!(file, "4821").unwrap();
+
+        let device = acpi_client::CoolingDevice::new(&mock_path).unwrap();
+        assert_eq!(device.total_transitions, Some(4821));
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn try_from_path() {
+        use std::convert::TryFrom;
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("cooling_device0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("cur_state")).unwrap();
+        writeln!(file, "1").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("max_state")).unwrap();
+        writeln!(file, "3").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Fan").unwrap();
+
+        let device = acpi_client::CoolingDevice::try_from(mock_path.as_path()).unwrap();
+        assert_eq!(device.state.unwrap().max_state, 3);
+        let device = acpi_client::CoolingDevice::try_from(mock_path.clone()).unwrap();
+        assert_eq!(device.device_type, "Fan");
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn look_up_device_by_name() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("cooling_device2");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("cur_state")).unwrap();
+        writeln!(file, "0").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("max_state")).unwrap();
+        writeln!(file, "10").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Processor").unwrap();
+
+        let device =
+            acpi_client::get_cooling_device_by_name(dir.path(), "cooling_device2").unwrap();
+        assert_eq!(device.name, "cooling_device2");
+        assert_eq!(device.device_type, "Processor");
+
+        match acpi_client::get_cooling_device_by_name(dir.path(), "cooling_device7") {
+            Err(acpi_client::utils::AcpiClientError::Io(err)) => {
+                assert_eq!(err.kind(), std::io::ErrorKind::NotFound)
+            }
+            _ => panic!("expected a not found error"),
+        }
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn write_cooling_state() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("cooling_device0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("cur_state")).unwrap();
+        writeln!(file, "0").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("max_state")).unwrap();
+        writeln!(file, "3").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Fan").unwrap();
+
+        let device = acpi_client::CoolingDevice::new(&mock_path).unwrap();
+        device.set_state(2).unwrap();
+        let device = acpi_client::CoolingDevice::new(&mock_path).unwrap();
+        assert_eq!(device.state.unwrap().current_state, 2);
+
+        for state in &[-1, 4] {
+            match device.set_state(*state) {
+                Err(acpi_client::utils::AcpiClientError::InvalidInput(_)) => {}
+                _ => panic!("expected state {} to be rejected", state),
+            }
+        }
+        let device = acpi_client::CoolingDevice::new(&mock_path).unwrap();
+        assert_eq!(device.state.unwrap().current_state, 2);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn write_cooling_state_without_status() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("cooling_device0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("cur_state")).unwrap();
+        writeln!(file, "-1").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("max_state")).unwrap();
+        writeln!(file, "3").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Fan").unwrap();
+
+        let device = acpi_client::CoolingDevice::new(&mock_path).unwrap();
+        assert!(device.state.is_none());
+        device.set_state(3).unwrap();
+        let device = acpi_client::CoolingDevice::new(&mock_path).unwrap();
+        assert_eq!(device.state.unwrap().current_state, 3);
+
+        std::fs::remove_file(mock_path.join("max_state")).unwrap();
+        assert!(device.set_state(1).is_err());
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn aggregate_fans_by_type() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        for (name, current_state, max_state, device_type) in &[
+            ("cooling_device0", "1", "4", "Fan"),
+            ("cooling_device1", "3", "4", "Fan"),
+            ("cooling_device2", "-1", "4", "Fan"),
+            ("cooling_device3", "5", "10", "Processor"),
+        ] {
+            let mock_path = dir.path().join(name);
+            std::fs::create_dir(&mock_path).unwrap();
+            let mut file = std::fs::File::create(mock_path.join("cur_state")).unwrap();
+            writeln!(file, "{}", current_state).unwrap();
+            let mut file = std::fs::File::create(mock_path.join("max_state")).unwrap();
+            writeln!(file, "{}", max_state).unwrap();
+            let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+            writeln!(file, "{}", device_type).unwrap();
+        }
+
+        let devices = acpi_client::get_cooling_device_info(dir.path()).unwrap();
+        assert_eq!(devices.len(), 4);
+        let aggregated = acpi_client::aggregate_cooling_by_type(&devices);
+        assert_eq!(aggregated.len(), 2);
+        assert_eq!(aggregated["Fan"], 50.);
+        assert_eq!(aggregated["Processor"], 50.);
+
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn ignore_state_without_levels() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("cooling_device0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("cur_state")).unwrap();
+        writeln!(file, "0").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("max_state")).unwrap();
+        writeln!(file, "0").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Processor").unwrap();
+
+        let device = acpi_client::CoolingDevice::new(&mock_path).unwrap();
+        assert!(device.state.is_none());
+
+        drop(file);
+        dir.close().unwrap();
+    }
+}
@@ -0,0 +1,68 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::MockSysfs;
+
+    #[test]
+    fn battery_ffi_round_trip() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .current_now(500000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        let ffi = acpi_client::BatteryInfoFfi::from(&battery);
+        assert_eq!(ffi.name_str(), "BAT0");
+        assert_eq!(ffi.state, 1);
+        assert_eq!(ffi.percentage, battery.percentage);
+        assert_eq!(ffi.remaining_capacity, 1000);
+        assert_eq!(ffi.last_capacity, 2000);
+        assert_eq!(ffi.design_capacity, 2800);
+        assert_eq!(ffi.capacity_unit, 0);
+        assert_eq!(ffi.present_rate, battery.present_rate);
+        assert_eq!(ffi.present_rate_unit, 0);
+        assert_eq!(ffi.voltage, 15045);
+        assert_eq!(
+            ffi.time_remaining_secs,
+            battery.time_remaining.unwrap().as_secs() as i64
+        );
+
+        std::fs::remove_file(mock_path.join("current_now")).unwrap();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        assert_eq!(
+            acpi_client::BatteryInfoFfi::from(&battery).time_remaining_secs,
+            -1
+        );
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn battery_ffi_truncates_long_names() {
+        let name = "hid-00:11:22:33:44:55-battery-éé";
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery(name)
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        let ffi = acpi_client::BatteryInfoFfi::from(&battery);
+        assert!(name.starts_with(ffi.name_str()));
+        assert!(ffi.name_str().len() < acpi_client::FFI_NAME_LEN);
+        assert_eq!(ffi.name[acpi_client::FFI_NAME_LEN - 1], 0);
+
+        sysfs.close();
+    }
+}
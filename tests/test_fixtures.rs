@@ -0,0 +1,243 @@
+#[cfg(test)]
+mod tests {
+    use acpi_client::{
+        BatteryInfo, Capacity, CapacityLevel, CapacityUnit, ChargingState, MilliAmpHours, RateUnit,
+        SystemDevices, Units,
+    };
+    use std::path::PathBuf;
+    use std::time::{Duration, SystemTime};
+
+    /// Returns the root of a fixture tree beneath `tests/fixtures`. The trees are hand-written
+    /// layouts rather than captured dumps, see `tests/fixtures/README.md`.
+    fn fixture_root(name: &str) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("tests/fixtures")
+            .join(name)
+    }
+
+    /// Scans every device of a fixture tree.
+    fn load(name: &str) -> SystemDevices {
+        acpi_client::scan_all(&fixture_root(name), Units::Celsius).unwrap()
+    }
+
+    /// Returns a duration rounded to whole minutes, since estimates computed in floating point
+    /// land a few seconds either side of the exact value.
+    fn minutes(duration: Option<Duration>) -> Option<u64> {
+        duration.map(|duration| (duration.as_secs_f32() / 60.).round() as u64)
+    }
+
+    /// Returns a battery with the time it was sampled at cleared, its time remaining rounded to
+    /// whole minutes, and its percentage rounded to hundredths, so it can be compared whole.
+    fn normalized(battery: &BatteryInfo) -> BatteryInfo {
+        BatteryInfo {
+            time_remaining: minutes(battery.time_remaining)
+                .map(|minutes| Duration::from_secs(minutes * 60)),
+            percentage: (battery.percentage * 100.).round() / 100.,
+            sampled_at: SystemTime::UNIX_EPOCH,
+            ..battery.clone()
+        }
+    }
+
+    /// Returns a discharging battery of a fixture tree reporting nothing beyond its name, to fill
+    /// in the fields an expected reading doesn't set.
+    fn bare_battery(fixture: &str, name: &str) -> BatteryInfo {
+        BatteryInfo {
+            name: String::from(name),
+            path: fixture_root(fixture)
+                .join("sys/class/power_supply")
+                .join(name),
+            model_name: None,
+            manufacturer: None,
+            serial_number: None,
+            manufacture_date: None,
+            uevent_name: None,
+            remaining_capacity: Capacity::Charge(MilliAmpHours(0)),
+            present_rate: 0,
+            present_rate_unit: RateUnit::MilliAmps,
+            voltage: 0,
+            design_voltage: None,
+            design_voltage_max: None,
+            voltage_ocv: None,
+            design_capacity: Capacity::Charge(MilliAmpHours(0)),
+            last_capacity: Capacity::Charge(MilliAmpHours(0)),
+            capacity_unit: CapacityUnit::MilliAmpHours,
+            time_remaining: None,
+            percentage: 0.,
+            state: ChargingState::Discharging,
+            raw_status: String::from("Discharging"),
+            capacity_level: None,
+            reported_health: None,
+            charge_type: None,
+            charge_start_threshold: None,
+            charge_stop_threshold: None,
+            capacity_error_margin: None,
+            temperature: None,
+            constant_charge_voltage: None,
+            constant_charge_voltage_max: None,
+            current_max: None,
+            voltage_max: None,
+            precharge_current: None,
+            charge_term_current: None,
+            cycle_count: None,
+            charge_counter: None,
+            sampled_at: SystemTime::UNIX_EPOCH,
+            design_energy: None,
+            last_energy: None,
+            remaining_energy: None,
+        }
+    }
+
+    #[test]
+    fn every_fixture_parses() {
+        let mut fixtures = 0;
+        for entry in std::fs::read_dir(fixture_root("")).unwrap() {
+            let path = entry.unwrap().path();
+            if !path.is_dir() {
+                continue;
+            }
+            let devices = acpi_client::scan_all(&path, Units::Celsius).unwrap();
+            assert!(!devices.batteries.is_empty(), "{:?}", path);
+            fixtures += 1;
+        }
+        assert!(fixtures >= 3);
+    }
+
+    #[test]
+    fn energy_battery_with_power_now() {
+        let devices = load("energy_power_now");
+
+        assert_eq!(devices.batteries.len(), 1);
+        let battery = &devices.batteries[0];
+        // Capacities are charges converted from the energies at 11.85 V
+        let expected = BatteryInfo {
+            model_name: Some(String::from("45N1773")),
+            manufacturer: Some(String::from("SANYO")),
+            serial_number: Some(String::from("12345")),
+            remaining_capacity: Capacity::Charge(MilliAmpHours(3207)),
+            present_rate: 12670,
+            present_rate_unit: RateUnit::MilliWatts,
+            voltage: 11850,
+            design_voltage: Some(10800),
+            design_capacity: Capacity::Charge(MilliAmpHours(4870)),
+            last_capacity: Capacity::Charge(MilliAmpHours(4276)),
+            time_remaining: Some(Duration::from_secs(3 * 3600)),
+            percentage: 75.,
+            capacity_level: Some(CapacityLevel::Normal),
+            cycle_count: Some(312),
+            design_energy: Some(57720),
+            last_energy: Some(50680),
+            remaining_energy: Some(38010),
+            ..bare_battery("energy_power_now", "BAT0")
+        };
+        assert_eq!(normalized(battery), expected);
+        assert_eq!(battery.label(), "SANYO 45N1773 (BAT0)");
+
+        assert_eq!(devices.ac_adapters.len(), 1);
+        assert!(!devices.ac_adapters[0].is_online());
+
+        assert_eq!(devices.thermal_sensors.len(), 1);
+        let sensor = &devices.thermal_sensors[0];
+        assert_eq!(sensor.category(), acpi_client::ThermalCategory::System);
+        assert_eq!(sensor.to_acpi_line(0), "Thermal 0: ok, 48.0 degrees C");
+    }
+
+    #[test]
+    fn charge_battery_with_current_now() {
+        let devices = load("charge_current_now");
+
+        assert_eq!(devices.batteries.len(), 1);
+        let expected = BatteryInfo {
+            model_name: Some(String::from("DELL5XJ28")),
+            manufacturer: Some(String::from("SMP")),
+            remaining_capacity: Capacity::Charge(MilliAmpHours(3000)),
+            present_rate: 1000,
+            voltage: 12600,
+            design_voltage: Some(11100),
+            design_capacity: Capacity::Charge(MilliAmpHours(4400)),
+            last_capacity: Capacity::Charge(MilliAmpHours(4000)),
+            time_remaining: Some(Duration::from_secs(3600)),
+            percentage: 75.,
+            state: ChargingState::Charging,
+            raw_status: String::from("Charging"),
+            ..bare_battery("charge_current_now", "BAT1")
+        };
+        assert_eq!(normalized(&devices.batteries[0]), expected);
+
+        assert!(devices.ac_adapters[0].is_online());
+        assert_eq!(
+            acpi_client::power_state_code(&devices.batteries, &devices.ac_adapters),
+            4
+        );
+
+        assert_eq!(devices.thermal_sensors.len(), 1);
+        assert_eq!(
+            devices.thermal_sensors[0].category(),
+            acpi_client::ThermalCategory::Cpu
+        );
+        assert_eq!(devices.cooling_devices.len(), 1);
+        assert_eq!(
+            devices.cooling_devices[0].kind(),
+            acpi_client::CoolingDeviceKind::Processor
+        );
+    }
+
+    #[test]
+    fn dual_batteries_with_threshold() {
+        let mut devices = load("dual_battery_threshold");
+        devices.batteries.sort_by(|a, b| a.name.cmp(&b.name));
+
+        assert_eq!(devices.batteries.len(), 2);
+        // Between its start and stop thresholds, so held rather than charging
+        let held = &devices.batteries[0];
+        let expected = BatteryInfo {
+            remaining_capacity: Capacity::Charge(MilliAmpHours(1477)),
+            present_rate_unit: RateUnit::MilliWatts,
+            voltage: 12400,
+            design_voltage: Some(11400),
+            design_capacity: Capacity::Charge(MilliAmpHours(1893)),
+            last_capacity: Capacity::Charge(MilliAmpHours(1893)),
+            time_remaining: Some(Duration::from_secs(0)),
+            percentage: 78.,
+            state: ChargingState::NotCharging,
+            raw_status: String::from("Not charging"),
+            charge_start_threshold: Some(75),
+            charge_stop_threshold: Some(80),
+            design_energy: Some(23480),
+            last_energy: Some(23480),
+            remaining_energy: Some(18314),
+            ..bare_battery("dual_battery_threshold", "BAT0")
+        };
+        assert_eq!(normalized(held), expected);
+        assert!(held.is_charge_inhibited());
+        assert_eq!(
+            held.high_level_state(),
+            acpi_client::BatteryState::HeldByThreshold
+        );
+
+        let expected = BatteryInfo {
+            remaining_capacity: Capacity::Charge(MilliAmpHours(796)),
+            present_rate: 9000,
+            present_rate_unit: RateUnit::MilliWatts,
+            voltage: 11300,
+            design_voltage: Some(11100),
+            design_capacity: Capacity::Charge(MilliAmpHours(4205)),
+            last_capacity: Capacity::Charge(MilliAmpHours(3982)),
+            time_remaining: Some(Duration::from_secs(3600)),
+            percentage: 20.,
+            design_energy: Some(47520),
+            last_energy: Some(45000),
+            remaining_energy: Some(9000),
+            ..bare_battery("dual_battery_threshold", "BAT1")
+        };
+        assert_eq!(normalized(&devices.batteries[1]), expected);
+
+        assert_eq!(
+            acpi_client::battery_power_flow(&devices.batteries),
+            acpi_client::PowerFlow::NetDischarging
+        );
+        // 27.3144 Wh remaining across both batteries at 9 W
+        let remaining =
+            acpi_client::system_time_remaining(&devices.batteries, &devices.ac_adapters);
+        assert_eq!(minutes(remaining), Some(182));
+    }
+}
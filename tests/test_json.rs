@@ -0,0 +1,53 @@
+#![cfg(feature = "json")]
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn render_mock_tree_as_json() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let power_supply_path = dir.path().join("sys/class/power_supply");
+        let thermal_path = dir.path().join("sys/class/thermal");
+
+        let mock_path = power_supply_path.join("BAT0");
+        std::fs::create_dir_all(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Battery").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let mock_path = power_supply_path.join("AC");
+        std::fs::create_dir_all(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Mains").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("online")).unwrap();
+        writeln!(file, "1").unwrap();
+
+        let mock_path = thermal_path.join("thermal_zone0");
+        std::fs::create_dir_all(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "47000").unwrap();
+
+        let tree = acpi_client::json::system_json(dir.path(), acpi_client::Units::Celsius).unwrap();
+        for key in &["batteries", "ac_adapters", "thermal", "cooling"] {
+            assert!(tree[key].is_array());
+        }
+        assert_eq!(tree["batteries"][0]["name"], "BAT0");
+        assert_eq!(tree["batteries"][0]["percentage"], 50.);
+        assert_eq!(tree["ac_adapters"][0]["online"], true);
+        assert_eq!(tree["thermal"][0]["temperature"], 47.);
+        assert_eq!(tree["cooling"].as_array().unwrap().len(), 0);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+}
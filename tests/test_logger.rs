@@ -0,0 +1,59 @@
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn log_snapshots_to_csv() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "599000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let log_path = dir.path().join("battery.csv");
+        let logger = acpi_client::CsvLogger::new(&log_path, std::time::Duration::from_secs(60));
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        logger.append(&battery, Some(47.)).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "900000").unwrap();
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        logger.append(&battery, None).unwrap();
+
+        let contents = std::fs::read_to_string(&log_path).unwrap();
+        let rows: Vec<Vec<&str>> = contents
+            .lines()
+            .map(|line| line.split(',').collect())
+            .collect();
+        assert_eq!(rows.len(), 3);
+        assert_eq!(
+            rows[0],
+            vec![
+                "timestamp",
+                "percentage",
+                "state",
+                "rate",
+                "voltage",
+                "temperature_c"
+            ]
+        );
+        assert!(rows[1][0].parse::<u64>().unwrap() > 0);
+        assert_eq!(
+            rows[1][1..],
+            ["50.0", "Discharging", "599", "15045", "47.0"]
+        );
+        assert_eq!(rows[2][1..], ["45.0", "Discharging", "599", "15045", ""]);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "metrics")]
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn render_mock_tree() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let power_supply_path = dir.path().join("sys/class/power_supply");
+        let thermal_path = dir.path().join("sys/class/thermal");
+
+        let mock_path = power_supply_path.join("BAT0");
+        std::fs::create_dir_all(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1660000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "599000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Battery").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let mock_path = power_supply_path.join("AC");
+        std::fs::create_dir_all(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Mains").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("online")).unwrap();
+        writeln!(file, "0").unwrap();
+
+        let mock_path = thermal_path.join("thermal_zone0");
+        std::fs::create_dir_all(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "47000").unwrap();
+
+        let output =
+            acpi_client::metrics::render_prometheus(dir.path(), acpi_client::Units::Celsius)
+                .unwrap();
+        assert!(output.contains("acpi_battery_percentage{name=\"BAT0\"} 83\n"));
+        assert!(output.contains("acpi_ac_online{name=\"AC\"} 0\n"));
+        assert!(output.contains("acpi_thermal_celsius{zone=\"thermal_zone0\"} 47\n"));
+        assert!(output.contains("# TYPE acpi_battery_percentage gauge\n"));
+        assert!(!output.contains("acpi_cooling_state"));
+        for line in output.lines().filter(|line| !line.starts_with('#')) {
+            let (series, value) = line.rsplit_once(' ').unwrap();
+            assert!(series.starts_with("acpi_"));
+            assert!(series.ends_with("\"}"));
+            assert!(value.parse::<f64>().is_ok());
+        }
+
+        drop(file);
+        dir.close().unwrap();
+    }
+}
@@ -0,0 +1,134 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::MockSysfs;
+
+    #[test]
+    fn enumerate_mock_power_supplies() {
+        use acpi_client::{PowerSupplyData, PowerSupplyType};
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "599000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Battery").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let mock_path = dir.path().join("AC");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Mains").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("online")).unwrap();
+        writeln!(file, "1").unwrap();
+
+        let mut supplies = acpi_client::get_power_supply_info(dir.path()).unwrap();
+        supplies.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(supplies.len(), 2);
+
+        assert_eq!(supplies[0].name, "AC");
+        assert_eq!(supplies[0].kind, PowerSupplyType::Mains);
+        assert!(!supplies[0].is_battery);
+        assert!(matches!(
+            supplies[0].data,
+            PowerSupplyData::Adapter(ref adapter) if adapter.is_online()
+        ));
+
+        assert_eq!(supplies[1].name, "BAT0");
+        assert_eq!(supplies[1].kind, PowerSupplyType::Battery);
+        assert!(supplies[1].is_battery);
+        assert!(matches!(
+            supplies[1].data,
+            PowerSupplyData::Battery(ref battery) if battery.remaining_capacity.value() == 1000
+        ));
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn choose_active_power_source() {
+        use acpi_client::PowerSource;
+
+        let sysfs = MockSysfs::new();
+        sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+        sysfs.adapter("AC").online(false).build();
+        sysfs
+            .device("ucsi-source-psy-USBC000:001")
+            .attribute("type", "USB")
+            .online(false)
+            .build();
+        assert_eq!(
+            acpi_client::active_power_source(sysfs.path()).unwrap(),
+            PowerSource::Battery
+        );
+
+        sysfs
+            .device("ucsi-source-psy-USBC000:001")
+            .online(true)
+            .build();
+        assert_eq!(
+            acpi_client::active_power_source(sysfs.path()).unwrap(),
+            PowerSource::Usb(String::from("ucsi-source-psy-USBC000:001"))
+        );
+
+        // Mains takes priority when both are online
+        sysfs.adapter("AC").online(true).build();
+        assert_eq!(
+            acpi_client::active_power_source(sysfs.path()).unwrap(),
+            PowerSource::Mains(String::from("AC"))
+        );
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn record_the_path_each_device_was_read_from() {
+        use acpi_client::PowerSupplyData;
+
+        let sysfs = MockSysfs::new();
+        let battery_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+        let adapter_path = sysfs.adapter("AC").online(true).build();
+
+        let mut supplies = acpi_client::get_power_supply_info(sysfs.path()).unwrap();
+        supplies.sort_by(|a, b| a.name.cmp(&b.name));
+        assert_eq!(supplies[0].path, adapter_path);
+        assert_eq!(supplies[1].path, battery_path);
+        match supplies[0].data {
+            PowerSupplyData::Adapter(ref adapter) => assert_eq!(adapter.path, adapter_path),
+            _ => panic!("expected an adapter"),
+        }
+        match supplies[1].data {
+            PowerSupplyData::Battery(ref battery) => assert_eq!(battery.path, battery_path),
+            _ => panic!("expected a battery"),
+        }
+
+        sysfs.close();
+    }
+}
@@ -0,0 +1,67 @@
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn parse_mock_rapl_domain() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        for (name, domain_name, energy) in &[
+            ("intel-rapl:0", "package-0", "31896524385"),
+            ("intel-rapl:0:0", "core", "8912740651"),
+        ] {
+            let mock_path = dir.path().join(name);
+            std::fs::create_dir(&mock_path).unwrap();
+            let mut file = std::fs::File::create(mock_path.join("name")).unwrap();
+            writeln!(file, "{}", domain_name).unwrap();
+            let mut file = std::fs::File::create(mock_path.join("energy_uj")).unwrap();
+            writeln!(file, "{}", energy).unwrap();
+            let mut file = std::fs::File::create(mock_path.join("max_energy_range_uj")).unwrap();
+            writeln!(file, "262143328850").unwrap();
+        }
+        std::fs::create_dir(dir.path().join("intel-rapl")).unwrap();
+
+        let domains = acpi_client::get_rapl_domains(dir.path()).unwrap();
+        assert_eq!(domains.len(), 2);
+        let package = &domains[0];
+        assert_eq!(package.name, "intel-rapl:0");
+        assert_eq!(package.domain_name, "package-0");
+        assert_eq!(package.energy_uj, 31896524385);
+        assert_eq!(package.max_energy_range_uj, Some(262143328850));
+        assert_eq!(domains[1].domain_name, "core");
+
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn compute_power_between_reads() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("intel-rapl:0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("name")).unwrap();
+        writeln!(file, "package-0").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("energy_uj")).unwrap();
+        writeln!(file, "262133328850").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("max_energy_range_uj")).unwrap();
+        writeln!(file, "262143328850").unwrap();
+
+        let previous = acpi_client::RaplDomain::new(&mock_path).unwrap();
+        let mut current = acpi_client::RaplDomain::new(&mock_path).unwrap();
+        current.sampled_at = previous.sampled_at + std::time::Duration::from_secs(2);
+        current.energy_uj = previous.energy_uj + 30000000;
+        // 30 J over two seconds
+        assert!((current.power_since(&previous).unwrap() - 15.).abs() < 1e-3);
+
+        // The counter wraps around 10 J before its range
+        current.energy_uj = 20000000;
+        assert!((current.power_since(&previous).unwrap() - 15.).abs() < 1e-3);
+
+        current.max_energy_range_uj = None;
+        assert_eq!(current.power_since(&previous), None);
+        assert_eq!(previous.power_since(&current), None);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+}
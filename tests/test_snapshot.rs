@@ -0,0 +1,48 @@
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn battery_snapshot_fields() {
+        use acpi_client::Snapshot;
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "500000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let battery = acpi_client::BatteryInfo::new(&mock_path).unwrap();
+        let snapshot = battery.snapshot();
+        assert_eq!(snapshot.name, "BAT0");
+        assert_eq!(snapshot.state, "Discharging");
+        assert!((snapshot.percentage - 50.).abs() < 0.01);
+        assert_eq!(snapshot.remaining_capacity, 1000);
+        assert_eq!(snapshot.last_capacity, 2000);
+        assert_eq!(snapshot.design_capacity, 2800);
+        assert_eq!(snapshot.capacity_unit, "mAh");
+        assert_eq!(snapshot.present_rate, 500);
+        assert_eq!(snapshot.present_rate_unit, "mA");
+        assert_eq!(snapshot.voltage, 15045);
+        assert_eq!(
+            snapshot.time_remaining_secs,
+            battery
+                .time_remaining
+                .map(|time_remaining| time_remaining.as_secs())
+        );
+        assert!(snapshot.time_remaining_secs.is_some());
+        assert!(snapshot.sampled_at_secs > 0);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+}
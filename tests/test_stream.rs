@@ -0,0 +1,36 @@
+#![cfg(feature = "async")]
+
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::MockSysfs;
+    use tokio_stream::StreamExt;
+
+    #[tokio::test]
+    async fn stream_yields_a_reading_per_tick() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .current_now(500000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+
+        let stream =
+            acpi_client::stream::battery_stream(&mock_path, std::time::Duration::from_millis(10));
+        let readings: Vec<_> = stream.take(2).collect().await;
+
+        assert_eq!(readings.len(), 2);
+        for reading in readings {
+            let battery = reading.unwrap();
+            assert_eq!(battery.name, "BAT0");
+            assert_eq!(battery.percentage, 50.);
+        }
+
+        sysfs.close();
+    }
+}
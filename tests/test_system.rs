@@ -0,0 +1,605 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    use crate::common::MockSysfs;
+
+    #[test]
+    fn scan_mock_tree() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let power_supply_path = dir.path().join("sys/class/power_supply");
+        let thermal_path = dir.path().join("sys/class/thermal");
+
+        let mock_path = power_supply_path.join("BAT0");
+        std::fs::create_dir_all(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "599000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Battery").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let mock_path = power_supply_path.join("ACAD");
+        std::fs::create_dir_all(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Mains").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("online")).unwrap();
+        writeln!(file, "1").unwrap();
+
+        let mock_path = thermal_path.join("thermal_zone0");
+        std::fs::create_dir_all(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "47000").unwrap();
+
+        let mock_path = thermal_path.join("cooling_device0");
+        std::fs::create_dir_all(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("cur_state")).unwrap();
+        writeln!(file, "0").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("max_state")).unwrap();
+        writeln!(file, "3").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Processor").unwrap();
+
+        let devices = acpi_client::scan_all(dir.path(), acpi_client::Units::Celsius).unwrap();
+        assert_eq!(devices.batteries.len(), 1);
+        assert_eq!(devices.ac_adapters.len(), 1);
+        assert_eq!(devices.thermal_sensors.len(), 1);
+        assert_eq!(devices.cooling_devices.len(), 1);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn summarize_mock_tree() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let power_supply_path = dir.path().join("sys/class/power_supply");
+        let thermal_path = dir.path().join("sys/class/thermal");
+
+        let mock_path = power_supply_path.join("BAT0");
+        std::fs::create_dir_all(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1660000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "487000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Charging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Battery").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "15045000").unwrap();
+
+        let mock_path = power_supply_path.join("AC");
+        std::fs::create_dir_all(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Mains").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("online")).unwrap();
+        writeln!(file, "1").unwrap();
+
+        let mock_path = thermal_path.join("thermal_zone0");
+        std::fs::create_dir_all(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "47000").unwrap();
+        let mock_path = thermal_path.join("thermal_zone1");
+        std::fs::create_dir_all(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "51000").unwrap();
+
+        let summary =
+            acpi_client::one_line_summary(dir.path(), acpi_client::Units::Celsius).unwrap();
+        assert_eq!(summary, "BAT0 83% (charging, 0:41) AC online 51°C");
+
+        std::fs::remove_dir_all(&power_supply_path).unwrap();
+        let summary =
+            acpi_client::one_line_summary(dir.path(), acpi_client::Units::Celsius).unwrap();
+        assert_eq!(summary, "51°C");
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn read_through_prefixed_root() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let root = acpi_client::SysfsRoot::new(dir.path().join("host"));
+        assert_eq!(
+            root.power_supply_path(),
+            dir.path().join("host/sys/class/power_supply")
+        );
+        assert_eq!(
+            root.thermal_path(),
+            dir.path().join("host/sys/class/thermal")
+        );
+        assert_eq!(
+            root.powercap_path(),
+            dir.path().join("host/sys/class/powercap")
+        );
+
+        let mock_path = root.power_supply_path().join("ACAD");
+        std::fs::create_dir_all(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Mains").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("online")).unwrap();
+        writeln!(file, "1").unwrap();
+
+        let adapters = root.ac_adapters().unwrap();
+        assert_eq!(adapters.len(), 1);
+        assert_eq!(adapters[0].name, "ACAD");
+        assert!(root.batteries().unwrap().is_empty());
+        let devices = root.scan_all(acpi_client::Units::Celsius).unwrap();
+        assert_eq!(devices.ac_adapters.len(), 1);
+        assert!(devices.thermal_sensors.is_empty());
+
+        let mock_path = root.powercap_path().join("intel-rapl:0");
+        std::fs::create_dir_all(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("name")).unwrap();
+        writeln!(file, "package-0").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("energy_uj")).unwrap();
+        writeln!(file, "31896524385").unwrap();
+        let domains = root.rapl_domains().unwrap();
+        assert_eq!(domains.len(), 1);
+        assert_eq!(domains[0].domain_name, "package-0");
+        assert_eq!(
+            acpi_client::SysfsRoot::default().power_supply_path(),
+            std::path::Path::new("/sys/class/power_supply")
+        );
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn classify_mock_devices() {
+        use acpi_client::DeviceClass;
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Battery").unwrap();
+        assert_eq!(
+            acpi_client::classify_device(&mock_path).unwrap(),
+            DeviceClass::Battery
+        );
+
+        let mock_path = dir.path().join("ACAD");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Mains").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("online")).unwrap();
+        writeln!(file, "1").unwrap();
+        assert_eq!(
+            acpi_client::classify_device(&mock_path).unwrap(),
+            DeviceClass::AcAdapter
+        );
+
+        let mock_path = dir.path().join("thermal_zone0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "47000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "acpitz").unwrap();
+        assert_eq!(
+            acpi_client::classify_device(&mock_path).unwrap(),
+            DeviceClass::ThermalSensor
+        );
+
+        let mock_path = dir.path().join("cooling_device0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("cur_state")).unwrap();
+        writeln!(file, "0").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("max_state")).unwrap();
+        writeln!(file, "3").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Processor").unwrap();
+        assert_eq!(
+            acpi_client::classify_device(&mock_path).unwrap(),
+            DeviceClass::CoolingDevice
+        );
+
+        let mock_path = dir.path().join("hidpp_battery_0");
+        std::fs::create_dir(&mock_path).unwrap();
+        assert_eq!(
+            acpi_client::classify_device(&mock_path).unwrap(),
+            DeviceClass::Unknown
+        );
+
+        assert!(acpi_client::classify_device(&dir.path().join("missing")).is_err());
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn key_devices_by_id() {
+        use std::collections::HashMap;
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        for (name, temperature) in &[("thermal_zone0", "47000"), ("thermal_zone1", "52000")] {
+            let mock_path = dir.path().join(name);
+            std::fs::create_dir(&mock_path).unwrap();
+            let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+            writeln!(file, "{}", temperature).unwrap();
+        }
+
+        let sensors =
+            acpi_client::get_thermal_sensor_info(dir.path(), acpi_client::Units::Celsius).unwrap();
+        let mut temperatures = HashMap::new();
+        for sensor in &sensors {
+            temperatures.insert(sensor.id(), sensor.current_temperature);
+        }
+        assert_eq!(temperatures.len(), 2);
+        let id = acpi_client::DeviceId(String::from("thermal_zone1"));
+        assert!((temperatures[&id] - 52.).abs() < 0.01);
+        assert_eq!(id.to_string(), "thermal_zone1");
+
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn combined_time_remaining() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("BAT0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full")).unwrap();
+        writeln!(file, "2000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_full_design")).unwrap();
+        writeln!(file, "2800000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("charge_now")).unwrap();
+        writeln!(file, "1000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("current_now")).unwrap();
+        writeln!(file, "500000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "10000000").unwrap();
+
+        let mock_path = dir.path().join("BAT1");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("energy_full")).unwrap();
+        writeln!(file, "45000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("energy_full_design")).unwrap();
+        writeln!(file, "57000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("energy_now")).unwrap();
+        writeln!(file, "20000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("power_now")).unwrap();
+        writeln!(file, "5000000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("voltage_now")).unwrap();
+        writeln!(file, "11400000").unwrap();
+
+        let batteries = vec![
+            acpi_client::BatteryInfo::new(&dir.path().join("BAT0")).unwrap(),
+            acpi_client::BatteryInfo::new(&dir.path().join("BAT1")).unwrap(),
+        ];
+
+        // 10 Wh + 20 Wh drained at 5 W + 5 W lasts three hours
+        let time_remaining = acpi_client::system_time_remaining(&batteries, &[]).unwrap();
+        assert_eq!(time_remaining.as_secs() / 60, 180);
+
+        let mock_path = dir.path().join("AC");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("online")).unwrap();
+        writeln!(file, "1").unwrap();
+        let adapters = vec![acpi_client::ACAdapterInfo::new(&mock_path).unwrap()];
+        assert!(acpi_client::system_time_remaining(&batteries, &adapters).is_none());
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn refresh_power_status_with_hotplug() {
+        let sysfs = MockSysfs::new();
+        sysfs
+            .adapter("sys/class/power_supply/ACAD")
+            .online(false)
+            .build();
+        sysfs
+            .battery("sys/class/power_supply/BAT1")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+
+        let root = acpi_client::SysfsRoot::new(sysfs.path());
+        let mut status = acpi_client::SystemPowerStatus::snapshot(&root).unwrap();
+        assert_eq!(status.batteries.len(), 1);
+        assert!(!status.ac_adapters[0].is_online());
+
+        sysfs
+            .adapter("sys/class/power_supply/ACAD")
+            .online(true)
+            .build();
+        sysfs
+            .battery("sys/class/power_supply/BAT1")
+            .charge_now(900000)
+            .build();
+        sysfs
+            .battery("sys/class/power_supply/BAT0")
+            .charge_full(4000000)
+            .charge_full_design(4000000)
+            .charge_now(4000000)
+            .status("Full")
+            .voltage_now(15045000)
+            .build();
+        status.refresh().unwrap();
+
+        // The existing battery keeps its position ahead of the new one
+        let names: Vec<&str> = status.batteries.iter().map(|b| b.name.as_str()).collect();
+        assert_eq!(names, vec!["BAT1", "BAT0"]);
+        assert_eq!(status.batteries[0].remaining_capacity.value(), 900);
+        assert!(status.ac_adapters[0].is_online());
+
+        std::fs::remove_dir_all(sysfs.path().join("sys/class/power_supply/BAT1")).unwrap();
+        status.refresh().unwrap();
+        assert_eq!(status.batteries.len(), 1);
+        assert_eq!(status.batteries[0].name, "BAT0");
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn ignore_stray_files_in_class_dirs() {
+        let sysfs = MockSysfs::new();
+        sysfs
+            .battery("sys/class/power_supply/BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+        sysfs
+            .adapter("sys/class/power_supply/ACAD")
+            .online(true)
+            .build();
+        sysfs
+            .device("sys/class/thermal/thermal_zone0")
+            .attribute("temp", 47000)
+            .build();
+        // Stray files alongside the device directories
+        sysfs
+            .device("sys/class")
+            .attribute("power_supply/stray", "not a device")
+            .attribute("thermal/stray", "not a device")
+            .build();
+
+        let devices = acpi_client::scan_all(sysfs.path(), acpi_client::Units::Celsius).unwrap();
+        assert_eq!(devices.batteries.len(), 1);
+        assert_eq!(devices.ac_adapters.len(), 1);
+        assert_eq!(devices.thermal_sensors.len(), 1);
+        assert!(devices.cooling_devices.is_empty());
+        let power_supply_path = sysfs.path().join("sys/class/power_supply");
+        let supplies = acpi_client::get_power_supply_info(&power_supply_path).unwrap();
+        assert_eq!(supplies.len(), 2);
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn validate_incomplete_battery() {
+        let sysfs = MockSysfs::new();
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_now(2100000)
+            .attribute("voltage_now", "unknown")
+            .build();
+
+        let warnings = acpi_client::validate_device(&mock_path);
+        assert_eq!(
+            warnings,
+            vec![
+                "missing charge_full_design",
+                "voltage_now is not a number",
+                "charge_now exceeds charge_full",
+                "missing current_now and power_now, so the time remaining is unknown",
+                "missing status",
+            ]
+        );
+
+        let mock_path = sysfs
+            .battery("BAT0")
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .current_now(500000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+        assert!(acpi_client::validate_device(&mock_path).is_empty());
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn summarize_power_source() {
+        let sysfs = MockSysfs::new();
+        let battery_path = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1660000)
+            .current_now(480000)
+            .status("Charging")
+            .voltage_now(15045000)
+            .build();
+        let adapter_path = sysfs.adapter("AC").online(true).build();
+
+        let read = || {
+            (
+                vec![acpi_client::BatteryInfo::new(&battery_path).unwrap()],
+                vec![acpi_client::ACAdapterInfo::new(&adapter_path).unwrap()],
+            )
+        };
+
+        let (batteries, adapters) = read();
+        assert_eq!(
+            acpi_client::power_summary(&batteries, &adapters),
+            "On AC (charging), 83%, 42 min to full"
+        );
+
+        sysfs
+            .battery("BAT0")
+            .charge_now(1220000)
+            .current_now(563000)
+            .status("Discharging")
+            .build();
+        sysfs.adapter("AC").online(false).build();
+        let (batteries, adapters) = read();
+        assert_eq!(
+            acpi_client::power_summary(&batteries, &adapters),
+            "On battery, 61%, 2h 10m remaining"
+        );
+
+        sysfs.adapter("AC").online(true).build();
+        let (_, adapters) = read();
+        assert_eq!(acpi_client::power_summary(&[], &adapters), "On AC power");
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn detect_internal_power_transfer() {
+        use acpi_client::PowerFlow;
+
+        let sysfs = MockSysfs::new();
+        let bat0 = sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .current_now(900000)
+            .status("Charging")
+            .voltage_now(15045000)
+            .build();
+        let bat1 = sysfs
+            .battery("BAT1")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1500000)
+            .current_now(1200000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+
+        let read = |paths: &[&std::path::PathBuf]| -> Vec<acpi_client::BatteryInfo> {
+            paths
+                .iter()
+                .map(|path| acpi_client::BatteryInfo::new(path).unwrap())
+                .collect()
+        };
+        assert_eq!(
+            acpi_client::battery_power_flow(&read(&[&bat0, &bat1])),
+            PowerFlow::InternalTransfer
+        );
+        assert_eq!(
+            acpi_client::battery_power_flow(&read(&[&bat0])),
+            PowerFlow::NetCharging
+        );
+        assert_eq!(
+            acpi_client::battery_power_flow(&read(&[&bat1])),
+            PowerFlow::NetDischarging
+        );
+        assert_eq!(acpi_client::battery_power_flow(&[]), PowerFlow::Idle);
+
+        // A current without a voltage to convert it with carries no power
+        sysfs.battery("BAT1").voltage_now(0).build();
+        assert_eq!(
+            acpi_client::battery_power_flow(&read(&[&bat0, &bat1])),
+            PowerFlow::NetCharging
+        );
+
+        sysfs.close();
+    }
+
+    #[test]
+    fn summarize_power_state_as_code() {
+        let sysfs = MockSysfs::new();
+        sysfs.adapter("AC").online(false).build();
+        sysfs
+            .battery("BAT0")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(1000000)
+            .status("Discharging")
+            .voltage_now(15045000)
+            .build();
+        sysfs
+            .battery("BAT1")
+            .charge_full(2000000)
+            .charge_full_design(2800000)
+            .charge_now(2000000)
+            .status("Full")
+            .voltage_now(15045000)
+            .build();
+        let read = || {
+            let batteries = ["BAT0", "BAT1"]
+                .iter()
+                .map(|name| acpi_client::BatteryInfo::new(&sysfs.path().join(name)).unwrap())
+                .collect::<Vec<_>>();
+            let adapters = vec![acpi_client::ACAdapterInfo::new(&sysfs.path().join("AC")).unwrap()];
+            acpi_client::power_state_code(&batteries, &adapters)
+        };
+
+        // The emptiest battery decides the level
+        assert_eq!(read(), 2);
+
+        sysfs.battery("BAT0").charge_now(250000).build();
+        assert_eq!(read(), 1);
+
+        sysfs
+            .battery("BAT0")
+            .attribute("capacity_level", "Critical")
+            .build();
+        assert_eq!(read(), 0);
+
+        sysfs.adapter("AC").online(true).build();
+        assert_eq!(read(), 5);
+
+        sysfs.battery("BAT0").status("Charging").build();
+        assert_eq!(read(), 4);
+
+        sysfs
+            .battery("BAT0")
+            .status("Full")
+            .charge_now(2000000)
+            .build();
+        sysfs.adapter("AC").online(false).build();
+        sysfs
+            .battery("BAT0")
+            .attribute("capacity_level", "Full")
+            .build();
+        assert_eq!(read(), 3);
+
+        assert_eq!(acpi_client::power_state_code(&[], &[]), 5);
+
+        sysfs.close();
+    }
+}
@@ -0,0 +1,937 @@
+mod common;
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn parse_bound_cooling_devices() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("thermal_zone0");
+        std::fs::create_dir(&mock_path).unwrap();
+        std::fs::create_dir(dir.path().join("cooling_device3")).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "47000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("cdev0_trip_point")).unwrap();
+        writeln!(file, "0").unwrap();
+
+        let sensor =
+            acpi_client::ThermalSensor::new(&mock_path, acpi_client::Units::Celsius).unwrap();
+        assert!(sensor.bound_cooling_devices.is_empty());
+
+        std::os::unix::fs::symlink("../cooling_device3", mock_path.join("cdev0")).unwrap();
+
+        let sensor =
+            acpi_client::ThermalSensor::new(&mock_path, acpi_client::Units::Celsius).unwrap();
+        assert_eq!(
+            sensor.bound_cooling_devices,
+            vec![String::from("cooling_device3")]
+        );
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn convert_valid_temperature() {
+        use acpi_client::Units;
+
+        let fahrenheit = acpi_client::try_convert_from_celsius(100., Units::Fahrenheit).unwrap();
+        assert!((fahrenheit - 212.).abs() < 0.01);
+        let celsius = acpi_client::try_convert_to_celsius(300., Units::Kelvin).unwrap();
+        assert!((celsius - 26.85).abs() < 0.01);
+        let celsius = acpi_client::try_convert_to_celsius(212., Units::Fahrenheit).unwrap();
+        assert!((celsius - 100.).abs() < 0.01);
+    }
+
+    #[test]
+    fn reject_impossible_temperature() {
+        use acpi_client::Units;
+
+        assert!(acpi_client::try_convert_to_celsius(-5., Units::Kelvin).is_err());
+        assert!(acpi_client::try_convert_to_celsius(-500., Units::Fahrenheit).is_err());
+        assert!(acpi_client::try_convert_from_celsius(-300., Units::Kelvin).is_err());
+        // The infallible versions pass the value through
+        assert!(acpi_client::convert_to_celsius(-5., Units::Kelvin) < -273.);
+    }
+
+    #[test]
+    fn select_hottest_and_coolest() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        for (name, temperature) in &[
+            ("thermal_zone0", "47000"),
+            ("thermal_zone1", "83000"),
+            ("thermal_zone2", "29500"),
+        ] {
+            let mock_path = dir.path().join(name);
+            std::fs::create_dir(&mock_path).unwrap();
+            let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+            writeln!(file, "{}", temperature).unwrap();
+        }
+
+        let sensors =
+            acpi_client::get_thermal_sensor_info(dir.path(), acpi_client::Units::Celsius).unwrap();
+        assert_eq!(sensors.len(), 3);
+        let hottest = acpi_client::hottest_sensor(&sensors).unwrap();
+        assert_eq!(hottest.name, "thermal_zone1");
+        let coolest = acpi_client::coolest_sensor(&sensors).unwrap();
+        assert_eq!(coolest.name, "thermal_zone2");
+        assert!(acpi_client::hottest_sensor(&[]).is_none());
+
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn parallel_scan_matches_sequential() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        for index in 0..12 {
+            let mock_path = dir.path().join(format!("thermal_zone{}", index));
+            std::fs::create_dir(&mock_path).unwrap();
+            let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+            writeln!(file, "{}", 30000 + index * 1500).unwrap();
+            let mut file = std::fs::File::create(mock_path.join("trip_point_0_temp")).unwrap();
+            writeln!(file, "95000").unwrap();
+            let mut file = std::fs::File::create(mock_path.join("trip_point_0_type")).unwrap();
+            writeln!(file, "critical").unwrap();
+        }
+        std::fs::create_dir(dir.path().join("cooling_device0")).unwrap();
+
+        let mut sequential =
+            acpi_client::get_thermal_sensor_info(dir.path(), acpi_client::Units::Celsius).unwrap();
+        sequential.sort_by(|a, b| a.name.cmp(&b.name));
+        let parallel =
+            acpi_client::get_thermal_sensor_info_parallel(dir.path(), acpi_client::Units::Celsius)
+                .unwrap();
+
+        assert_eq!(parallel.len(), 12);
+        assert_eq!(parallel.len(), sequential.len());
+        for (a, b) in parallel.iter().zip(sequential.iter()) {
+            assert_eq!(a.name, b.name);
+            assert_eq!(a.current_temperature, b.current_temperature);
+            assert_eq!(a.trip_points.len(), b.trip_points.len());
+        }
+
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn trip_point_temperature_in_each_unit() {
+        use acpi_client::{TripPoint, Units};
+
+        let trip_point = TripPoint {
+            number: 0,
+            action_type: String::from("critical"),
+            temperature: 100.,
+            units: Units::Celsius,
+        };
+        assert!((trip_point.temperature_in(Units::Celsius) - 100.).abs() < 0.01);
+        assert!((trip_point.temperature_in(Units::Fahrenheit) - 212.).abs() < 0.01);
+        assert!((trip_point.temperature_in(Units::Kelvin) - 373.15).abs() < 0.01);
+    }
+
+    #[test]
+    fn parse_zone_type() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("thermal_zone0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "47000").unwrap();
+
+        let sensor =
+            acpi_client::ThermalSensor::new(&mock_path, acpi_client::Units::Celsius).unwrap();
+        assert_eq!(sensor.zone_type, None);
+
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "x86_pkg_temp").unwrap();
+
+        let sensor =
+            acpi_client::ThermalSensor::new(&mock_path, acpi_client::Units::Celsius).unwrap();
+        assert_eq!(sensor.zone_type, Some(String::from("x86_pkg_temp")));
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn resolve_units_from_environment() {
+        use acpi_client::Units;
+
+        assert_eq!("Fahrenheit".parse::<Units>().unwrap(), Units::Fahrenheit);
+        assert_eq!("k".parse::<Units>().unwrap(), Units::Kelvin);
+        assert!("rankine".parse::<Units>().is_err());
+
+        std::env::remove_var(acpi_client::UNITS_ENV_VAR);
+        assert_eq!(acpi_client::resolve_units(None), Units::Celsius);
+
+        std::env::set_var(acpi_client::UNITS_ENV_VAR, "fahrenheit");
+        assert_eq!(acpi_client::resolve_units(None), Units::Fahrenheit);
+        assert_eq!(
+            acpi_client::resolve_units(Some(Units::Kelvin)),
+            Units::Kelvin
+        );
+
+        std::env::set_var(acpi_client::UNITS_ENV_VAR, "rankine");
+        assert_eq!(acpi_client::resolve_units(None), Units::Celsius);
+        std::env::remove_var(acpi_client::UNITS_ENV_VAR);
+    }
+
+    #[test]
+    fn look_up_trip_point() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("thermal_zone0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "47000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("trip_point_0_temp")).unwrap();
+        writeln!(file, "105000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("trip_point_0_type")).unwrap();
+        writeln!(file, "critical").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("trip_point_1_temp")).unwrap();
+        writeln!(file, "90000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("trip_point_1_type")).unwrap();
+        writeln!(file, "passive").unwrap();
+
+        let sensor =
+            acpi_client::ThermalSensor::new(&mock_path, acpi_client::Units::Celsius).unwrap();
+        let trip_point = sensor.trip_point(1).unwrap();
+        assert_eq!(trip_point.action_type, "passive");
+        assert!((trip_point.temperature - 90.).abs() < 0.01);
+        assert!(sensor.trip_point(5).is_none());
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn unit_symbols_and_names() {
+        use acpi_client::Units;
+
+        assert_eq!(Units::Celsius.symbol(), "°C");
+        assert_eq!(Units::Fahrenheit.symbol(), "°F");
+        assert_eq!(Units::Kelvin.symbol(), "K");
+        assert_eq!(Units::Celsius.name(), "Celsius");
+        assert_eq!(Units::Fahrenheit.name(), "Fahrenheit");
+        assert_eq!(Units::Kelvin.name(), "Kelvin");
+    }
+
+    #[test]
+    fn parse_emulated_temperature() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("thermal_zone0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "47000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("emul_temp")).unwrap();
+        writeln!(file, "0").unwrap();
+
+        let sensor =
+            acpi_client::ThermalSensor::new(&mock_path, acpi_client::Units::Celsius).unwrap();
+        assert_eq!(sensor.emulated_temperature, None);
+
+        let mut file = std::fs::File::create(mock_path.join("emul_temp")).unwrap();
+        writeln!(file, "100000").unwrap();
+
+        let sensor =
+            acpi_client::ThermalSensor::new(&mock_path, acpi_client::Units::Fahrenheit).unwrap();
+        assert!((sensor.emulated_temperature.unwrap() - 212.).abs() < 0.01);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn average_across_units() {
+        use acpi_client::Units;
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        for (name, temperature) in &[
+            ("thermal_zone0", "40000"),
+            ("thermal_zone1", "60000"),
+            ("thermal_zone2", "50000"),
+        ] {
+            let mock_path = dir.path().join(name);
+            std::fs::create_dir(&mock_path).unwrap();
+            let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+            writeln!(file, "{}", temperature).unwrap();
+        }
+
+        let sensors = vec![
+            acpi_client::ThermalSensor::new(&dir.path().join("thermal_zone0"), Units::Celsius)
+                .unwrap(),
+            acpi_client::ThermalSensor::new(&dir.path().join("thermal_zone1"), Units::Fahrenheit)
+                .unwrap(),
+            acpi_client::ThermalSensor::new(&dir.path().join("thermal_zone2"), Units::Kelvin)
+                .unwrap(),
+        ];
+        let average = acpi_client::average_temperature(&sensors, Units::Celsius).unwrap();
+        assert!((average - 50.).abs() < 0.01);
+        let average = acpi_client::average_temperature(&sensors, Units::Fahrenheit).unwrap();
+        assert!((average - 122.).abs() < 0.01);
+        assert!(acpi_client::average_temperature(&[], Units::Celsius).is_none());
+
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn try_from_path_in_celsius() {
+        use std::convert::TryFrom;
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("thermal_zone0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "47000").unwrap();
+
+        let sensor = acpi_client::ThermalSensor::try_from(mock_path.as_path()).unwrap();
+        assert_eq!(sensor.units, acpi_client::Units::Celsius);
+        assert!((sensor.current_temperature - 47.).abs() < 0.01);
+        let sensor = acpi_client::ThermalSensor::try_from(mock_path.clone()).unwrap();
+        assert_eq!(sensor.name, "thermal_zone0");
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn skip_unreadable_trip_points() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("thermal_zone0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "47000").unwrap();
+        for number in 0..4 {
+            let mut file =
+                std::fs::File::create(mock_path.join(format!("trip_point_{}_type", number)))
+                    .unwrap();
+            writeln!(file, "passive").unwrap();
+        }
+        let mut file = std::fs::File::create(mock_path.join("trip_point_0_temp")).unwrap();
+        writeln!(file, "105000").unwrap();
+        // A trip point whose temperature can't be read
+        std::fs::create_dir(mock_path.join("trip_point_1_temp")).unwrap();
+        // A disabled trip point
+        let mut file = std::fs::File::create(mock_path.join("trip_point_2_temp")).unwrap();
+        writeln!(file, "-1").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("trip_point_3_temp")).unwrap();
+        writeln!(file, "90000").unwrap();
+
+        let sensor =
+            acpi_client::ThermalSensor::new(&mock_path, acpi_client::Units::Celsius).unwrap();
+        let numbers: Vec<u8> = sensor.trip_points.iter().map(|tp| tp.number).collect();
+        assert_eq!(numbers, vec![0, 3]);
+        assert!((sensor.trip_point(3).unwrap().temperature - 90.).abs() < 0.01);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn write_emulated_temperature() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("thermal_zone0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "47000").unwrap();
+
+        let sensor =
+            acpi_client::ThermalSensor::new(&mock_path, acpi_client::Units::Celsius).unwrap();
+        assert!(sensor.set_emulated_temperature(Some(95.)).is_err());
+        assert!(!mock_path.join("emul_temp").exists());
+
+        let mut file = std::fs::File::create(mock_path.join("emul_temp")).unwrap();
+        writeln!(file, "0").unwrap();
+
+        sensor.set_emulated_temperature(Some(95.5)).unwrap();
+        let sensor =
+            acpi_client::ThermalSensor::new(&mock_path, acpi_client::Units::Celsius).unwrap();
+        assert!((sensor.emulated_temperature.unwrap() - 95.5).abs() < 0.01);
+
+        sensor.set_emulated_temperature(Some(-12.5)).unwrap();
+        let sensor =
+            acpi_client::ThermalSensor::new(&mock_path, acpi_client::Units::Celsius).unwrap();
+        assert!((sensor.emulated_temperature.unwrap() + 12.5).abs() < 0.01);
+
+        sensor.set_emulated_temperature(Some(0.)).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(mock_path.join("emul_temp")).unwrap(),
+            "1"
+        );
+        sensor.set_emulated_temperature(Some(-0.0001)).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(mock_path.join("emul_temp")).unwrap(),
+            "-1"
+        );
+
+        assert!(sensor.set_emulated_temperature(Some(-300.)).is_err());
+        sensor.set_emulated_temperature(None).unwrap();
+        let sensor =
+            acpi_client::ThermalSensor::new(&mock_path, acpi_client::Units::Celsius).unwrap();
+        assert_eq!(sensor.emulated_temperature, None);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn critical_comparison_in_any_units() {
+        use acpi_client::{TripPoint, Units};
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("thermal_zone0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "95000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("trip_point_0_temp")).unwrap();
+        writeln!(file, "105000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("trip_point_0_type")).unwrap();
+        writeln!(file, "critical").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("trip_point_1_temp")).unwrap();
+        writeln!(file, "90000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("trip_point_1_type")).unwrap();
+        writeln!(file, "passive").unwrap();
+
+        // 203°F is below the critical 221°F, though past the passive trip point
+        let mut sensor = acpi_client::ThermalSensor::new(&mock_path, Units::Fahrenheit).unwrap();
+        assert!(!sensor.is_critical());
+
+        // A critical trip point in different units is still compared correctly
+        sensor.trip_points.push(TripPoint {
+            number: 2,
+            action_type: String::from("critical"),
+            temperature: 95.,
+            units: Units::Celsius,
+        });
+        assert!(sensor.is_critical());
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn headroom_to_critical_trip_points() {
+        use acpi_client::Units;
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        for (name, temperature, critical) in &[
+            ("thermal_zone0", "60000", "105000"),
+            ("thermal_zone1", "80000", "100000"),
+            ("thermal_zone2", "90000", ""),
+        ] {
+            let mock_path = dir.path().join(name);
+            std::fs::create_dir(&mock_path).unwrap();
+            let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+            writeln!(file, "{}", temperature).unwrap();
+            if !critical.is_empty() {
+                let mut file = std::fs::File::create(mock_path.join("trip_point_0_temp")).unwrap();
+                writeln!(file, "{}", critical).unwrap();
+                let mut file = std::fs::File::create(mock_path.join("trip_point_0_type")).unwrap();
+                writeln!(file, "critical").unwrap();
+            }
+        }
+
+        let zone = |name: &str, units: Units| {
+            acpi_client::ThermalSensor::new(&dir.path().join(name), units).unwrap()
+        };
+        let sensors = vec![
+            zone("thermal_zone0", Units::Celsius),
+            zone("thermal_zone1", Units::Fahrenheit),
+            zone("thermal_zone2", Units::Celsius),
+        ];
+        let headroom = acpi_client::thermal_headroom(&sensors).unwrap();
+        assert!((headroom - 20.).abs() < 0.01);
+
+        let sensors = vec![zone("thermal_zone2", Units::Celsius)];
+        assert!(acpi_client::thermal_headroom(&sensors).is_none());
+
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn parse_disabled_mode() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("thermal_zone0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "47000").unwrap();
+
+        let sensor = acpi_client::ThermalSensor::new(&mock_path, acpi_client::Units::Celsius);
+        assert_eq!(sensor.unwrap().mode, None);
+
+        let mut file = std::fs::File::create(mock_path.join("mode")).unwrap();
+        writeln!(file, "disabled").unwrap();
+        let sensor =
+            acpi_client::ThermalSensor::new(&mock_path, acpi_client::Units::Celsius).unwrap();
+        assert_eq!(sensor.mode, Some(acpi_client::ThermalMode::Disabled));
+        assert_eq!(sensor.current_temperature, 47.);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn format_as_acpi_line() {
+        use acpi_client::{ThermalSensor, Units};
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("thermal_zone0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "47000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("trip_point_0_temp")).unwrap();
+        writeln!(file, "100000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("trip_point_0_type")).unwrap();
+        writeln!(file, "critical").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("trip_point_1_temp")).unwrap();
+        writeln!(file, "70000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("trip_point_1_type")).unwrap();
+        writeln!(file, "active").unwrap();
+
+        let sensor = ThermalSensor::new(&mock_path, Units::Celsius).unwrap();
+        assert_eq!(sensor.to_acpi_line(0), "Thermal 0: ok, 47.0 degrees C");
+        let sensor = ThermalSensor::new(&mock_path, Units::Fahrenheit).unwrap();
+        assert_eq!(sensor.to_acpi_line(0), "Thermal 0: ok, 116.6 degrees F");
+
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "75000").unwrap();
+        let sensor = ThermalSensor::new(&mock_path, Units::Celsius).unwrap();
+        assert_eq!(sensor.to_acpi_line(1), "Thermal 1: active, 75.0 degrees C");
+
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "101000").unwrap();
+        let sensor = ThermalSensor::new(&mock_path, Units::Celsius).unwrap();
+        assert_eq!(
+            sensor.to_acpi_line(0),
+            "Thermal 0: critical, 101.0 degrees C"
+        );
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn parse_cooling_device_trip_points() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("thermal_zone0");
+        std::fs::create_dir(&mock_path).unwrap();
+        std::fs::create_dir(dir.path().join("cooling_device0")).unwrap();
+        std::fs::create_dir(dir.path().join("cooling_device1")).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "47000").unwrap();
+        std::os::unix::fs::symlink("../cooling_device0", mock_path.join("cdev0")).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("cdev0_trip_point")).unwrap();
+        writeln!(file, "2").unwrap();
+        std::os::unix::fs::symlink("../cooling_device1", mock_path.join("cdev1")).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("cdev1_trip_point")).unwrap();
+        writeln!(file, "-1").unwrap();
+
+        let sensor =
+            acpi_client::ThermalSensor::new(&mock_path, acpi_client::Units::Celsius).unwrap();
+        assert_eq!(
+            sensor.cooling_device_trip_points,
+            vec![
+                (String::from("cooling_device0"), 2),
+                (String::from("cooling_device1"), -1)
+            ]
+        );
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn find_last_crossed_trip_point() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("thermal_zone0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "30000").unwrap();
+        for (number, temperature, action_type) in &[
+            (0, "100000", "critical"),
+            (1, "80000", "passive"),
+            (2, "60000", "active"),
+            (3, "45000", "active"),
+        ] {
+            let mut file =
+                std::fs::File::create(mock_path.join(format!("trip_point_{}_temp", number)))
+                    .unwrap();
+            writeln!(file, "{}", temperature).unwrap();
+            let mut file =
+                std::fs::File::create(mock_path.join(format!("trip_point_{}_type", number)))
+                    .unwrap();
+            writeln!(file, "{}", action_type).unwrap();
+        }
+
+        let sensor =
+            acpi_client::ThermalSensor::new(&mock_path, acpi_client::Units::Celsius).unwrap();
+        assert!(sensor.last_crossed_trip_point().is_none());
+
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "65000").unwrap();
+        let sensor =
+            acpi_client::ThermalSensor::new(&mock_path, acpi_client::Units::Fahrenheit).unwrap();
+        assert_eq!(sensor.last_crossed_trip_point().unwrap().number, 2);
+
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "80000").unwrap();
+        let sensor =
+            acpi_client::ThermalSensor::new(&mock_path, acpi_client::Units::Celsius).unwrap();
+        assert_eq!(sensor.last_crossed_trip_point().unwrap().number, 1);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn categorize_zone_types() {
+        use acpi_client::ThermalCategory;
+        use std::io::Write;
+
+        assert_eq!(ThermalCategory::from("x86_pkg_temp"), ThermalCategory::Cpu);
+        assert_eq!(ThermalCategory::from("cpu-thermal"), ThermalCategory::Cpu);
+        assert_eq!(ThermalCategory::from("gpu-thermal"), ThermalCategory::Gpu);
+        assert_eq!(ThermalCategory::from("acpitz"), ThermalCategory::System);
+        assert_eq!(
+            ThermalCategory::from("pch_cannonlake"),
+            ThermalCategory::System
+        );
+        assert_eq!(
+            ThermalCategory::from("iwlwifi_1"),
+            ThermalCategory::Wireless
+        );
+        assert_eq!(
+            ThermalCategory::from("INT3400 Thermal"),
+            ThermalCategory::Other(String::from("INT3400 Thermal"))
+        );
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("thermal_zone0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "47000").unwrap();
+
+        let sensor =
+            acpi_client::ThermalSensor::new(&mock_path, acpi_client::Units::Celsius).unwrap();
+        assert_eq!(sensor.category(), ThermalCategory::Other(String::new()));
+
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "x86_pkg_temp").unwrap();
+        let sensor =
+            acpi_client::ThermalSensor::new(&mock_path, acpi_client::Units::Celsius).unwrap();
+        assert_eq!(sensor.category(), ThermalCategory::Cpu);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn track_rising_temperature() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("thermal_zone0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "47000").unwrap();
+
+        let mut sensor =
+            acpi_client::ThermalSensor::new(&mock_path, acpi_client::Units::Celsius).unwrap();
+        let start = sensor.sampled_at;
+        let mut tracker = acpi_client::TemperatureTracker::new(3);
+        assert_eq!(tracker.update(&sensor), None);
+        assert_eq!(tracker.rate("thermal_zone1"), None);
+
+        for (seconds, temperature) in [(30, 48.), (60, 50.), (90, 53.)] {
+            sensor.sampled_at = start + std::time::Duration::from_secs(seconds);
+            sensor.current_temperature = temperature;
+            tracker.update(&sensor);
+        }
+        // Only the last three readings, from 48 to 53 degrees over a minute, are considered
+        let rate = tracker.rate("thermal_zone0").unwrap();
+        assert!((rate - 5.).abs() < 1e-3);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn sort_sensors_by_temperature() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut sensors = vec![];
+        for (name, temperature) in &[
+            ("thermal_zone0", "47000"),
+            ("thermal_zone1", "83000"),
+            ("thermal_zone2", "29500"),
+            ("thermal_zone3", "47000"),
+        ] {
+            let mock_path = dir.path().join(name);
+            std::fs::create_dir(&mock_path).unwrap();
+            let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+            writeln!(file, "{}", temperature).unwrap();
+            sensors.push(
+                acpi_client::ThermalSensor::new(&mock_path, acpi_client::Units::Celsius).unwrap(),
+            );
+        }
+
+        let names: Vec<String> = acpi_client::thermal_sensors_sorted(sensors)
+            .into_iter()
+            .map(|sensor| sensor.name)
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "thermal_zone1",
+                "thermal_zone0",
+                "thermal_zone3",
+                "thermal_zone2"
+            ]
+        );
+
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn summarize_hottest_zone() {
+        use acpi_client::Units;
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        for (name, temperature) in &[
+            ("thermal_zone0", "47000"),
+            ("thermal_zone1", "83000"),
+            ("thermal_zone2", "29500"),
+        ] {
+            let mock_path = dir.path().join(name);
+            std::fs::create_dir(&mock_path).unwrap();
+            let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+            writeln!(file, "{}", temperature).unwrap();
+        }
+        let mut file =
+            std::fs::File::create(dir.path().join("thermal_zone1/trip_point_0_temp")).unwrap();
+        writeln!(file, "80000").unwrap();
+        let mut file =
+            std::fs::File::create(dir.path().join("thermal_zone1/trip_point_0_type")).unwrap();
+        writeln!(file, "passive").unwrap();
+
+        let sensors = acpi_client::get_thermal_sensor_info(dir.path(), Units::Celsius).unwrap();
+        assert_eq!(
+            acpi_client::thermal_status_line(&sensors, Units::Celsius),
+            "83°C (thermal_zone1, active)"
+        );
+        assert_eq!(
+            acpi_client::thermal_status_line(&sensors, Units::Fahrenheit),
+            "181°F (thermal_zone1, active)"
+        );
+        assert_eq!(
+            acpi_client::thermal_status_line(&[], Units::Celsius),
+            "no sensors"
+        );
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn report_dual_units() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("thermal_zone0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "40000").unwrap();
+
+        let sensor =
+            acpi_client::ThermalSensor::new(&mock_path, acpi_client::Units::Celsius).unwrap();
+        let (celsius, fahrenheit) = sensor.dual();
+        assert!((celsius - 40.).abs() < 1e-3);
+        assert!((fahrenheit - 104.).abs() < 1e-3);
+
+        let sensor =
+            acpi_client::ThermalSensor::new(&mock_path, acpi_client::Units::Kelvin).unwrap();
+        let (celsius, fahrenheit) = sensor.dual();
+        assert!((celsius - 40.).abs() < 1e-3);
+        assert!((fahrenheit - 104.).abs() < 1e-3);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn report_zone_entering_critical_once() {
+        use acpi_client::Units;
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("thermal_zone0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("trip_point_0_temp")).unwrap();
+        writeln!(file, "95000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("trip_point_0_type")).unwrap();
+        writeln!(file, "critical").unwrap();
+
+        let mut watcher = acpi_client::CriticalWatcher::new();
+        let mut entered = vec![];
+        for temperature in &["90000", "96000", "97000", "94000", "95000"] {
+            let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+            writeln!(file, "{}", temperature).unwrap();
+            let sensors = acpi_client::get_thermal_sensor_info(dir.path(), Units::Celsius).unwrap();
+            entered.push(watcher.update(&sensors).len());
+        }
+        assert_eq!(entered, vec![0, 1, 0, 0, 1]);
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn watch_until_stopped() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let root = dir.path().join("thermal");
+        let mock_path = root.join("thermal_zone0");
+        std::fs::create_dir_all(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "101000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("trip_point_0_temp")).unwrap();
+        writeln!(file, "95000").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("trip_point_0_type")).unwrap();
+        writeln!(file, "critical").unwrap();
+        drop(file);
+
+        // The zone cools after the first reading and heats up again after the second
+        let temperatures = ["90000", "101000"];
+        let mut readings = 0;
+        let mut calls = vec![];
+        let result = acpi_client::watch_thermal_critical(
+            &root,
+            acpi_client::Units::Celsius,
+            std::time::Duration::from_millis(0),
+            |sensor| calls.push(sensor.name.clone()),
+            || {
+                readings += 1;
+                match temperatures.get(readings - 1) {
+                    Some(temperature) => {
+                        std::fs::write(mock_path.join("temp"), temperature).unwrap();
+                        false
+                    }
+                    None => true,
+                }
+            },
+        );
+        assert!(result.is_ok());
+        assert_eq!(readings, 3);
+        assert_eq!(calls, vec!["thermal_zone0", "thermal_zone0"]);
+
+        // Zones which can no longer be read end the watch with an error
+        let result = acpi_client::watch_thermal_critical(
+            &root,
+            acpi_client::Units::Celsius,
+            std::time::Duration::from_millis(0),
+            |_| {},
+            || {
+                std::fs::remove_dir_all(&root).unwrap();
+                false
+            },
+        );
+        assert!(result.is_err());
+
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn read_hwmon_sub_temperatures() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("thermal_zone0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "47000").unwrap();
+
+        let sensor =
+            acpi_client::ThermalSensor::new(&mock_path, acpi_client::Units::Celsius).unwrap();
+        assert!(sensor.sub_temperatures.is_empty());
+
+        let hwmon_path = mock_path.join("hwmon3");
+        std::fs::create_dir(&hwmon_path).unwrap();
+        let mut file = std::fs::File::create(hwmon_path.join("temp1_input")).unwrap();
+        writeln!(file, "45000").unwrap();
+        let mut file = std::fs::File::create(hwmon_path.join("temp1_label")).unwrap();
+        writeln!(file, "Core 0").unwrap();
+        let mut file = std::fs::File::create(hwmon_path.join("temp2_input")).unwrap();
+        writeln!(file, "50000").unwrap();
+
+        let sensor =
+            acpi_client::ThermalSensor::new(&mock_path, acpi_client::Units::Fahrenheit).unwrap();
+        assert_eq!(
+            sensor.sub_temperatures,
+            vec![
+                (String::from("Core 0"), 113.),
+                (String::from("hwmon3/temp2"), 122.)
+            ]
+        );
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn scan_thermal_zones_from_source() {
+        use crate::common::MemorySource;
+
+        let root = std::path::Path::new("/nonexistent/thermal");
+        let source = MemorySource::default()
+            .attribute(root.join("cooling_device0/type"), "Fan")
+            .attribute(root.join("thermal_zone0/type"), "acpitz")
+            .attribute(root.join("thermal_zone0/temp"), 47000)
+            .attribute(root.join("thermal_zone0/trip_point_0_temp"), 95000)
+            .attribute(root.join("thermal_zone0/trip_point_0_type"), "critical")
+            .attribute(root.join("thermal_zone0/hwmon1/temp1_input"), 51000)
+            .attribute(root.join("thermal_zone0/hwmon1/temp1_label"), "Package");
+
+        let sensors = acpi_client::get_thermal_sensor_info_from_source(
+            &source,
+            root,
+            acpi_client::Units::Celsius,
+        )
+        .unwrap();
+        assert_eq!(sensors.len(), 1);
+        assert_eq!(sensors[0].name, "thermal_zone0");
+        assert_eq!(sensors[0].zone_type, Some(String::from("acpitz")));
+        assert_eq!(sensors[0].current_temperature, 47.);
+        assert_eq!(sensors[0].trip_points.len(), 1);
+        assert_eq!(sensors[0].trip_points[0].action_type, "critical");
+        assert_eq!(
+            sensors[0].sub_temperatures,
+            vec![(String::from("Package"), 51.)]
+        );
+    }
+}
@@ -0,0 +1,37 @@
+#![cfg(feature = "timing")]
+
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn record_timing_per_device() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        for name in &["thermal_zone0", "thermal_zone1"] {
+            let mock_path = dir.path().join(name);
+            std::fs::create_dir(&mock_path).unwrap();
+            let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+            writeln!(file, "47000").unwrap();
+        }
+        let mock_path = dir.path().join("thermal_zone2");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("temp")).unwrap();
+        writeln!(file, "invalid").unwrap();
+
+        let (sensors, mut timings) = acpi_client::timing::get_thermal_sensor_info_timed(
+            dir.path(),
+            acpi_client::Units::Celsius,
+        )
+        .unwrap();
+        assert_eq!(sensors.len(), 2);
+        timings.sort();
+        let names: Vec<&str> = timings.iter().map(|(name, _)| name.as_str()).collect();
+        assert_eq!(
+            names,
+            vec!["thermal_zone0", "thermal_zone1", "thermal_zone2"]
+        );
+
+        drop(file);
+        dir.close().unwrap();
+    }
+}
@@ -0,0 +1,43 @@
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn parse_mock_ups() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mock_path = dir.path().join("hiddev0");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "UPS").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("capacity")).unwrap();
+        writeln!(file, "87").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("status")).unwrap();
+        writeln!(file, "Discharging").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("time_to_empty_now")).unwrap();
+        writeln!(file, "1920").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("model_name")).unwrap();
+        writeln!(file, "Back-UPS ES 700").unwrap();
+
+        let mock_path = dir.path().join("ACAD");
+        std::fs::create_dir(&mock_path).unwrap();
+        let mut file = std::fs::File::create(mock_path.join("type")).unwrap();
+        writeln!(file, "Mains").unwrap();
+        let mut file = std::fs::File::create(mock_path.join("online")).unwrap();
+        writeln!(file, "0").unwrap();
+
+        let upses = acpi_client::get_ups_info(dir.path()).unwrap();
+        assert_eq!(upses.len(), 1);
+        let ups = &upses[0];
+        assert_eq!(ups.name, "hiddev0");
+        assert_eq!(ups.model_name, Some(String::from("Back-UPS ES 700")));
+        assert!((ups.percentage - 87.).abs() < 0.01);
+        assert_eq!(ups.state, acpi_client::ChargingState::Discharging);
+        assert_eq!(
+            ups.time_to_empty,
+            Some(std::time::Duration::from_secs(1920))
+        );
+
+        drop(file);
+        dir.close().unwrap();
+    }
+}
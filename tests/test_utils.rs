@@ -0,0 +1,148 @@
+#[cfg(test)]
+mod tests {
+    #[test]
+    fn parse_decimal_value() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut file = std::fs::File::create(dir.path().join("energy_now")).unwrap();
+        writeln!(file, "5850000.0").unwrap();
+        let mut file = std::fs::File::create(dir.path().join("charge_now")).unwrap();
+        writeln!(file, "5850000").unwrap();
+        let mut file = std::fs::File::create(dir.path().join("voltage_now")).unwrap();
+        writeln!(file, "not a number").unwrap();
+
+        assert_eq!(
+            acpi_client::utils::parse_file_to_i32(&dir.path().join("energy_now"), 1000).unwrap(),
+            5850
+        );
+        assert_eq!(
+            acpi_client::utils::parse_file_to_i32(&dir.path().join("charge_now"), 1).unwrap(),
+            5850000
+        );
+        assert!(acpi_client::utils::parse_file_to_i32(&dir.path().join("voltage_now"), 1).is_err());
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn clamp_oversized_value() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        let mut file = std::fs::File::create(dir.path().join("charge_now")).unwrap();
+        writeln!(file, "4294967296").unwrap();
+        let mut file = std::fs::File::create(dir.path().join("energy_now")).unwrap();
+        writeln!(file, "4294967296").unwrap();
+        let mut file = std::fs::File::create(dir.path().join("current_now")).unwrap();
+        writeln!(file, "-99999999999999999999999").unwrap();
+
+        assert_eq!(
+            acpi_client::utils::parse_file_to_i32(&dir.path().join("charge_now"), 1).unwrap(),
+            i32::MAX
+        );
+        assert_eq!(
+            acpi_client::utils::parse_file_to_i32(&dir.path().join("energy_now"), 1000).unwrap(),
+            4294967
+        );
+        assert_eq!(
+            acpi_client::utils::parse_file_to_i32(&dir.path().join("current_now"), 1).unwrap(),
+            i32::MIN
+        );
+
+        drop(file);
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn match_name_globs() {
+        use acpi_client::utils::name_matches;
+
+        assert!(name_matches("BAT0", "BAT0"));
+        assert!(!name_matches("BAT0", "BAT1"));
+        assert!(name_matches("BAT*", "BAT0"));
+        assert!(name_matches("BAT*", "BAT"));
+        assert!(name_matches("BAT*", "BATT12"));
+        assert!(!name_matches("BAT*", "CMB0"));
+        assert!(name_matches("BAT?", "BAT1"));
+        assert!(!name_matches("BAT?", "BAT"));
+        assert!(!name_matches("BAT?", "BAT10"));
+        assert!(name_matches("*_battery_*", "hidpp_battery_0"));
+        assert!(name_matches("*", ""));
+        assert!(!name_matches("", "BAT0"));
+    }
+
+    #[test]
+    fn parse_values_with_units_suffix() {
+        use std::io::Write;
+
+        let dir = tempfile::tempdir().unwrap();
+        for (name, contents) in &[
+            ("voltage_now", "15045000 uV"),
+            ("current_now", "-599000mA"),
+            ("charge_now", "1660000"),
+            ("status", "Discharging"),
+        ] {
+            let mut file = std::fs::File::create(dir.path().join(name)).unwrap();
+            writeln!(file, "{}", contents).unwrap();
+        }
+
+        let parse = |name: &str, scalar: i32| {
+            acpi_client::utils::parse_file_to_i32(&dir.path().join(name), scalar)
+        };
+        assert_eq!(parse("voltage_now", 1000).unwrap(), 15045);
+        assert_eq!(parse("current_now", 1).unwrap(), -599000);
+        assert_eq!(parse("charge_now", 1000).unwrap(), 1660);
+        assert!(parse("status", 1).is_err());
+
+        dir.close().unwrap();
+    }
+
+    #[test]
+    fn battery_type_ignores_case_and_whitespace() {
+        use acpi_client::utils::is_battery_type;
+
+        assert!(is_battery_type("Battery"));
+        assert!(is_battery_type("BATTERY"));
+        assert!(is_battery_type(" battery\n"));
+        assert!(!is_battery_type("Mains"));
+        assert!(!is_battery_type("batteries"));
+        assert_eq!(
+            acpi_client::PowerSupplyType::from(" BATTERY\n"),
+            acpi_client::PowerSupplyType::Battery
+        );
+    }
+
+    struct ErrorSource;
+
+    impl acpi_client::AttributeSource for ErrorSource {
+        fn read(&self, path: &std::path::Path) -> std::io::Result<String> {
+            let kind = match path.file_name().and_then(|name| name.to_str()) {
+                Some("missing") => std::io::ErrorKind::NotFound,
+                Some("denied") => std::io::ErrorKind::PermissionDenied,
+                Some("malformed") => return Ok(String::from("n/a")),
+                Some("valid") => return Ok(String::from("42")),
+                _ => std::io::ErrorKind::Other,
+            };
+            Err(std::io::Error::new(kind, "Read failed"))
+        }
+
+        fn exists(&self, _path: &std::path::Path) -> bool {
+            true
+        }
+    }
+
+    #[test]
+    fn optional_attribute_errors_are_none() {
+        use acpi_client::utils::read_optional_attribute_to_i32;
+        use std::path::Path;
+
+        let read = |name: &str| read_optional_attribute_to_i32(&ErrorSource, Path::new(name), 1);
+        assert_eq!(read("valid"), Some(42));
+        assert_eq!(read("missing"), None);
+        assert_eq!(read("denied"), None);
+        assert_eq!(read("malformed"), None);
+        assert_eq!(read("broken"), None);
+    }
+}